@@ -3,7 +3,7 @@ use quote::quote;
 use std::collections::HashSet;
 use syn::{DeriveInput, Type};
 
-use crate::schemable::{FieldMeta, ParsedStruct};
+use crate::schemable::{EnumStorage, FieldMeta, ParsedStruct};
 
 /// Derives the Bindable trait for binding struct fields to SQL parameters.
 pub fn derive_bindable(input: TokenStream) -> TokenStream {
@@ -26,6 +26,16 @@ pub(crate) fn derive_bindable_impl(input: &DeriveInput) -> proc_macro2::TokenStr
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let bind_stmts = gen_bind_statements(&parsed.fields);
     let column_names = gen_bind_column_names(&parsed.fields);
+    let now_add_columns = gen_auto_columns(&parsed.fields, AutoNow::Add);
+    let now_columns = gen_auto_columns(&parsed.fields, AutoNow::Always);
+    let copy_stmts = gen_copy_statements(&parsed.fields);
+    let soft_delete = parsed.container.soft_delete.as_ref().map(|column| {
+        quote! {
+            fn soft_delete_guard() -> Option<&'static str> {
+                Some(#column)
+            }
+        }
+    });
 
     quote! {
         impl #impl_generics ::vyuh::db::Bindable for #ident #ty_generics #where_clause {
@@ -35,6 +45,25 @@ pub(crate) fn derive_bindable_impl(input: &DeriveInput) -> proc_macro2::TokenStr
                 cols
             }
 
+            fn auto_now_add_columns() -> Vec<String> {
+                let mut cols = Vec::new();
+                #(#now_add_columns)*
+                cols
+            }
+
+            fn auto_now_columns() -> Vec<String> {
+                let mut cols = Vec::new();
+                #(#now_columns)*
+                cols
+            }
+
+            fn copy_values(&self, row: &mut ::vyuh::db::CopyRow) -> Result<(), ::sqlx::Error> {
+                #(#copy_stmts)*
+                Ok(())
+            }
+
+            #soft_delete
+
             fn bind_values(
                 &self,
                 args: &mut ::vyuh::db::Arguments<'static>,
@@ -68,6 +97,14 @@ fn gen_where_clause(generics: &mut syn::Generics, fields: &[FieldMeta]) {
                     #ty: ::vyuh::db::Bindable
                 });
             }
+        } else if enum_storage(field).is_some() {
+            if seen.insert(ty_str) {
+                wc.predicates.push(syn::parse_quote! {
+                    #ty: ::vyuh::db::ColumnEnum
+                });
+            }
+        } else if field.column.array {
+            // `bind_array` bounds the field through `ArrayColumn`.
         } else if is_json(field) {
             if seen.insert(ty_str) {
                 wc.predicates.push(syn::parse_quote! {
@@ -100,6 +137,12 @@ fn gen_bind_statements(fields: &[FieldMeta]) -> Vec<proc_macro2::TokenStream> {
 
         let stmt = if is_flatten(field) {
             gen_flatten_bind(ident, &field.ty)
+        } else if let Some(storage) = enum_storage(field) {
+            gen_enum_bind(ident, storage)
+        } else if field.column.array {
+            quote! {
+                ::vyuh::db::bind_array(&self.#ident, args)?;
+            }
         } else if is_json(field) {
             gen_json_bind(ident)
         } else {
@@ -123,14 +166,26 @@ fn gen_flatten_bind(ident: &syn::Ident, ty: &Type) -> proc_macro2::TokenStream {
 fn gen_json_bind(ident: &syn::Ident) -> proc_macro2::TokenStream {
     quote! {
         {
-            let value = ::vyuh::db::serde_json::to_value(&self.#ident)
+            let value = ::serde_json::to_value(&self.#ident)
                 .map_err(|e| ::sqlx::Error::Decode(Box::new(e)))?;
             ::sqlx::Arguments::add(args, value)
-                .map_err(::sqlx::Error::Decode)?;;
+                .map_err(::sqlx::Error::Decode)?;
         }
     }
 }
 
+/// Generate bind statement for a `ColumnEnum` field.
+fn gen_enum_bind(ident: &syn::Ident, storage: EnumStorage) -> proc_macro2::TokenStream {
+    match storage {
+        EnumStorage::Text => quote! {
+            ::vyuh::db::bind_enum_text(&self.#ident, args)?;
+        },
+        EnumStorage::Int => quote! {
+            ::vyuh::db::bind_enum_int(&self.#ident, args)?;
+        },
+    }
+}
+
 /// Generate bind statement for scalar field.
 fn gen_scalar_bind(ident: &syn::Ident) -> proc_macro2::TokenStream {
     quote! {
@@ -141,9 +196,15 @@ fn gen_scalar_bind(ident: &syn::Ident) -> proc_macro2::TokenStream {
     }
 }
 
-/// Check if field should be skipped (column only).
+/// Check if field should be skipped (column only). Auto timestamps are
+/// written by SQL, so they are never bound either.
 fn is_skip(field: &FieldMeta) -> bool {
-    field.column.skip || is_reference(field)
+    field.column.skip || is_reference(field) || is_auto(field)
+}
+
+/// Check if the column is an `auto_now_add` or `auto_now` timestamp.
+fn is_auto(field: &FieldMeta) -> bool {
+    field.column.auto_now_add || field.column.auto_now
 }
 
 /// Check if field is a reference to another model.
@@ -156,6 +217,11 @@ fn is_flatten(field: &FieldMeta) -> bool {
     field.column.flatten
 }
 
+/// `ColumnEnum` storage from `#[column(store_as = "...")]`, checked while parsing.
+fn enum_storage(field: &FieldMeta) -> Option<EnumStorage> {
+    field.column.enum_storage().ok().flatten()
+}
+
 /// Check if field should be JSON-serialized (column only).
 fn is_json(field: &FieldMeta) -> bool {
     field.column.json
@@ -176,13 +242,101 @@ fn gen_bind_column_names(fields: &[FieldMeta]) -> Vec<proc_macro2::TokenStream>
                 cols.extend(<#ty as ::vyuh::db::Bindable>::bind_column_names());
             });
         } else {
-            let col_name = field
-                .column
-                .name
-                .as_ref()
-                .map(|lit| lit.value())
-                .or_else(|| field.ident.as_ref().map(|i| i.to_string()))
-                .unwrap_or_default();
+            let col_name = column_name(field);
+            stmts.push(quote! {
+                cols.push(#col_name.to_string());
+            });
+        }
+    }
+
+    stmts
+}
+
+/// Generate the copy_values implementation: each bound column encoded the
+/// way `gen_bind_statements` binds it.
+fn gen_copy_statements(fields: &[FieldMeta]) -> Vec<proc_macro2::TokenStream> {
+    let mut stmts = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        let Some(ident) = &field.ident else {
+            continue;
+        };
+
+        if is_skip(field) {
+            continue;
+        }
+
+        let stmt = if is_flatten(field) {
+            let ty = &field.ty;
+            quote! {
+                <#ty as ::vyuh::db::Bindable>::copy_values(&self.#ident, row)?;
+            }
+        } else if let Some(storage) = enum_storage(field) {
+            match storage {
+                EnumStorage::Text => quote! {
+                    ::vyuh::db::copy_enum_text(&self.#ident, row)?;
+                },
+                EnumStorage::Int => quote! {
+                    ::vyuh::db::copy_enum_int(&self.#ident, row)?;
+                },
+            }
+        } else if field.column.array {
+            quote! {
+                ::vyuh::db::copy_array(&self.#ident, row)?;
+            }
+        } else if is_json(field) {
+            quote! {
+                {
+                    let value = ::serde_json::to_value(&self.#ident)
+                        .map_err(|e| ::sqlx::Error::Encode(Box::new(e)))?;
+                    row.push(&value)?;
+                }
+            }
+        } else {
+            quote! {
+                row.push(&self.#ident)?;
+            }
+        };
+
+        stmts.push(stmt);
+    }
+
+    stmts
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AutoNow {
+    Add,
+    Always,
+}
+
+/// Generate the auto_now_add_columns / auto_now_columns implementations,
+/// including those of flattened fields.
+fn gen_auto_columns(fields: &[FieldMeta], kind: AutoNow) -> Vec<proc_macro2::TokenStream> {
+    let mut stmts = Vec::new();
+
+    for field in fields {
+        if field.column.skip || is_reference(field) {
+            continue;
+        }
+        if is_flatten(field) {
+            let ty = &field.ty;
+            stmts.push(match kind {
+                AutoNow::Add => quote! {
+                    cols.extend(<#ty as ::vyuh::db::Bindable>::auto_now_add_columns());
+                },
+                AutoNow::Always => quote! {
+                    cols.extend(<#ty as ::vyuh::db::Bindable>::auto_now_columns());
+                },
+            });
+            continue;
+        }
+        let wanted = match kind {
+            AutoNow::Add => field.column.auto_now_add && !field.column.auto_now,
+            AutoNow::Always => field.column.auto_now,
+        };
+        if wanted {
+            let col_name = column_name(field);
             stmts.push(quote! {
                 cols.push(#col_name.to_string());
             });
@@ -191,3 +345,13 @@ fn gen_bind_column_names(fields: &[FieldMeta]) -> Vec<proc_macro2::TokenStream>
 
     stmts
 }
+
+fn column_name(field: &FieldMeta) -> String {
+    field
+        .column
+        .name
+        .as_ref()
+        .map(|lit| lit.value())
+        .or_else(|| field.ident.as_ref().map(|i| i.to_string()))
+        .unwrap_or_default()
+}
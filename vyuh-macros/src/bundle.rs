@@ -1,5 +1,6 @@
 use proc_macro::TokenStream;
 use quote::quote;
+use syn::parse::Parse as _;
 use syn::spanned::Spanned;
 use syn::{Ident, Path, Token, parse_macro_input};
 
@@ -26,11 +27,16 @@ pub(crate) fn parse_bundle(input: TokenStream) -> TokenStream {
         })
         .collect();
 
+    let requires = input_parsed.requires.iter().map(|ty| {
+        quote! { .layer(::vyuh::routes::Require::<#ty>::new()) }
+    });
+
     let expanded = quote! {
         {
             ::vyuh::bundles::bundle([
                 #(#bundle_part_setup)*
             ])
+            #(#requires)*
         }
     };
 
@@ -39,15 +45,22 @@ pub(crate) fn parse_bundle(input: TokenStream) -> TokenStream {
 
 struct BundleInput {
     handlers: Vec<Path>,
+    /// Extractors from `requires = [...]`, run once before every route.
+    requires: Vec<syn::Type>,
 }
 
 impl syn::parse::Parse for BundleInput {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let mut handlers = Vec::new();
+        let mut requires = Vec::new();
 
-        // Parse handlers
+        // Parse handlers and `key = [...]` options
         while !input.is_empty() {
-            handlers.push(input.parse::<Path>()?);
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                requires.extend(parse_requires(input)?);
+            } else {
+                handlers.push(input.parse::<Path>()?);
+            }
 
             // Optional trailing comma
             if input.peek(Token![,]) {
@@ -55,6 +68,22 @@ impl syn::parse::Parse for BundleInput {
             }
         }
 
-        Ok(Self { handlers })
+        Ok(Self { handlers, requires })
+    }
+}
+
+/// Parses `requires = [Type, ...]`.
+fn parse_requires(input: syn::parse::ParseStream) -> syn::Result<Vec<syn::Type>> {
+    let key = input.parse::<Ident>()?;
+    if key != "requires" {
+        return Err(syn::Error::new(
+            key.span(),
+            format!("unknown bundle option `{key}`; expected `requires`"),
+        ));
     }
+    input.parse::<Token![=]>()?;
+    let content;
+    syn::bracketed!(content in input);
+    let types = content.parse_terminated(syn::Type::parse, Token![,])?;
+    Ok(types.into_iter().collect())
 }
@@ -447,11 +447,23 @@ pub fn generate_bundle_part<T: darling::FromMeta + Default>(
     item: TokenStream,
     attr_name: &str,
     conf_builder: fn(&T, &FnSpec) -> Result<proc_macro2::TokenStream, syn::Error>,
+) -> TokenStream {
+    generate_bundle_part_with(attr, item, attr_name, conf_builder, |_| quote! {})
+}
+
+/// Like [`generate_bundle_part`], with extra `BundlePart` method calls (such
+/// as `.layer(...)`) chained after the patch operations.
+pub fn generate_bundle_part_with<T: darling::FromMeta + Default>(
+    attr: TokenStream,
+    item: TokenStream,
+    attr_name: &str,
+    conf_builder: fn(&T, &FnSpec) -> Result<proc_macro2::TokenStream, syn::Error>,
+    part_chain: fn(&T) -> proc_macro2::TokenStream,
 ) -> TokenStream {
     let attr2: proc_macro2::TokenStream = attr.into();
     let item2: proc_macro2::TokenStream = item.into();
 
-    match generate_impl(attr2, item2, attr_name, conf_builder) {
+    match generate_impl(attr2, item2, attr_name, conf_builder, part_chain) {
         Ok(tokens) => tokens.into(),
         Err(e) => e.to_compile_error().into(),
     }
@@ -463,18 +475,20 @@ pub(crate) fn generate_impl<T: darling::FromMeta + Default>(
     item: proc_macro2::TokenStream,
     attr_name: &str,
     conf_builder: fn(&T, &FnSpec) -> Result<proc_macro2::TokenStream, syn::Error>,
+    part_chain: fn(&T) -> proc_macro2::TokenStream,
 ) -> Result<proc_macro2::TokenStream, syn::Error> {
     let mut spec = extract_func_spec(&item, attr_name)?;
     let conf = parse_and_apply_metadata::<T>(attr, &mut spec)?;
     let conf_tokens = conf_builder(&conf, &spec)?;
     let patch_chain = build_patch_chain(&spec);
+    let part_chain = part_chain(&conf);
 
     Ok(emit_registration(
         &spec,
         attr_name,
         &item,
         &conf_tokens,
-        &patch_chain,
+        &quote! { #patch_chain #part_chain },
     ))
 }
 
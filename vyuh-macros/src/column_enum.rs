@@ -0,0 +1,86 @@
+use heck::ToSnakeCase;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, parse_macro_input};
+
+pub fn derive_column_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match derive_column_enum_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn derive_column_enum_impl(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let ident = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "ColumnEnum can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "ColumnEnum does not support generic enums",
+        ));
+    }
+
+    let mut to_text = Vec::new();
+    let mut from_text = Vec::new();
+    let mut to_int = Vec::new();
+    let mut from_int = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "ColumnEnum only supports unit variants (no fields)",
+            ));
+        }
+        let name = &variant.ident;
+        let text = name.to_string().to_snake_case();
+        to_text.push(quote! { Self::#name => #text, });
+        from_text.push(quote! { #text => Some(Self::#name), });
+        to_int.push(quote! { Self::#name => Self::#name as i64, });
+        from_int.push(quote! { v if v == Self::#name as i64 => Some(Self::#name), });
+    }
+
+    let crate_path = crate_path();
+    Ok(quote! {
+        impl #crate_path::db::ColumnEnum for #ident {
+            fn to_text(&self) -> &'static str {
+                match self {
+                    #(#to_text)*
+                }
+            }
+
+            fn from_text(text: &str) -> Option<Self> {
+                match text {
+                    #(#from_text)*
+                    _ => None,
+                }
+            }
+
+            fn to_int(&self) -> i64 {
+                match self {
+                    #(#to_int)*
+                }
+            }
+
+            fn from_int(value: i64) -> Option<Self> {
+                match value {
+                    #(#from_int)*
+                    _ => None,
+                }
+            }
+        }
+    })
+}
+
+fn crate_path() -> TokenStream {
+    if std::env::var("CARGO_CRATE_NAME").as_deref() == Ok("vyuh") {
+        quote! { crate }
+    } else {
+        quote! { ::vyuh }
+    }
+}
@@ -0,0 +1,161 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Lit, Type, parse_macro_input};
+
+/// Operator suffixes accepted after `__` in field names and in `op = "..."`.
+const OPS: &[(&str, &str)] = &[
+    ("eq", "Eq"),
+    ("ne", "Ne"),
+    ("lt", "Lt"),
+    ("lte", "Lte"),
+    ("gt", "Gt"),
+    ("gte", "Gte"),
+    ("contains", "Contains"),
+    ("icontains", "IContains"),
+    ("startswith", "StartsWith"),
+    ("endswith", "EndsWith"),
+];
+
+/// Operators whose value is bound as an escaped LIKE pattern.
+const PATTERN_OPS: &[&str] = &["contains", "icontains", "startswith", "endswith"];
+
+pub fn derive_filterable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = input.ident;
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "Filterable can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "Filterable can only be derived for structs",
+            ));
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut filters = Vec::new();
+    for field in fields {
+        let Some(field_ident) = field.ident.clone() else {
+            continue;
+        };
+        let attrs = FilterAttrs::parse(&field)?;
+        if attrs.skip {
+            continue;
+        }
+        let field_name = field_ident.to_string();
+        let (column, suffix) = match field_name.rsplit_once("__") {
+            Some((column, suffix)) if attrs.op.is_none() => (column.to_string(), suffix),
+            _ => (field_name.clone(), attrs.op.as_deref().unwrap_or("eq")),
+        };
+        let column = attrs.column.unwrap_or(column);
+        let Some((_, variant)) = OPS.iter().find(|(name, _)| *name == suffix) else {
+            return Err(syn::Error::new_spanned(
+                &field_ident,
+                format!("unknown filter operator '{suffix}'"),
+            ));
+        };
+        let variant = syn::Ident::new(variant, field_ident.span());
+        let value = if PATTERN_OPS.contains(&suffix) {
+            quote! { ::vyuh::db::FilterOp::#variant.pattern(value) }
+        } else {
+            quote! { value.clone() }
+        };
+        let apply = quote! {
+            builder.filter_op(
+                #column,
+                ::vyuh::db::FilterOp::#variant,
+                #field_name,
+                ::vyuh::db::ArgValue::new(#value),
+            )
+        };
+        filters.push(if is_option(&field.ty) {
+            quote! {
+                let builder = match &self.#field_ident {
+                    Some(value) => #apply,
+                    None => builder,
+                };
+            }
+        } else {
+            quote! {
+                let value = &self.#field_ident;
+                let builder = #apply;
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::vyuh::db::Filterable for #ident #ty_generics #where_clause {
+            fn apply_filters<B: ::vyuh::db::FilteredBuilder>(&self, builder: B) -> B {
+                #(#filters)*
+                builder
+            }
+        }
+    })
+}
+
+#[derive(Default)]
+struct FilterAttrs {
+    op: Option<String>,
+    column: Option<String>,
+    skip: bool,
+}
+
+impl FilterAttrs {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut attrs = Self::default();
+        for attr in &field.attrs {
+            if !attr.path().is_ident("filter") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    attrs.skip = true;
+                } else if meta.path.is_ident("op") {
+                    attrs.op = Some(parse_str(meta.value()?.parse()?, "op")?);
+                } else if meta.path.is_ident("column") {
+                    attrs.column = Some(parse_str(meta.value()?.parse()?, "column")?);
+                } else {
+                    return Err(meta.error("unsupported filter attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(attrs)
+    }
+}
+
+fn parse_str(value: Lit, key: &str) -> syn::Result<String> {
+    match value {
+        Lit::Str(value) => Ok(value.value()),
+        other => Err(syn::Error::new_spanned(
+            other,
+            format!("{key} must be a string literal"),
+        )),
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
@@ -3,7 +3,9 @@ mod bindable;
 mod bitrole;
 mod bundle;
 mod bundlepart;
+mod column_enum;
 mod cron;
+mod filterable;
 mod multipart;
 mod openapi;
 mod periodic;
@@ -30,7 +32,7 @@ extern crate proc_macro;
 /// - `custom = "path"` - Call a custom validation function: `fn(&T) -> Result<(), ValidationError>`
 /// - `custom_schema = "name"` - Emit `x-vyuh-validators: ["name"]` for custom validation
 /// - String: `min_length`, `max_length`, `exact_length`, `pattern`
-/// - String formats: `email`, `url`, `uuid`, `phone_e164`, `ipv4`, `ipv6`, `date`, `datetime`
+/// - String formats: `email`, `url`, `uuid`, `phone`, `phone_e164`, `ipv4`, `ipv6`, `ip`, `date`, `datetime`
 /// - Numeric: `min`, `max`, `exclusive_min`, `exclusive_max`, `multiple_of`
 /// - Array: `min_items`, `max_items`, `unique_items`
 #[proc_macro_derive(Validate, attributes(validate))]
@@ -61,11 +63,19 @@ pub fn derive_multipart_data(input: TokenStream) -> TokenStream {
 /// # Optional Attributes
 ///
 /// - `method` - HTTP method. Defaults to `"GET"` and can be repeated for
-///   multi-method routes.
+///   multi-method routes. `"ANY"` accepts every method (catch-all/proxy
+///   handlers) and cannot be combined with others.
+/// - `head = true` - Also list `HEAD` for a `GET` route. Axum already answers
+///   `HEAD` by running the `GET` handler and dropping the body; this records
+///   it in the route metadata. Use `Bundle::with_auto_head()` for a bundle.
 /// - `name` - Route name for reverse routing (defaults to function name)
 /// - `description` - Detailed description for OpenAPI. Defaults to doc comments.
 /// - `arg(...)` - Override OpenAPI argument metadata by position/name.
 /// - `returns(...)` - Override or append OpenAPI response metadata.
+/// - `timeout_ms` - Route timeout in milliseconds. Replaces the site-wide
+///   `HttpConf::timeout` for this route (longer or shorter); expiry returns 504.
+/// - `roles("admin", ...)` - Require an authenticated user holding any of the
+///   named roles (see `AuthConf::role_names`); others get 403.
 ///
 /// # Examples
 ///
@@ -118,6 +128,9 @@ pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// - `tags` - Optional array of tags to apply to all routes in the bundle.
 ///   These tags extend (not replace) any tags defined on individual routes.
 ///   Note: tags only apply to route parts, not other bundle parts.
+/// - `requires` - Extractors run once before every route in the bundle, e.g.
+///   `requires = [AuthUser]`. A failed extraction rejects the request before
+///   the handler runs; handlers may still declare the same extractor.
 ///
 /// # Examples
 ///
@@ -154,16 +167,49 @@ pub fn bundle(input: TokenStream) -> TokenStream {
     bundle::parse_bundle(input)
 }
 
-#[proc_macro_derive(Bindable, attributes(field, column))]
+#[proc_macro_derive(Bindable, attributes(field, column, schema))]
 pub fn derive_bindable(input: TokenStream) -> TokenStream {
     bindable::derive_bindable(input)
 }
 
-#[proc_macro_derive(Scannable, attributes(field, column))]
+/// Derives `db::Scannable` and `sqlx::FromRow`.
+///
+/// `#[schema(soft_delete = "deleted_at")]` on the struct makes selects of this
+/// model skip rows whose column is set; see `SelectQuery::with_deleted`.
+#[proc_macro_derive(Scannable, attributes(field, column, schema))]
 pub fn derive_scannable(input: TokenStream) -> TokenStream {
     scannable::derive_scannable(input)
 }
 
+/// Derives `db::ColumnEnum` for a fieldless enum.
+///
+/// Text storage uses the snake_case variant name and integer storage uses the
+/// discriminant. Choose one per field with `#[column(store_as = "text")]` or
+/// `#[column(store_as = "int")]` on a `Bindable`/`Scannable` struct.
+#[proc_macro_derive(ColumnEnum)]
+pub fn derive_column_enum(input: TokenStream) -> TokenStream {
+    column_enum::derive_column_enum(input)
+}
+
+/// Derives the Filterable trait for query filter structs.
+///
+/// Each field becomes one bound condition, combined with `AND`. The operator
+/// comes from a `__` suffix in the field name (`age__gte` filters `age >=`) or
+/// from `#[filter(op = "...")]`; plain fields use equality. `Option` fields
+/// add their condition only when `Some`.
+///
+/// # Attributes
+///
+/// ## `#[filter(...)]`
+/// - `op = "..."` - `eq`, `ne`, `lt`, `lte`, `gt`, `gte`, `contains`,
+///   `icontains`, `startswith`, `endswith`
+/// - `column = "..."` - Column name when it differs from the field name
+/// - `skip` - Ignore the field
+#[proc_macro_derive(Filterable, attributes(filter))]
+pub fn derive_filterable(input: TokenStream) -> TokenStream {
+    filterable::derive_filterable(input)
+}
+
 /// Derives the BitRole trait for role-based access control.
 ///
 /// Automatically implements BitRole for enums with unit variants only.
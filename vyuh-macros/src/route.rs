@@ -26,13 +26,43 @@ struct RouteConfMeta {
 
     /// Optional slash policy: exact, trim, redirect_append, redirect_remove, auto.
     slash: Option<String>,
+
+    /// List `HEAD` next to `GET`; axum already serves it without a body.
+    #[darling(default)]
+    head: bool,
+
+    /// Route timeout in milliseconds, replacing the site-wide timeout.
+    timeout_ms: Option<u64>,
+
+    /// Role names, any of which grants access: `roles("admin", "editor")`.
+    #[darling(default)]
+    roles: Vec<syn::LitStr>,
 }
 
 /// Entry point for #[route] macro.
 ///
 /// Handles both free functions and methods in impl blocks.
 pub(crate) fn parse_route(attr: TokenStream, item: TokenStream) -> TokenStream {
-    bundlepart::generate_bundle_part::<RouteConfMeta>(attr, item, "route", build_route_conf)
+    bundlepart::generate_bundle_part_with::<RouteConfMeta>(
+        attr,
+        item,
+        "route",
+        build_route_conf,
+        build_route_layers,
+    )
+}
+
+/// Wrap the route in `RequireRoles::any` when `roles(...)` is given and in
+/// `RouteTimeout` when `timeout_ms` is.
+fn build_route_layers(conf: &RouteConfMeta) -> proc_macro2::TokenStream {
+    let timeout = conf.timeout_ms.map(|ms| {
+        quote! { .layer(::vyuh::middlewares::RouteTimeout::from_millis(#ms)) }
+    });
+    let roles = (!conf.roles.is_empty()).then(|| {
+        let roles = &conf.roles;
+        quote! { .layer(::vyuh::auth::RequireRoles::any([#(#roles),*])) }
+    });
+    quote! { #timeout #roles }
 }
 
 /// Build RouteConf from parsed metadata and function spec.
@@ -45,10 +75,14 @@ fn build_route_conf(
     let path = &conf.path;
     validate_path(path)?;
 
-    let methods = normalize_methods(&conf.methods);
+    let mut methods = normalize_methods(&conf.methods);
     for method in &methods {
         validate_method(method)?;
     }
+    validate_any(&methods)?;
+    if conf.head {
+        add_head(&mut methods)?;
+    }
 
     let method_filter = build_method_filter(&methods);
     let name = &conf.name.as_deref().unwrap_or(&spec.name);
@@ -122,7 +156,7 @@ fn validate_path(path: &str) -> Result<(), syn::Error> {
 /// Validate HTTP method.
 fn validate_method(method: &str) -> Result<(), syn::Error> {
     const VALID: &[&str] = &[
-        "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+        "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE", "CONNECT", "ANY",
     ];
 
     if !VALID.contains(&method) {
@@ -139,6 +173,31 @@ fn validate_method(method: &str) -> Result<(), syn::Error> {
     Ok(())
 }
 
+/// `ANY` already covers every method, so it must stand alone.
+fn validate_any(methods: &[String]) -> Result<(), syn::Error> {
+    if methods.len() > 1 && methods.iter().any(|m| m == "ANY") {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "method = \"ANY\" cannot be combined with other methods",
+        ));
+    }
+    Ok(())
+}
+
+/// `head = true` only makes sense on a `GET` route.
+fn add_head(methods: &mut Vec<String>) -> Result<(), syn::Error> {
+    if !methods.iter().any(|m| m == "GET") {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "head = true requires a GET route",
+        ));
+    }
+    if !methods.iter().any(|m| m == "HEAD") {
+        methods.push("HEAD".to_string());
+    }
+    Ok(())
+}
+
 /// Build Methods filter expression from method list.
 fn build_method_filter(methods: &[String]) -> proc_macro2::TokenStream {
     let filters: Vec<_> = methods
@@ -167,6 +226,7 @@ fn method_to_const(method: &str) -> proc_macro2::TokenStream {
         "HEAD" => quote! { ::vyuh::routes::Methods::HEAD },
         "TRACE" => quote! { ::vyuh::routes::Methods::TRACE },
         "CONNECT" => quote! { ::vyuh::routes::Methods::CONNECT },
+        "ANY" => quote! { ::vyuh::routes::Methods::ANY },
         _ => quote! { compile_error!("invalid HTTP method") },
     }
 }
@@ -195,6 +255,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn any_must_stand_alone() {
+        validate_any(&["ANY".to_string()]).unwrap();
+        let err = validate_any(&["ANY".to_string(), "GET".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("cannot be combined"));
+    }
+
+    #[test]
+    fn head_is_added_only_to_get_routes() {
+        let mut methods = vec!["GET".to_string()];
+        add_head(&mut methods).unwrap();
+        add_head(&mut methods).unwrap();
+        assert_eq!(methods, vec!["GET", "HEAD"]);
+        assert!(add_head(&mut vec!["POST".to_string()]).is_err());
+    }
+
     #[test]
     fn validate_method_rejects_unknown_methods() {
         let err = validate_method("BREW").unwrap_err();
@@ -3,7 +3,7 @@ use quote::quote;
 use std::collections::HashSet;
 use syn::{DeriveInput, Type};
 
-use crate::schemable::{FieldMeta, ParsedStruct};
+use crate::schemable::{EnumStorage, FieldMeta, ParsedStruct};
 
 /// Derives the Scannable trait for deserializing database rows into structs.
 pub fn derive_scannable(input: TokenStream) -> TokenStream {
@@ -30,6 +30,13 @@ pub(crate) fn derive_scannable_impl(input: &DeriveInput) -> proc_macro2::TokenSt
     let field_inits = gen_field_initializers(&parsed.fields, &crate_path);
     let field_inits_unordered = gen_field_initializers_unordered(&parsed.fields, &crate_path);
     let column_names = gen_scan_column_names(&parsed.fields, &crate_path);
+    let soft_delete = parsed.container.soft_delete.as_ref().map(|column| {
+        quote! {
+            fn soft_delete_column() -> Option<&'static str> {
+                Some(#column)
+            }
+        }
+    });
 
     quote! {
         impl #impl_generics #crate_path::db::Scannable for #ident #ty_generics #where_clause {
@@ -39,6 +46,8 @@ pub(crate) fn derive_scannable_impl(input: &DeriveInput) -> proc_macro2::TokenSt
                 cols
             }
 
+            #soft_delete
+
             fn scan_row_ordered(
                 row: &#crate_path::db::Row,
                 start_idx: &mut usize,
@@ -102,6 +111,12 @@ fn gen_where_clause(
                     #ty: #crate_path::db::Scannable
                 });
             }
+        } else if enum_storage(field).is_some() {
+            if seen.insert(ty_str) {
+                wc.predicates.push(syn::parse_quote! {
+                    #ty: #crate_path::db::ColumnEnum
+                });
+            }
         } else if is_json(field) && seen.insert(ty_str) {
             wc.predicates.push(syn::parse_quote! {
                 #ty: ::serde::de::DeserializeOwned
@@ -126,6 +141,23 @@ fn gen_field_initializers(
             gen_default_init(ident)
         } else if is_flatten(field) || is_reference(field) {
             gen_flatten_init(ident, &field.ty, crate_path)
+        } else if let Some(storage) = enum_storage(field) {
+            let scan = enum_scan_fn(storage, crate_path);
+            quote! {
+                #ident: {
+                    let val = #scan(row, *start_idx)?;
+                    *start_idx += 1;
+                    val
+                },
+            }
+        } else if field.column.array {
+            quote! {
+                #ident: {
+                    let val = #crate_path::db::scan_array(row, *start_idx)?;
+                    *start_idx += 1;
+                    val
+                },
+            }
         } else if is_json(field) {
             gen_json_init(ident, crate_path)
         } else {
@@ -157,6 +189,17 @@ fn gen_field_initializers_unordered(
             gen_reference_unordered_error(ident, &field.ty)
         } else if is_flatten(field) {
             gen_flatten_init_unordered(ident, &field.ty, crate_path)
+        } else if let Some(storage) = enum_storage(field) {
+            let scan = enum_scan_fn(storage, crate_path);
+            let col_name = column_name(field, ident);
+            quote! {
+                #ident: #scan(row, #col_name)?,
+            }
+        } else if field.column.array {
+            let col_name = column_name(field, ident);
+            quote! {
+                #ident: #crate_path::db::scan_array(row, #col_name)?,
+            }
         } else if is_json(field) {
             gen_json_init_unordered(ident, field, crate_path)
         } else {
@@ -247,6 +290,30 @@ fn is_json(field: &FieldMeta) -> bool {
     field.column.json
 }
 
+/// `ColumnEnum` storage from `#[column(store_as = "...")]`, checked while parsing.
+fn enum_storage(field: &FieldMeta) -> Option<EnumStorage> {
+    field.column.enum_storage().ok().flatten()
+}
+
+fn enum_scan_fn(
+    storage: EnumStorage,
+    crate_path: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match storage {
+        EnumStorage::Text => quote! { #crate_path::db::scan_enum_text },
+        EnumStorage::Int => quote! { #crate_path::db::scan_enum_int },
+    }
+}
+
+fn column_name(field: &FieldMeta, ident: &syn::Ident) -> String {
+    field
+        .column
+        .name
+        .as_ref()
+        .map(|lit| lit.value())
+        .unwrap_or_else(|| ident.to_string())
+}
+
 /// Check if field is selectable (column attr only, None means true).
 fn is_selectable(field: &FieldMeta) -> bool {
     field.column.selectable.unwrap_or(true)
@@ -18,9 +18,11 @@ static VALIDATE_KEYS: &[&str] = &[
     "email",
     "url",
     "uuid",
+    "phone",
     "phone_e164",
     "ipv4",
     "ipv6",
+    "ip",
     "date",
     "datetime",
     "min",
@@ -34,6 +36,7 @@ static VALIDATE_KEYS: &[&str] = &[
     "custom",
     "custom_schema",
     "delegate",
+    "required_if",
 ];
 
 static COLUMN_KEYS: &[&str] = &[
@@ -52,6 +55,10 @@ static COLUMN_KEYS: &[&str] = &[
     "index_type",
     "unique",
     "unique_group",
+    "auto_now_add",
+    "auto_now",
+    "store_as",
+    "array",
 ];
 
 // -------------------------------------------------------------------------------------
@@ -188,6 +195,10 @@ impl FromMeta for ReferenceSpec {
 pub struct ContainerAttrs {
     #[darling(default)]
     pub table: Option<LitStr>,
+
+    /// Timestamp column marking deleted rows, from `soft_delete = "..."`.
+    #[darling(default)]
+    pub soft_delete: Option<LitStr>,
 }
 
 /// Field-level validation attributes from #[validate(...)]
@@ -214,12 +225,16 @@ pub struct ValidateAttrs {
     #[darling(default)]
     pub uuid: bool,
     #[darling(default)]
+    pub phone: bool,
+    #[darling(default)]
     pub phone_e164: bool,
     #[darling(default)]
     pub ipv4: bool,
     #[darling(default)]
     pub ipv6: bool,
     #[darling(default)]
+    pub ip: bool,
+    #[darling(default)]
     pub date: bool,
     #[darling(default)]
     pub datetime: bool,
@@ -248,6 +263,19 @@ pub struct ValidateAttrs {
     pub custom_schema: Option<LitStr>,
     #[darling(default)]
     pub delegate: bool,
+
+    /// `"other"` or `"other == <literal>"`; the field must be present when
+    /// the condition holds.
+    #[darling(default)]
+    pub required_if: Option<LitStr>,
+}
+
+/// Parsed `required_if` condition.
+#[derive(Debug, Clone)]
+pub struct RequiredIf {
+    pub field: syn::Ident,
+    /// Compared with `==`; `None` tests the sibling for truthiness.
+    pub equals: Option<Lit>,
 }
 
 impl ValidateAttrs {
@@ -272,9 +300,11 @@ impl ValidateAttrs {
             ("email", self.email),
             ("url", self.url),
             ("uuid", self.uuid),
+            ("phone", self.phone),
             ("phone_e164", self.phone_e164),
             ("ipv4", self.ipv4),
             ("ipv6", self.ipv6),
+            ("ip", self.ip),
             ("date", self.date),
             ("datetime", self.datetime),
         ];
@@ -323,8 +353,33 @@ impl ValidateAttrs {
             ));
         }
 
+        self.required_if()?;
+
         Ok(())
     }
+
+    pub fn required_if(&self) -> Result<Option<RequiredIf>> {
+        let Some(lit) = &self.required_if else {
+            return Ok(None);
+        };
+        let value = lit.value();
+        let (field, equals) = match value.split_once("==") {
+            Some((field, literal)) => (field, Some(literal)),
+            None => (value.as_str(), None),
+        };
+        let invalid = || {
+            Error::new(
+                lit.span(),
+                "expected `required_if = \"field\"` or `required_if = \"field == <literal>\"`",
+            )
+        };
+        let field = syn::parse_str::<syn::Ident>(field.trim()).map_err(|_| invalid())?;
+        let equals = equals
+            .map(|literal| syn::parse_str::<Lit>(literal.trim()))
+            .transpose()
+            .map_err(|_| invalid())?;
+        Ok(Some(RequiredIf { field, equals }))
+    }
 }
 
 /// Database column metadata from #[column(...)]
@@ -345,6 +400,10 @@ pub struct ColumnAttrs {
     pub flatten: bool,
     #[darling(default)]
     pub json: bool,
+    /// `Vec<T>` or `Option<Vec<T>>` stored as a Postgres array, or as JSON on
+    /// other backends.
+    #[darling(default)]
+    pub array: bool,
     #[darling(default)]
     pub reference: Option<ReferenceSpec>,
 
@@ -368,6 +427,40 @@ pub struct ColumnAttrs {
 
     #[darling(default, multiple, rename = "unique_group")]
     pub unique_groups: Vec<LitStr>,
+
+    /// Set to the current time by inserts; never bound from the struct.
+    #[darling(default)]
+    pub auto_now_add: bool,
+    /// Set to the current time by inserts and updates; never bound.
+    #[darling(default)]
+    pub auto_now: bool,
+
+    /// `ColumnEnum` storage: `"text"` or `"int"`.
+    #[darling(default)]
+    pub store_as: Option<LitStr>,
+}
+
+/// How a `ColumnEnum` field is stored, from `#[column(store_as = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumStorage {
+    Text,
+    Int,
+}
+
+impl ColumnAttrs {
+    pub fn enum_storage(&self) -> Result<Option<EnumStorage>> {
+        let Some(lit) = &self.store_as else {
+            return Ok(None);
+        };
+        match lit.value().as_str() {
+            "text" => Ok(Some(EnumStorage::Text)),
+            "int" => Ok(Some(EnumStorage::Int)),
+            _ => Err(Error::new(
+                lit.span(),
+                "expected `store_as = \"text\"` or `store_as = \"int\"`",
+            )),
+        }
+    }
 }
 
 /// Combined field attributes across all namespaces
@@ -433,6 +526,7 @@ impl FieldMeta {
                         &format!("Error decoding #[column] on field '{field_name}'"),
                     )
                 })?;
+                column.enum_storage()?;
             }
         }
 
@@ -537,6 +631,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_required_if_parses_conditions() {
+        let input: DeriveInput = parse_quote! {
+            struct Test {
+                #[validate(required_if = "kind == 3")]
+                field: Option<String>,
+                #[validate(required_if = "has_reason")]
+                reason: Option<String>,
+            }
+        };
+        let parsed = ParsedStruct::from_derive_input(input).unwrap();
+        let cond = parsed.fields[0].validate.required_if().unwrap().unwrap();
+        assert_eq!(cond.field, "kind");
+        assert!(matches!(cond.equals, Some(Lit::Int(_))));
+        let cond = parsed.fields[1].validate.required_if().unwrap().unwrap();
+        assert_eq!(cond.field, "has_reason");
+        assert!(cond.equals.is_none());
+
+        let input: DeriveInput = parse_quote! {
+            struct Test {
+                #[validate(required_if = "kind ==")]
+                field: Option<String>,
+            }
+        };
+        assert!(ParsedStruct::from_derive_input(input).is_err());
+    }
+
     #[test]
     fn validate_multiple_of_zero_fails() {
         let input: DeriveInput = parse_quote! {
@@ -27,6 +27,21 @@ fn generate_validate(parsed: &ParsedStruct) -> Result<proc_macro2::TokenStream>
     let mut schema_validations = Vec::with_capacity(field_count);
 
     for field in &parsed.fields {
+        if let Some(cond) = field.validate.required_if()? {
+            let sibling = parsed
+                .fields
+                .iter()
+                .find(|f| f.ident.as_ref() == Some(&cond.field));
+            if sibling.is_none() || field.ident.as_ref() == Some(&cond.field) {
+                return Err(syn::Error::new(
+                    cond.field.span(),
+                    format!(
+                        "required_if must name another field, found '{}'",
+                        cond.field
+                    ),
+                ));
+            }
+        }
         if let Some(tokens) = gen_field_validation(field)? {
             field_validations.push(tokens);
         }
@@ -76,6 +91,10 @@ fn gen_field_validation(
 
     let mut checks = Vec::with_capacity(8);
 
+    if let Some(cond) = validate.required_if()? {
+        checks.push(gen_required_if_check(field_ident, &field_name, &cond));
+    }
+
     if validate.delegate {
         checks.push(gen_delegate_check(field_ident, &field_name));
     }
@@ -108,6 +127,29 @@ fn gen_delegate_check(field_ident: &syn::Ident, field_name: &str) -> proc_macro2
     }
 }
 
+/// Generate conditional presence check keyed to the required field.
+fn gen_required_if_check(
+    field_ident: &syn::Ident,
+    field_name: &str,
+    cond: &crate::schemable::RequiredIf,
+) -> proc_macro2::TokenStream {
+    let other = &cond.field;
+    let other_name = other.to_string();
+    let condition = match &cond.equals {
+        Some(lit) => quote! { self.#other == #lit },
+        None => quote! { ::vyuh::validation::Truthy::is_truthy(&self.#other) },
+    };
+    quote! {
+        if #condition && !::vyuh::validation::Truthy::is_truthy(&self.#field_ident) {
+            main_report.push(
+                ::vyuh::validation::Path::root().at_field(#field_name),
+                ::vyuh::validation::ValidationError::new("required", "This field is required.")
+                    .with_param("required_if", #other_name),
+            );
+        }
+    }
+}
+
 /// Generate custom validation function check.
 fn gen_custom_check(
     field_ident: &syn::Ident,
@@ -130,9 +172,11 @@ fn has_standard_validators(validate: &crate::schemable::ValidateAttrs) -> bool {
         || validate.email
         || validate.url
         || validate.uuid
+        || validate.phone
         || validate.phone_e164
         || validate.ipv4
         || validate.ipv6
+        || validate.ip
         || validate.date
         || validate.datetime
         || validate.min.is_some()
@@ -252,6 +296,24 @@ fn gen_string_formats(
             }
         });
     }
+    if validate.ip {
+        checks.push(quote! {
+            if let Some(v) = target {
+                if let Err(e) = ::vyuh::validators::ip(v.as_ref()) {
+                    main_report.push(::vyuh::validation::Path::root().at_field(#field_name), e);
+                }
+            }
+        });
+    }
+    if validate.phone {
+        checks.push(quote! {
+            if let Some(v) = target {
+                if let Err(e) = ::vyuh::validators::phone(v.as_ref()) {
+                    main_report.push(::vyuh::validation::Path::root().at_field(#field_name), e);
+                }
+            }
+        });
+    }
     if validate.phone_e164 {
         checks.push(quote! {
             if let Some(v) = target {
@@ -474,7 +536,7 @@ fn gen_field_schema_validation(
         constraints.push(quote! { ("format", ::serde_json::json!("uri")) });
     } else if validate.uuid {
         constraints.push(quote! { ("format", ::serde_json::json!("uuid")) });
-    } else if validate.phone_e164 {
+    } else if validate.phone || validate.phone_e164 {
         constraints.push(quote! { ("pattern", ::serde_json::json!(r"^\+[1-9]\d{1,14}$")) });
     } else if validate.ipv4 {
         constraints.push(quote! { ("format", ::serde_json::json!("ipv4")) });
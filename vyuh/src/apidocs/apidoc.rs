@@ -95,7 +95,7 @@ impl ApiDocGenerator {
         // Build paths from views
         let mut paths_map: IndexMap<String, ReferenceOr<PathItem>> = IndexMap::new();
 
-        for view in views {
+        for view in views.iter().filter(|view| !view.is_websocket()) {
             add_view_to_paths(&mut paths_map, view, &mut registry)?;
         }
 
@@ -527,7 +527,7 @@ fn build_layer_param(
             registry.register_security(scheme.to_string(), &scopes_str, *join_all);
             return Ok(None);
         }
-        ArgPart::Zone | ArgPart::Ignore => return Ok(None),
+        ArgPart::Zone | ArgPart::Ignore | ArgPart::Upgrade => return Ok(None),
     };
 
     let openapi_schema = type_schema_to_openapi(schema, registry)?;
@@ -589,7 +589,7 @@ fn build_param(
             registry.register_security(scheme.to_string(), &scopes_str, *join_all);
             return Ok(None);
         }
-        ArgPart::Zone | ArgPart::Ignore => return Ok(None),
+        ArgPart::Zone | ArgPart::Ignore | ArgPart::Upgrade => return Ok(None),
     };
 
     let openapi_schema = type_schema_to_openapi(schema, registry)?;
@@ -911,6 +911,7 @@ mod tests {
     use crate::{
         callables::{
             ArgPart, ArgSpec, Operation as VyuhOperation, OperationKind, ReturnPart, ReturnSpec,
+            TypeSchema,
         },
         routes::Methods,
     };
@@ -996,6 +997,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn maps_path_and_query_arguments_to_parameter_locations() {
+        let mut op = route_op("note", "/notes/{id}", Methods::GET | Methods::DELETE);
+        op.args.push(ArgSpec {
+            name: "id".to_string(),
+            description: None,
+            position: 0,
+            part: ArgPart::Path(TypeSchema::wrap::<i64>()),
+        });
+        op.args.push(ArgSpec {
+            name: "fields".to_string(),
+            description: None,
+            position: 1,
+            part: ArgPart::Query(TypeSchema::wrap::<String>()),
+        });
+
+        let api = ApiDocGenerator::default().generate(&[&op]).unwrap();
+        assert_eq!(api.paths.paths.len(), 1);
+        let ReferenceOr::Item(item) = api.paths.paths.get("/notes/{id}").unwrap() else {
+            panic!("expected inline path item");
+        };
+        assert!(item.delete.is_some());
+        let params = &item.get.as_ref().unwrap().parameters;
+
+        let Some(ReferenceOr::Item(Parameter::Path { parameter_data, .. })) = params.first() else {
+            panic!("expected path parameter");
+        };
+        assert_eq!(parameter_data.name, "id");
+        assert!(parameter_data.required);
+
+        let Some(ReferenceOr::Item(Parameter::Query { parameter_data, .. })) = params.get(1) else {
+            panic!("expected query parameter");
+        };
+        assert_eq!(parameter_data.name, "fields");
+        assert!(!parameter_data.required);
+    }
+
+    #[test]
+    fn skips_websocket_routes() {
+        let mut socket = route_op("live", "/live", Methods::GET);
+        socket.args.push(ArgSpec {
+            name: "upgrade".to_string(),
+            description: None,
+            position: 0,
+            part: ArgPart::Upgrade,
+        });
+        let notes = route_op("notes", "/notes", Methods::GET);
+
+        let api = ApiDocGenerator::default()
+            .generate(&[&socket, &notes])
+            .unwrap();
+        assert!(socket.is_websocket());
+        assert!(api.paths.paths.get("/live").is_none());
+        assert!(api.paths.paths.get("/notes").is_some());
+    }
+
     #[test]
     fn emits_security_metadata_from_arguments() {
         let mut op = route_op("notes", "/notes", Methods::GET);
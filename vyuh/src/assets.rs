@@ -1,12 +1,13 @@
 use axum::{
     body::Body,
     extract::Request,
-    http::{Method, StatusCode, header},
+    http::{HeaderMap, HeaderValue, Method, StatusCode, header},
     response::Response,
 };
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
-use rust_silos::SiloSet;
+use rust_silos::{Silo, SiloSet};
 use std::{
     collections::HashMap,
     convert::Infallible,
@@ -36,6 +37,7 @@ pub struct AssetServe {
     precompressed: bool,
     etag: bool,
     etag_cache: Arc<RwLock<HashMap<String, String>>>,
+    cache_rules: Arc<[(Silo, HeaderValue)]>,
 }
 
 impl AssetServe {
@@ -48,12 +50,27 @@ impl AssetServe {
             precompressed: false,
             etag: false,
             etag_cache: Arc::new(RwLock::new(HashMap::new())),
+            cache_rules: Arc::new([]),
         }
     }
 
+    /// Dirs registered with [`embed::Dir::with_cache_control`] override the
+    /// default policy for the files they contain.
     pub fn from_dirs(dirs: Vec<embed::Dir>, folder: &str) -> Self {
+        let mut rules = Vec::new();
+        for dir in &dirs {
+            let Some(value) = dir.cache_control() else {
+                continue;
+            };
+            match HeaderValue::from_str(value) {
+                Ok(value) => rules.push((dir.clone().into_silo(), value)),
+                Err(_) => tracing::warn!("ignoring invalid asset Cache-Control '{value}'"),
+            }
+        }
         let silos = dirs.into_iter().map(embed::Dir::into_silo).collect();
-        Self::new(SiloSet::new(silos), folder)
+        let mut serve = Self::new(SiloSet::new(silos), folder);
+        serve.cache_rules = rules.into();
+        serve
     }
 
     pub fn strip_url_prefix(mut self, prefix: &str) -> Self {
@@ -72,6 +89,16 @@ impl AssetServe {
         self.etag = enabled;
         self
     }
+
+    fn cache_control(&self, logical_path: &str) -> HeaderValue {
+        self.cache_rules
+            .iter()
+            .find(|(silo, _)| silo.get_file(logical_path).is_some())
+            .map_or_else(
+                || HeaderValue::from_static(cache_control_for(logical_path)),
+                |(_, value)| value.clone(),
+            )
+    }
 }
 
 impl Service<Request> for AssetServe {
@@ -84,134 +111,188 @@ impl Service<Request> for AssetServe {
     }
 
     fn call(&mut self, req: Request) -> Self::Future {
+        let serve = self.clone();
         let method = req.method().clone();
         let raw_path = req.uri().path().to_string();
-
-        let silos = Arc::clone(&self.silos);
-        let prefix = Arc::clone(&self.prefix);
-        let url_prefix = Arc::clone(&self.url_prefix);
-        let precompressed = self.precompressed;
-        let use_etag = self.etag;
-        let cache = Arc::clone(&self.etag_cache);
-
         let accept_encoding = req
             .headers()
             .get(header::ACCEPT_ENCODING)
             .and_then(|v| v.to_str().ok())
             .map(|s| s.to_string());
-
-        let if_none_match = if use_etag {
-            req.headers()
-                .get(header::IF_NONE_MATCH)
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.to_string())
-        } else {
-            None
-        };
+        let conditions = Conditions::from_headers(req.headers());
 
         Box::pin(async move {
             Ok(serve_file_impl(
-                &silos,
-                &prefix,
-                &url_prefix,
+                &serve,
                 &method,
                 &raw_path,
-                precompressed,
                 accept_encoding.as_deref(),
-                if_none_match.as_deref(),
-                use_etag,
-                &cache,
+                &conditions,
             )
             .await)
         })
     }
 }
 
+/// Validators from a conditional GET (RFC 9110 §13.1).
+#[derive(Debug, Default)]
+struct Conditions {
+    if_none_match: Option<String>,
+    if_modified_since: Option<i64>,
+}
+
+impl Conditions {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let text = |name| {
+            headers
+                .get(name)
+                .and_then(|v: &HeaderValue| v.to_str().ok())
+        };
+        Self {
+            if_none_match: text(header::IF_NONE_MATCH).map(str::to_string),
+            if_modified_since: text(header::IF_MODIFIED_SINCE)
+                .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+                .map(|at| at.timestamp()),
+        }
+    }
+
+    /// `If-None-Match` takes precedence; `If-Modified-Since` is only consulted
+    /// without it, and only when the file has a modification time.
+    fn is_fresh(&self, etag: Option<&str>, modified: Option<i64>) -> bool {
+        if let Some(candidates) = &self.if_none_match {
+            // Weak comparison: `W/"x"` and `"x"` name the same representation.
+            let strip = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+            return candidates
+                .split(',')
+                .map(strip)
+                .any(|tag| tag == "*" || etag.is_some_and(|etag| strip(etag) == tag));
+        }
+        matches!((self.if_modified_since, modified), (Some(since), Some(at)) if at <= since)
+    }
+}
+
 async fn serve_file_impl(
-    silos: &SiloSet,
-    prefix: &str,
-    url_prefix: &str,
+    serve: &AssetServe,
     method: &Method,
     raw_path: &str,
-    precompressed: bool,
     accept_encoding: Option<&str>,
-    if_none_match: Option<&str>,
-    use_etag: bool,
-    cache: &RwLock<HashMap<String, String>>,
+    conditions: &Conditions,
 ) -> Response {
     // Only GET/HEAD for static
     if *method != Method::GET && *method != Method::HEAD {
-        return Response::builder()
-            .status(StatusCode::METHOD_NOT_ALLOWED)
-            .body(Body::empty())
-            .unwrap();
+        return status_only(StatusCode::METHOD_NOT_ALLOWED);
     }
 
     // Decode & normalize path safely
     let clean_rel =
-        match clean_rel_path(raw_path).and_then(|path| strip_url_prefix(path, url_prefix)) {
+        match clean_rel_path(raw_path).and_then(|path| strip_url_prefix(path, &serve.url_prefix)) {
             Some(p) => p,
             None => return not_found(),
         };
 
     // Build lookup path inside silo root
-    let logical_path = join_prefix(prefix, &clean_rel);
+    let logical_path = join_prefix(&serve.prefix, &clean_rel);
 
     // Select and read bytes (possibly precompressed variant)
-    let (served_path, bytes, content_encoding) =
-        match read_best_variant(silos, &logical_path, precompressed, accept_encoding).await {
-            Some(v) => v,
-            None => return not_found(),
-        };
+    let (served_path, bytes, content_encoding) = match read_best_variant(
+        &serve.silos,
+        &logical_path,
+        serve.precompressed,
+        accept_encoding,
+    )
+    .await
+    {
+        Some(v) => v,
+        None => return not_found(),
+    };
 
     // Compute or retrieve cached ETag
-    let etag_val = if use_etag {
-        Some(get_or_compute_etag(cache, &served_path, &bytes, silos).await)
+    let etag_val = if serve.etag {
+        Some(get_or_compute_etag(&serve.etag_cache, &served_path, &bytes, &serve.silos).await)
     } else {
         None
     };
+    let modified = modified_secs(&serve.silos, &served_path).await;
+    let validators = Validators {
+        etag: etag_val.as_deref(),
+        modified,
+        cache_control: serve.cache_control(&logical_path),
+        precompressed: serve.precompressed,
+    };
 
-    if let (Some(etag), Some(client_etag)) = (etag_val.as_deref(), if_none_match) {
-        // Very simple exact match. (If client sends a list, you can extend later.)
-        if client_etag.trim() == etag {
-            return not_modified(etag, precompressed);
-        }
+    if conditions.is_fresh(etag_val.as_deref(), modified) {
+        return validators.apply(status_only(StatusCode::NOT_MODIFIED));
     }
 
-    // Build response headers
-    let mut builder = Response::builder().status(StatusCode::OK);
-
-    // Content-Type
-    let mime = guess_mime(&served_path);
-    builder = builder.header(header::CONTENT_TYPE, mime);
+    validators.apply(file_response(method, &served_path, bytes, content_encoding))
+}
 
+fn file_response(
+    method: &Method,
+    served_path: &str,
+    bytes: Bytes,
+    content_encoding: Option<&'static str>,
+) -> Response {
+    let len = bytes.len();
+    let mut response = if *method == Method::HEAD {
+        // HEAD returns headers only
+        status_only(StatusCode::OK)
+    } else {
+        Response::new(Body::from(bytes))
+    };
+    let headers = response.headers_mut();
+    if let Ok(mime) = HeaderValue::from_str(&guess_mime(served_path)) {
+        headers.insert(header::CONTENT_TYPE, mime);
+    }
     // Content-Encoding for br/gz variants
     if let Some(enc) = content_encoding {
-        builder = builder.header(header::CONTENT_ENCODING, enc);
-    }
-
-    // Vary if we do content negotiation
-    if precompressed {
-        builder = builder.header(header::VARY, "Accept-Encoding");
-    }
-
-    // Cache-Control policy
-    builder = builder.header(header::CACHE_CONTROL, cache_control_for(&served_path));
-
-    // ETag
-    if let Some(etag) = etag_val.as_deref() {
-        builder = builder.header(header::ETAG, etag);
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(enc));
     }
+    headers.insert(header::CONTENT_LENGTH, HeaderValue::from(len));
+    response
+}
 
-    // Content-Length
-    builder = builder.header(header::CONTENT_LENGTH, bytes.len().to_string());
+/// Caching headers shared by 200 and 304 responses.
+struct Validators<'a> {
+    etag: Option<&'a str>,
+    modified: Option<i64>,
+    cache_control: HeaderValue,
+    precompressed: bool,
+}
 
-    // HEAD returns headers only
-    if *method == Method::HEAD {
-        return builder.body(Body::empty()).unwrap();
+impl Validators<'_> {
+    fn apply(self, mut response: Response) -> Response {
+        let headers = response.headers_mut();
+        // Vary if we do content negotiation
+        if self.precompressed {
+            headers.insert(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+        }
+        headers.insert(header::CACHE_CONTROL, self.cache_control);
+        if let Some(etag) = self.etag.and_then(|etag| HeaderValue::from_str(etag).ok()) {
+            headers.insert(header::ETAG, etag);
+        }
+        let last_modified = self
+            .modified
+            .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0))
+            .map(|at| at.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+            .and_then(|at| HeaderValue::from_str(&at).ok());
+        if let Some(value) = last_modified {
+            headers.insert(header::LAST_MODIFIED, value);
+        }
+        response
     }
+}
 
-    builder.body(Body::from(bytes)).unwrap()
+/// Whole-second mtime of a filesystem file; embedded files have none.
+async fn modified_secs(silos: &SiloSet, path: &str) -> Option<i64> {
+    let file = silos.get_file(path)?;
+    let absolute = file.absolute_path()?;
+    let modified = tokio::fs::metadata(absolute).await.ok()?.modified().ok()?;
+    let secs = modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    i64::try_from(secs).ok()
 }
 
 async fn read_best_variant(
@@ -256,7 +337,8 @@ async fn try_read_file(silos: &SiloSet, path: &str) -> Option<Bytes> {
         reader.read_to_end(&mut buf).ok()?;
         Some(Bytes::from(buf))
     } else {
-        tokio::fs::read(file.path()).await.ok().map(Bytes::from)
+        let absolute = file.absolute_path()?;
+        tokio::fs::read(absolute).await.ok().map(Bytes::from)
     }
 }
 
@@ -395,23 +477,14 @@ fn strong_etag(bytes: &Bytes) -> String {
     format!("\"{}\"", digest.to_hex())
 }
 
-fn not_modified(etag: &str, precompressed: bool) -> Response {
-    let mut builder = Response::builder()
-        .status(StatusCode::NOT_MODIFIED)
-        .header(header::ETAG, etag);
-
-    if precompressed {
-        builder = builder.header(header::VARY, "Accept-Encoding");
-    }
-
-    builder.body(Body::empty()).unwrap()
+fn not_found() -> Response {
+    status_only(StatusCode::NOT_FOUND)
 }
 
-fn not_found() -> Response {
-    Response::builder()
-        .status(StatusCode::NOT_FOUND)
-        .body(Body::empty())
-        .unwrap()
+fn status_only(status: StatusCode) -> Response {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
 }
 
 /// Minimal Accept-Encoding parser that respects `q=0` disable.
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     future::Future,
     hash::Hash,
     path::{Path, PathBuf},
@@ -25,7 +26,10 @@ use thiserror::Error;
 use time;
 
 pub use crate::permit;
-pub use crate::roles::{BitRole, Permit, PermitAll, PermitAny, RoleType, format_roles};
+pub use crate::roles::{
+    BitRole, Permit, PermitAll, PermitAny, RequireRoles, RequireRolesService, RoleType,
+    format_roles,
+};
 
 const DEFAULT_PBKDF2_ITERATIONS: u32 = 260_000;
 const UNUSABLE_PASSWORD_PREFIX: &str = "!";
@@ -55,6 +59,19 @@ impl From<CookieSameSite> for cookie::SameSite {
     }
 }
 
+/// Where `AuthUser` looks for an access or refresh token, and in which order.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenLocation {
+    /// The `Authorization` header, then the configured cookie.
+    #[default]
+    HeaderFirst,
+    /// The configured cookie, then the `Authorization` header.
+    CookieFirst,
+    /// The `Authorization` header only; cookies are never read.
+    HeaderOnly,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CookieConf {
     pub name: String,
@@ -483,6 +500,12 @@ pub struct AuthConf {
     pub leeway_seconds: u64,
     pub min_secret_len: usize,
     pub api_keys: ApiKeyConf,
+    #[serde(default)]
+    pub token_location: TokenLocation,
+    /// Role name to bit position, used by name-based guards such as
+    /// `#[route(roles(...))]`. Names match case-insensitively.
+    #[serde(default)]
+    pub role_names: BTreeMap<String, u8>,
 }
 
 impl Default for AuthConf {
@@ -498,6 +521,8 @@ impl Default for AuthConf {
             leeway_seconds: 0,
             min_secret_len: 32,
             api_keys: ApiKeyConf::default(),
+            token_location: TokenLocation::default(),
+            role_names: BTreeMap::new(),
         }
     }
 }
@@ -558,6 +583,19 @@ impl AuthConf {
         self.api_keys = api_keys;
         self
     }
+
+    pub fn token_location(mut self, location: TokenLocation) -> Self {
+        self.token_location = location;
+        self
+    }
+
+    /// Registers every variant of a `BitRole` enum under its name.
+    pub fn bit_roles<R: BitRole>(mut self) -> Self {
+        for (bit, name) in R::role_pairs() {
+            self.role_names.insert((*name).to_string(), *bit);
+        }
+        self
+    }
 }
 
 fn extract_token(parts: &Parts) -> Option<&str> {
@@ -722,6 +760,8 @@ pub struct Authenticator {
     access_cookie_same_site: cookie::SameSite,
     refresh_cookie_same_site: cookie::SameSite,
     api_keys: ApiKeyConf,
+    token_location: TokenLocation,
+    role_names: BTreeMap<String, u8>,
     algorithm: Algorithm,
     key_id: Option<String>,
     encoding_key: EncodingKey,
@@ -739,6 +779,8 @@ impl std::fmt::Debug for Authenticator {
             .field("access_cookie_conf", &self.access_cookie_conf)
             .field("refresh_cookie_conf", &self.refresh_cookie_conf)
             .field("api_keys", &self.api_keys)
+            .field("token_location", &self.token_location)
+            .field("role_names", &self.role_names)
             .field("algorithm", &self.algorithm)
             .field("key_id", &self.key_id)
             .finish()
@@ -875,6 +917,8 @@ impl Authenticator {
             access_cookie_conf,
             refresh_cookie_conf,
             api_keys,
+            token_location: conf.token_location,
+            role_names: conf.role_names.clone(),
             algorithm,
             key_id: conf.jwt.key_id.clone(),
             encoding_key,
@@ -891,31 +935,80 @@ impl Authenticator {
     }
 
     pub fn decode(&self, token: &str) -> Result<JWTClaim, AuthError> {
+        // Payloads from `sign_claims` share the key but never authenticate.
+        let header = jsonwebtoken::decode_header(token).map_err(|e| AuthError::from(&e))?;
+        if header.typ.as_deref().is_some_and(|typ| typ != "JWT") {
+            return Err(AuthError::InvalidToken);
+        }
         let key = &self.decoding_key;
         decode::<JWTClaim>(&token, &key, &self.validation)
             .map(|o| o.claims)
             .map_err(|e| AuthError::from(&e))
     }
 
+    /// Signs non-auth `claims` (which must carry `exp`) with the JWT key under
+    /// header `typ`, so `decode` never accepts the result as a login token.
+    pub(crate) fn sign_claims<T: Serialize>(
+        &self,
+        typ: &str,
+        claims: &T,
+    ) -> Result<String, AuthError> {
+        let mut header = jsonwebtoken::Header::new(self.algorithm);
+        header.typ = Some(typ.to_string());
+        header.kid = self.key_id.clone();
+        encode(&header, claims, &self.encoding_key).map_err(|e| AuthError::from(&e))
+    }
+
+    /// Verifies a payload from [`sign_claims`](Self::sign_claims) with the same `typ`.
+    pub(crate) fn verify_claims<T: serde::de::DeserializeOwned>(
+        &self,
+        typ: &str,
+        token: &str,
+    ) -> Result<T, AuthError> {
+        let header = jsonwebtoken::decode_header(token).map_err(|e| AuthError::from(&e))?;
+        if header.typ.as_deref() != Some(typ) {
+            return Err(AuthError::InvalidToken);
+        }
+        let mut validation = Validation::new(self.algorithm);
+        validation.validate_aud = false;
+        validation.set_required_spec_claims(&["exp"]);
+        decode::<T>(token, &self.decoding_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| AuthError::from(&e))
+    }
+
+    /// Combines the bits of the named roles registered in `AuthConf::role_names`.
+    pub fn role_mask<S: AsRef<str>>(&self, names: &[S]) -> Result<RoleType, AuthError> {
+        names.iter().try_fold(0, |mask, name| {
+            let name = name.as_ref();
+            self.role_names
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(name))
+                .and_then(|(_, bit)| (1 as RoleType).checked_shl(u32::from(*bit)))
+                .map(|bit| mask | bit)
+                .ok_or_else(|| AuthError::InternalError(format!("role '{name}' is not registered")))
+        })
+    }
+
     pub fn extract_claims(&self, parts: &Parts, kind: TokenKind) -> Result<JWTClaim, AuthError> {
-        let cookies_conf = if kind == TokenKind::Refresh {
+        let cookie_conf = if kind == TokenKind::Refresh {
             &self.refresh_cookie_conf
         } else {
             &self.access_cookie_conf
         };
-        let token = extract_token(parts)
-            .map(|t| t.to_owned())
-            .or_else(|| {
-                cookies_conf
-                    .as_ref()
-                    .map(|c| c.name.as_str())
-                    .and_then(|cookie_name| {
-                        CookieJar::from_headers(&parts.headers)
-                            .get(cookie_name)
-                            .map(|c| c.value().to_owned())
-                    })
-            })
-            .ok_or(AuthError::MissingToken)?;
+        let header = || extract_token(parts).map(str::to_owned);
+        let cookie = || {
+            let name = cookie_conf.as_ref()?.name.as_str();
+            CookieJar::from_headers(&parts.headers)
+                .get(name)
+                .map(|c| c.value().to_owned())
+        };
+        let token = match self.token_location {
+            TokenLocation::HeaderFirst => header().or_else(cookie),
+            TokenLocation::CookieFirst => cookie().or_else(header),
+            TokenLocation::HeaderOnly => header(),
+        }
+        .ok_or(AuthError::MissingToken)?;
         let claims = self.decode(&token)?;
         if claims.token_kind() != kind {
             return Err(AuthError::WrongTokenKind);
@@ -1293,14 +1386,42 @@ pub enum AuthError {
     InternalError(String),
 }
 
-//convert jsonwebtoken::errors::Error to JWTError
+impl AuthError {
+    /// True when the request's token is missing or unusable: malformed,
+    /// expired, badly signed, or of the wrong kind. Configuration and internal
+    /// failures return false.
+    pub fn is_token_error(&self) -> bool {
+        matches!(
+            self,
+            AuthError::MissingToken
+                | AuthError::InvalidToken
+                | AuthError::ExpiredToken
+                | AuthError::InvalidSignature
+                | AuthError::WrongTokenKind
+        )
+    }
+}
+
+/// Maps jsonwebtoken failures to auth errors. Anything a client can cause with
+/// a malformed or unacceptable token is `InvalidToken`; only key and crypto
+/// failures on our side are internal.
 impl From<&jsonwebtoken::errors::Error> for AuthError {
     fn from(err: &jsonwebtoken::errors::Error) -> Self {
+        use jsonwebtoken::errors::ErrorKind;
         match err.kind() {
-            jsonwebtoken::errors::ErrorKind::InvalidToken => AuthError::InvalidToken,
-            jsonwebtoken::errors::ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
-            jsonwebtoken::errors::ErrorKind::InvalidSignature => AuthError::InvalidSignature,
-            jsonwebtoken::errors::ErrorKind::InvalidAlgorithm => AuthError::InvalidToken,
+            ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
+            ErrorKind::InvalidSignature => AuthError::InvalidSignature,
+            ErrorKind::InvalidToken
+            | ErrorKind::Base64(_)
+            | ErrorKind::Json(_)
+            | ErrorKind::Utf8(_)
+            | ErrorKind::InvalidAlgorithm
+            | ErrorKind::MissingAlgorithm
+            | ErrorKind::MissingRequiredClaim(_)
+            | ErrorKind::InvalidIssuer
+            | ErrorKind::InvalidAudience
+            | ErrorKind::InvalidSubject
+            | ErrorKind::ImmatureSignature => AuthError::InvalidToken,
             _ => AuthError::InternalError(err.to_string()),
         }
     }
@@ -1321,6 +1442,25 @@ impl axum::extract::FromRequestParts<Site> for AuthUser {
     }
 }
 
+/// The signed-in user if the request carries a valid access token, otherwise
+/// `None`. Missing, malformed, and expired tokens are all treated as anonymous
+/// rather than rejected; use `AuthUser` when a token is required. Failures on
+/// the server side, such as `InternalError`, still reject the request.
+#[derive(Debug, Clone)]
+pub struct MaybeUser(pub Option<AuthUser>);
+
+impl axum::extract::FromRequestParts<Site> for MaybeUser {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, site: &Site) -> Result<Self, Self::Rejection> {
+        match AuthUser::from_request_parts(parts, site).await {
+            Ok(user) => Ok(Self(Some(user))),
+            Err(err) if err.is_token_error() => Ok(Self(None)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
 impl axum::extract::FromRequestParts<Site> for ApiKey {
     type Rejection = AuthError;
 
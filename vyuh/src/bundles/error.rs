@@ -44,3 +44,16 @@ pub enum BundleError {
     #[error("duplicate route path/method: {methods} {path}")]
     DuplicateRoutePathMethod { path: String, methods: String },
 }
+
+/// Failure from [`Bundle::reverse_checked`](crate::bundles::Bundle::reverse_checked).
+#[derive(Debug, thiserror::Error, Clone, PartialEq, Eq)]
+pub enum ReverseError {
+    #[error("no route named '{0}'")]
+    UnknownRoute(String),
+
+    #[error("route '{route}' requires parameter '{param}'")]
+    MissingParam { route: String, param: String },
+
+    #[error("route '{route}' has no parameter '{param}'")]
+    UnknownParam { route: String, param: String },
+}
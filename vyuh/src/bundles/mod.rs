@@ -24,7 +24,7 @@ use crate::{
 
 use openapi::DocEngine;
 
-pub use error::BundleError;
+pub use error::{BundleError, ReverseError};
 pub use openapi::{OpenApiConf, OpenApiViewerConf};
 pub use part::{
     BundlePart, asset_dir, bundle, command, cron, periodic, pgnotify, route, service, signal, task,
@@ -73,10 +73,11 @@ impl IntoBundle for axum::Router<Site> {
 /// the application.
 ///
 /// Build one with [`bundle()`], then compose with [`merge`], [`with_prefix`],
-/// [`layer`], [`with_tags`], and [`with_openapi`].
+/// [`with_namespace`], [`layer`], [`with_tags`], and [`with_openapi`].
 ///
 /// [`merge`]: Bundle::merge
 /// [`with_prefix`]: Bundle::with_prefix
+/// [`with_namespace`]: Bundle::with_namespace
 /// [`layer`]: Bundle::layer
 /// [`with_tags`]: Bundle::with_tags
 /// [`with_openapi`]: Bundle::with_openapi
@@ -186,6 +187,28 @@ impl Bundle {
         self
     }
 
+    /// Lists `HEAD` alongside `GET` on every route in this bundle.
+    ///
+    /// Axum already answers `HEAD` for `GET` routes by running the handler and
+    /// dropping the body; this records it in the operation metadata. Paths
+    /// where another route claims `HEAD` explicitly are left alone.
+    pub fn with_auto_head(mut self) -> Self {
+        let explicit: Vec<String> = self
+            .iter_routes()
+            .filter(|op| op.methods.contains(routes::Methods::HEAD))
+            .map(|op| op.path.clone())
+            .collect();
+        for op in self.ops.values_mut() {
+            if op.kind == OperationKind::Route
+                && op.methods.contains(routes::Methods::GET)
+                && !explicit.contains(&op.path)
+            {
+                op.methods |= routes::Methods::HEAD;
+            }
+        }
+        self
+    }
+
     /// Merges another bundle into this one.
     ///
     /// Routes, operations, services, signals, emitters, tasks, and commands from
@@ -229,6 +252,31 @@ impl Bundle {
         self
     }
 
+    /// Prefixes every route name in this bundle with `namespace:`.
+    ///
+    /// Combine with [`with_prefix`](Bundle::with_prefix) to mount an app under
+    /// both a URL prefix and a reverse namespace, e.g. `site.reverse("blog:index", &[])`.
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        if namespace.trim().is_empty() || namespace.contains(char::is_whitespace) {
+            self.errors.push(BundleError::InvalidRouteName {
+                name: namespace.to_string(),
+                reason: "namespace must be non-empty and contain no whitespace".to_string(),
+            });
+            return self;
+        }
+
+        let mut name_index = BTreeMap::new();
+        for (name, id) in std::mem::take(&mut self.name_index) {
+            let name = format!("{namespace}:{name}");
+            if let Some(op) = self.ops.get_mut(&id) {
+                op.name = name.clone();
+            }
+            name_index.insert(name, id);
+        }
+        self.name_index = name_index;
+        self
+    }
+
     /// Applies a middleware layer to all routes in this bundle.
     ///
     /// Accepts any type implementing [`Middleware`]. If the middleware provides a
@@ -276,22 +324,95 @@ impl Bundle {
 
     /// Reverse-resolves a named route to its URL, filling in path parameters.
     ///
-    /// Returns `None` if no route with that name is registered.
+    /// Arguments that name no path parameter become the query string, sorted
+    /// by key. Returns `None` if no route with that name is registered.
     pub fn reverse(&self, name: &str, args: &[(&str, &str)]) -> Option<String> {
         let id = self.name_index.get(name)?;
-        let op = self.ops.get(id)?;
-        let mut path = op.path.to_string();
-        for (k, v) in args {
-            let placeholder = format!("{{{k}}}");
-            if path.contains(&placeholder) {
-                let encoded = utf8_percent_encode(v, NON_ALPHANUMERIC).to_string();
-                path = path.replace(&placeholder, &encoded);
-            }
+        reverse_path(&self.ops.get(id)?.path, args)
+    }
+
+    /// Iterates over the named HTTP routes, in name order.
+    pub fn iter_routes(&self) -> impl Iterator<Item = &crate::callables::Operation> {
+        self.name_index.values().filter_map(|id| self.ops.get(id))
+    }
+
+    /// Routes whose path template matches `path`, one per registered method
+    /// set. `path` may be a concrete URL path (`/users/42`) or a template
+    /// (`/users/{id}`). Literal segments compare ASCII case-insensitively and
+    /// a trailing slash or query string is ignored, so it suits lookups typed
+    /// by a person; the router itself stays case-sensitive.
+    pub fn find_by_path(&self, path: &str) -> Vec<&crate::callables::Operation> {
+        self.iter_routes()
+            .filter(|op| path_matches(&op.path, path))
+            .collect()
+    }
+
+    /// Routes named under `namespace`, including nested ones: `"blog"` yields
+    /// `blog:index` and `blog:admin:list`, but not `blogroll:index`.
+    pub fn iter_namespace<'a>(
+        &'a self,
+        namespace: &str,
+    ) -> impl Iterator<Item = &'a crate::callables::Operation> + 'a {
+        let prefix = format!("{}:", namespace.trim_end_matches(':'));
+        self.name_index
+            .range(prefix.clone()..)
+            .take_while(move |(name, _)| name.starts_with(&prefix))
+            .filter_map(|(_, id)| self.ops.get(id))
+    }
+
+    /// Route name to path template, for reversing once the bundle is consumed.
+    pub(crate) fn route_paths(&self) -> BTreeMap<String, String> {
+        self.name_index
+            .iter()
+            .filter_map(|(name, id)| Some((name.clone(), self.ops.get(id)?.path.clone())))
+            .collect()
+    }
+
+    /// Like [`reverse`](Bundle::reverse), but errors on an unknown route name,
+    /// a missing path parameter, or an argument the path does not declare.
+    pub fn reverse_checked(
+        &self,
+        name: &str,
+        args: &[(&str, &str)],
+    ) -> Result<String, ReverseError> {
+        let op = self
+            .name_index
+            .get(name)
+            .and_then(|id| self.ops.get(id))
+            .ok_or_else(|| ReverseError::UnknownRoute(name.to_string()))?;
+        let params = path_params(&op.path);
+        if let Some((key, _)) = args
+            .iter()
+            .find(|(key, _)| !params.iter().any(|(param, _)| param == key))
+        {
+            return Err(ReverseError::UnknownParam {
+                route: name.to_string(),
+                param: key.to_string(),
+            });
         }
-        if path.contains('{') || path.contains('}') {
-            return None;
+        let mut path = op.path.clone();
+        for (param, wildcard) in params {
+            let Some((_, value)) = args.iter().find(|(key, _)| *key == param) else {
+                return Err(ReverseError::MissingParam {
+                    route: name.to_string(),
+                    param: param.to_string(),
+                });
+            };
+            let (placeholder, encoded) = if wildcard {
+                let segments = value
+                    .split('/')
+                    .map(|segment| utf8_percent_encode(segment, NON_ALPHANUMERIC).to_string());
+                (
+                    format!("{{*{param}}}"),
+                    segments.collect::<Vec<_>>().join("/"),
+                )
+            } else {
+                let encoded = utf8_percent_encode(value, NON_ALPHANUMERIC).to_string();
+                (format!("{{{param}}}"), encoded)
+            };
+            path = path.replace(&placeholder, &encoded);
         }
-        Some(path)
+        Ok(path)
     }
 
     // -----------------------------------------------------------------------
@@ -401,6 +522,65 @@ fn validate_route_path(path: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Fills `{param}` placeholders in `template` from `args`; the remaining
+/// arguments become a sorted query string. `None` if a parameter is missing.
+pub(crate) fn reverse_path(template: &str, args: &[(&str, &str)]) -> Option<String> {
+    let params = path_params(template);
+    let mut path = template.to_string();
+    let mut query = Vec::new();
+    for (k, v) in args {
+        if !params.iter().any(|(param, _)| param == k) {
+            query.push((*k, *v));
+            continue;
+        }
+        let placeholder = format!("{{{k}}}");
+        let encoded = utf8_percent_encode(v, NON_ALPHANUMERIC).to_string();
+        path = path.replace(&placeholder, &encoded);
+    }
+    if path.contains('{') || path.contains('}') {
+        return None;
+    }
+    if !query.is_empty() {
+        query.sort();
+        path.push('?');
+        path.push_str(&serde_urlencoded::to_string(&query).ok()?);
+    }
+    Some(path)
+}
+
+/// Placeholder names in a route path, flagged `true` for `{*wildcard}` captures.
+fn path_params(path: &str) -> Vec<(&str, bool)> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(name, _)| match name.strip_prefix('*') {
+            Some(name) => (name, true),
+            None => (name, false),
+        })
+        .collect()
+}
+
+/// Whether `path` fits the route `template`: `{param}` takes one segment and
+/// `{*rest}` the remainder.
+fn path_matches(template: &str, path: &str) -> bool {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let mut wanted = template.trim_end_matches('/').split('/');
+    let mut given = path.trim_end_matches('/').split('/');
+    loop {
+        match (wanted.next(), given.next()) {
+            (None, None) => return true,
+            (Some(seg), _) if seg.starts_with("{*") => return true,
+            (Some(seg), Some(part)) if seg.starts_with('{') && seg.ends_with('}') => {
+                if part.is_empty() {
+                    return false;
+                }
+            }
+            (Some(seg), Some(part)) if seg.eq_ignore_ascii_case(part) => {}
+            _ => return false,
+        }
+    }
+}
+
 fn validate_route_prefix(path: &str) -> Result<(), String> {
     validate_route_path(path)?;
     if path == "/" {
@@ -511,6 +691,127 @@ mod tests {
         assert_ne!(op.bundle_id, Some(parent_id));
     }
 
+    #[test]
+    fn namespace_and_prefix_apply_to_reverse() {
+        let app = crate::bundles::bundle([crate::bundles::route(
+            ping,
+            RouteConf {
+                name: "ping".into(),
+                methods: routes::Methods::GET,
+                path: "/ping".into(),
+                slash: None,
+            },
+        )])
+        .with_prefix("/blog")
+        .with_namespace("blog");
+        let site = Bundle::new().merge(app);
+
+        assert_eq!(
+            site.reverse("blog:ping", &[]).as_deref(),
+            Some("/blog/ping")
+        );
+        assert!(site.reverse("ping", &[]).is_none());
+        assert!(site.validate().is_ok());
+    }
+
+    #[test]
+    fn routes_can_be_found_by_path_and_namespace() {
+        let blog = Bundle::new()
+            .merge(bundle_with_route(route_op(
+                "index",
+                "/",
+                routes::Methods::GET,
+            )))
+            .merge(bundle_with_route(route_op(
+                "post",
+                "/posts/{slug}",
+                routes::Methods::GET,
+            )))
+            .with_prefix("/blog")
+            .with_namespace("blog");
+        let files = bundle_with_route(route_op("files", "/files/{*path}", routes::Methods::GET))
+            .with_namespace("blogroll");
+        let site = Bundle::new().merge(blog).merge(files);
+
+        let names = |ops: Vec<&crate::callables::Operation>| -> Vec<String> {
+            ops.into_iter().map(|op| op.name.clone()).collect()
+        };
+        assert_eq!(
+            names(site.find_by_path("/Blog/posts/hello?x=1")),
+            ["blog:post"]
+        );
+        assert_eq!(
+            names(site.find_by_path("/blog/posts/{slug}")),
+            ["blog:post"]
+        );
+        assert_eq!(names(site.find_by_path("/blog/")), ["blog:index"]);
+        assert_eq!(
+            names(site.find_by_path("/files/a/b.txt")),
+            ["blogroll:files"]
+        );
+        assert!(site.find_by_path("/blog/posts").is_empty());
+        assert!(site.find_by_path("/blog/posts/a/b").is_empty());
+
+        let blog_names: Vec<&str> = site
+            .iter_namespace("blog")
+            .map(|op| op.name.as_str())
+            .collect();
+        assert_eq!(blog_names, ["blog:index", "blog:post"]);
+        assert_eq!(site.iter_routes().count(), 3);
+    }
+
+    async fn echo(upgrade: routes::WebSocketUpgrade) -> routes::Response {
+        upgrade.on_upgrade(|_socket| async {})
+    }
+
+    #[test]
+    fn websocket_route_is_flagged() {
+        let bundle = crate::bundles::bundle([crate::bundles::route(
+            echo,
+            RouteConf {
+                name: "echo".into(),
+                methods: routes::Methods::GET,
+                path: "/echo".into(),
+                slash: None,
+            },
+        )]);
+        let route_id = *bundle.name_index.get("echo").unwrap();
+
+        assert!(bundle.ops[&route_id].is_websocket());
+    }
+
+    #[test]
+    fn reverse_checked_requires_exact_params() {
+        let bundle = bundle_with_route(route_op(
+            "file",
+            "/notes/{id}/files/{*path}",
+            routes::Methods::GET,
+        ));
+
+        assert_eq!(
+            bundle.reverse_checked("file", &[("id", "7"), ("path", "a b/c.txt")]),
+            Ok("/notes/7/files/a%20b/c%2Etxt".to_string())
+        );
+        assert!(matches!(
+            bundle.reverse_checked("file", &[("id", "7")]),
+            Err(ReverseError::MissingParam { param, .. }) if param == "path"
+        ));
+        assert!(matches!(
+            bundle.reverse_checked("file", &[("id", "7"), ("path", "x"), ("page", "2")]),
+            Err(ReverseError::UnknownParam { param, .. }) if param == "page"
+        ));
+        assert_eq!(
+            bundle.reverse_checked("missing", &[]),
+            Err(ReverseError::UnknownRoute("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn blank_namespace_is_rejected() {
+        let bundle = Bundle::new().with_namespace(" ");
+        assert!(bundle.validate().is_err());
+    }
+
     #[test]
     fn validates_route_path() {
         let bundle = Bundle::new();
@@ -553,6 +854,30 @@ mod tests {
         assert!(matches!(err, BundleError::DuplicateRoutePathMethod { .. }));
     }
 
+    #[test]
+    fn auto_head_skips_paths_with_explicit_head() {
+        let mut bundle = bundle_with_route(route_op("list", "/notes", routes::Methods::GET));
+        let explicit = route_op("probe", "/items", routes::Methods::HEAD);
+        let get_items = route_op("items", "/items", routes::Methods::GET);
+        for op in [explicit, get_items] {
+            bundle.name_index.insert(op.name.clone(), op.id);
+            bundle.ops.insert(op.id, op);
+        }
+        let bundle = bundle.with_auto_head();
+        let methods = |name: &str| {
+            bundle
+                .iter_routes()
+                .find(|op| op.name == name)
+                .unwrap()
+                .methods
+        };
+        assert_eq!(
+            methods("list"),
+            routes::Methods::GET | routes::Methods::HEAD
+        );
+        assert_eq!(methods("items"), routes::Methods::GET);
+    }
+
     #[test]
     fn allows_same_path_with_different_methods() {
         let existing = route_op("list_notes", "/notes", routes::Methods::GET);
@@ -593,6 +918,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reverse_appends_extra_args_as_sorted_query() {
+        let op = route_op("note", "/notes/{id}", routes::Methods::GET);
+        let bundle = bundle_with_route(op);
+        assert_eq!(
+            bundle.reverse("note", &[("q", "a b&c"), ("id", "7"), ("page", "2")]),
+            Some("/notes/7?page=2&q=a+b%26c".to_string())
+        );
+    }
+
     #[test]
     fn reverse_returns_none_when_params_are_missing() {
         let op = route_op("note", "/notes/{id}", routes::Methods::GET);
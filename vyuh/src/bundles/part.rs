@@ -1,10 +1,12 @@
-use std::sync::Arc;
+use std::{convert::Infallible, sync::Arc};
+
+use axum::{body::Body, http::Request, routing::Route};
 
 use crate::{
     Error, Site,
     callables::{self},
     commands::{self},
-    embed, emitters,
+    embed, emitters, routes,
     services::{Service, ServiceBuildContext, ServiceHandler, ServiceInstance},
     signals::{self, SignalConf},
     tasks::TaskHandlerConf,
@@ -46,6 +48,34 @@ impl BundlePart {
         }
         self
     }
+
+    /// Applies a middleware layer to this route only.
+    ///
+    /// Works like [`Bundle::layer`] scoped to a single route: the layer wraps
+    /// the route's method router and any [`LayerSpec`] is added to its
+    /// operation. Non-route parts are returned unchanged.
+    ///
+    /// [`LayerSpec`]: crate::callables::LayerSpec
+    pub fn layer<M>(mut self, mw: M) -> Self
+    where
+        M: routes::Middleware,
+        <M::Layer as tower::Layer<Route>>::Service:
+            tower::Service<Request<Body>> + Clone + Send + Sync + 'static,
+        <<M::Layer as tower::Layer<Route>>::Service as tower::Service<Request<Body>>>::Response:
+            axum::response::IntoResponse + 'static,
+        <<M::Layer as tower::Layer<Route>>::Service as tower::Service<Request<Body>>>::Error:
+            Into<Infallible> + 'static,
+        <<M::Layer as tower::Layer<Route>>::Service as tower::Service<Request<Body>>>::Future:
+            Send + 'static,
+    {
+        if let BundlePartInner::Route(router, mut op) = self.part {
+            if let Some(spec) = mw.layer_spec() {
+                op.layers.push(spec);
+            }
+            self.part = BundlePartInner::Route(router.layer(mw.into_layer()), op);
+        }
+        self
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -139,7 +169,11 @@ where
     op.slash_policy = meta.slash;
     op = op.with_conf(&meta);
 
-    let router = axum::routing::on(meta.methods.into(), handler);
+    let router = if meta.methods == routes::Methods::ANY {
+        axum::routing::any(handler)
+    } else {
+        axum::routing::on(meta.methods.into(), handler)
+    };
     BundlePart {
         operation: None,
         part: BundlePartInner::Route(router, op),
@@ -8,7 +8,7 @@ use crate::routes::{BodyBytes, Form, Json, JsonStr, Path, Query};
 use crate::validation::{Valid, Validate, ValidationSchema};
 use crate::{
     Site,
-    auth::{ApiKey, AuthUser},
+    auth::{ApiKey, AuthUser, MaybeUser},
     site,
 };
 use schemars::JsonSchema;
@@ -387,6 +387,13 @@ impl IntoArgPart for AuthUser {
     }
 }
 
+/// Anonymous access is allowed, so no security requirement is documented.
+impl IntoArgPart for MaybeUser {
+    fn into_arg_part() -> ArgPart {
+        ArgPart::Ignore
+    }
+}
+
 impl IntoArgPart for ApiKey {
     fn into_arg_part() -> ArgPart {
         ArgPart::Security {
@@ -5,7 +5,7 @@ use std::borrow::Cow;
 use crate::middlewares::SlashPolicy;
 use crate::routes::Methods;
 
-use super::{ArgSpec, CallSpec, Callable, LayerSpec, ReturnSpec};
+use super::{ArgPart, ArgSpec, CallSpec, Callable, LayerSpec, ReturnSpec};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 
@@ -66,6 +66,13 @@ impl Operation {
         self
     }
 
+    /// Whether the handler takes a `WebSocketUpgrade`; OpenAPI skips these.
+    pub fn is_websocket(&self) -> bool {
+        self.args
+            .iter()
+            .any(|arg| matches!(arg.part, ArgPart::Upgrade))
+    }
+
     /// Extract individual HTTP methods from the Methods.
     /// Returns a list of method strings like "GET", "POST", etc.
     /// Handles combined filters (e.g., GET | POST).
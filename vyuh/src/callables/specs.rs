@@ -259,6 +259,9 @@ pub enum ArgPart {
 
     /// Multi-tenancy zone identifier
     Zone,
+
+    /// WebSocket upgrade; marks the route as a websocket endpoint
+    Upgrade,
 }
 
 /// Describes how a handler return value is serialized into responses.
@@ -236,7 +236,7 @@ impl callables::IntoArgPart for Channels {
 
 impl callables::IntoArgPart for WebSocketUpgrade {
     fn into_arg_part() -> callables::ArgPart {
-        callables::ArgPart::Ignore
+        callables::ArgPart::Upgrade
     }
 }
 
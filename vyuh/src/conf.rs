@@ -3,7 +3,10 @@
 //! Secrets and deployment-specific values go in env vars.
 //! Project structure and logic go in source code.
 
-use std::{ffi::OsString, path::PathBuf};
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
 
 use crate::{
     auth::{AuthConf, JwtKeySource},
@@ -46,6 +49,9 @@ pub enum ConfError {
     #[error("missing required field: {0}")]
     MissingField(String),
 
+    #[error("cannot load config file '{path}': {reason}")]
+    File { path: String, reason: String },
+
     #[error("{0}")]
     Other(String),
 }
@@ -111,6 +117,16 @@ pub struct SiteConf {
     #[serde(default = "default_secret_key")]
     pub secret_key: String,
 
+    /// File holding the secret key; overrides `secret_key` when the site is
+    /// built. Absolute or relative to project_dir.
+    #[serde(default)]
+    pub secret_key_file: Option<String>,
+
+    /// File holding the database URL; overrides `database` when the site is
+    /// built. Absolute or relative to project_dir.
+    #[serde(default)]
+    pub database_url_file: Option<String>,
+
     /// absolute or relative to project_dir
     pub media_dir: Option<String>,
 
@@ -143,6 +159,10 @@ pub struct SiteConf {
 
     #[serde(skip)]
     pub errors: ErrorConf,
+
+    /// File read by [`SiteConf::from_file`]; `SIGHUP` reloads from it.
+    #[serde(skip)]
+    pub config_file: Option<String>,
 }
 
 impl Default for SiteConf {
@@ -154,6 +174,8 @@ impl Default for SiteConf {
             project_dir: project_dir().as_os_str().to_string_lossy().to_string(),
             database: Default::default(),
             secret_key,
+            secret_key_file: None,
+            database_url_file: None,
             media_dir: None,
             templates: TemplateConf::default(),
             touch_reload: None,
@@ -168,6 +190,7 @@ impl Default for SiteConf {
             logging: logging::LoggingConf::default(),
             http: HttpConf::default(),
             errors: ErrorConf::default(),
+            config_file: None,
         }
     }
 }
@@ -184,6 +207,28 @@ impl SiteConf {
         Self::default().with_env()
     }
 
+    /// Load a TOML config file over the defaults, then apply env vars.
+    ///
+    /// Precedence is env > file > defaults; tables in the file may be
+    /// partial, keys left out keep their default value.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfError> {
+        let path = path.as_ref();
+        let file_error = |reason: String| ConfError::File {
+            path: path.display().to_string(),
+            reason,
+        };
+        let text = std::fs::read_to_string(path).map_err(|e| file_error(e.to_string()))?;
+        let file: serde_json::Value =
+            toml::from_str(&text).map_err(|e| file_error(e.to_string()))?;
+        let mut merged =
+            serde_json::to_value(Self::default()).map_err(|e| file_error(e.to_string()))?;
+        merge_values(&mut merged, file);
+        let mut conf: Self =
+            serde_json::from_value(merged).map_err(|e| file_error(e.to_string()))?;
+        conf.config_file = Some(path.display().to_string());
+        conf.with_env()
+    }
+
     /// Load .env files and parse env vars.
     pub fn from_env_with_files() -> Result<Self, ConfError> {
         Self::load_env_files();
@@ -211,6 +256,34 @@ impl SiteConf {
         }
     }
 
+    /// Replace `secret_key` and `database` with the contents of their
+    /// `*_file` counterparts, if set. Trailing whitespace is trimmed.
+    pub fn load_secret_files(mut self) -> Result<Self, ConfError> {
+        if let Some(file) = self.secret_key_file.clone() {
+            self.secret_key = self.read_secret_file(&file, "secret_key_file")?;
+        }
+        if let Some(file) = self.database_url_file.clone() {
+            let url = self.read_secret_file(&file, "database_url_file")?;
+            self.database = DbConf::from_url(&url).map_err(|e| ConfError::InvalidValue {
+                field: "database_url_file".into(),
+                reason: e.to_string(),
+                expected: None,
+            })?;
+        }
+        Ok(self)
+    }
+
+    fn read_secret_file(&self, file: &str, field: &str) -> Result<String, ConfError> {
+        let path = PathBuf::from(&self.project_dir).join(file);
+        std::fs::read_to_string(&path)
+            .map(|value| value.trim_end().to_string())
+            .map_err(|e| ConfError::InvalidPath {
+                field: field.into(),
+                path: path.display().to_string(),
+                reason: e.to_string(),
+            })
+    }
+
     // Chainable setter methods
 
     pub fn host(mut self, host: impl Into<String>) -> Self {
@@ -238,6 +311,16 @@ impl SiteConf {
         self
     }
 
+    pub fn secret_key_file(mut self, path: impl Into<String>) -> Self {
+        self.secret_key_file = Some(path.into());
+        self
+    }
+
+    pub fn database_url_file(mut self, path: impl Into<String>) -> Self {
+        self.database_url_file = Some(path.into());
+        self
+    }
+
     pub fn media_dir(mut self, dir: impl Into<String>) -> Self {
         self.media_dir = Some(dir.into());
         self
@@ -248,6 +331,27 @@ impl SiteConf {
         self
     }
 
+    /// Adds a template filter; see [`TemplateConf::with_filter`].
+    pub fn with_template_filter<F, Rv, Args>(mut self, name: impl Into<String>, f: F) -> Self
+    where
+        F: minijinja::functions::Function<Rv, Args>,
+        Rv: minijinja::value::FunctionResult,
+        Args: for<'a> minijinja::value::FunctionArgs<'a>,
+    {
+        self.templates = self.templates.with_filter(name, f);
+        self
+    }
+
+    /// Adds a template global; see [`TemplateConf::with_global`].
+    pub fn with_template_global(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<minijinja::Value>,
+    ) -> Self {
+        self.templates = self.templates.with_global(name, value);
+        self
+    }
+
     pub fn uploads(mut self, uploads: UploadConf) -> Self {
         self.uploads = uploads;
         self
@@ -273,6 +377,50 @@ impl SiteConf {
         self
     }
 
+    /// Header used to read, generate, and echo the request id.
+    pub fn request_id_header(mut self, header: impl Into<String>) -> Self {
+        self.http.request_id.header = header.into();
+        self
+    }
+
+    /// Serves liveness at `path` and readiness at `{path}/ready`.
+    pub fn with_healthcheck(mut self, path: impl Into<String>) -> Self {
+        let path = path.into();
+        self.http.health.ready_path = format!("{}/ready", path.trim_end_matches('/'));
+        self.http.health.path = path;
+        self.http.health.enabled = true;
+        self
+    }
+
+    /// Serves a JSON list of every named route at `path`; see
+    /// `RouteInspectorConf` for the auth default.
+    pub fn with_route_inspector(mut self, path: impl Into<String>) -> Self {
+        self.http.route_inspector.path = path.into();
+        self.http.route_inspector.enabled = true;
+        self
+    }
+
+    /// Compresses responses when the client sends `Accept-Encoding`.
+    pub fn with_compression(mut self) -> Self {
+        self.http.compression.enabled = true;
+        self
+    }
+
+    /// Enables per-client rate limiting: `requests` per `per`, bursting up to
+    /// `requests`. See `RateLimitConf` to key logged-in users separately.
+    pub fn with_rate_limit(mut self, requests: u32, per: std::time::Duration) -> Self {
+        self.http.rate_limit.requests = requests;
+        self.http.rate_limit.per_ms = u64::try_from(per.as_millis()).unwrap_or(u64::MAX);
+        self.http.rate_limit.enabled = true;
+        self
+    }
+
+    /// Requires a CSRF token on unsafe form posts; see `CsrfConf`.
+    pub fn with_csrf(mut self) -> Self {
+        self.http.csrf.enabled = true;
+        self
+    }
+
     pub fn touch_reload(mut self, path: impl Into<String>) -> Self {
         self.touch_reload = Some(path.into());
         self
@@ -309,13 +457,19 @@ impl SiteConf {
     }
 
     /// Validate config. Returns Ok(()) if valid, or Err(ConfError::Many) with all errors.
+    ///
+    /// Called first thing when the site is built, so every problem is
+    /// reported at once instead of failing on the first one later.
     pub fn validate(&self) -> Result<(), ConfError> {
         let mut errors = Vec::new();
 
         self.validate_required(&mut errors);
         self.validate_database(&mut errors);
+        self.validate_auth(&mut errors);
         self.validate_paths(&mut errors);
         self.console.validate(&mut errors);
+        self.http.cors.validate(&mut errors);
+        self.http.rate_limit.validate(&mut errors);
 
         if errors.is_empty() {
             Ok(())
@@ -336,10 +490,10 @@ impl SiteConf {
             errors.push(ConfError::InvalidValue {
                 field: "secret_key".into(),
                 reason: format!(
-                    "must be at least {} characters for auth signing",
+                    "must be at least {} bytes for auth signing",
                     self.auth.min_secret_len
                 ),
-                expected: Some(format!("{} or more characters", self.auth.min_secret_len)),
+                expected: Some(format!("{} or more bytes", self.auth.min_secret_len)),
             });
         }
         #[cfg(not(debug_assertions))]
@@ -366,6 +520,15 @@ impl SiteConf {
                 reason: "cannot be empty".into(),
             });
         }
+        if let Some(tz) = &self.tz
+            && tz.parse::<chrono_tz::Tz>().is_err()
+        {
+            errors.push(ConfError::InvalidValue {
+                field: "tz".into(),
+                reason: format!("unknown timezone '{tz}'"),
+                expected: Some("an IANA name such as 'Europe/Berlin'".into()),
+            });
+        }
     }
 
     fn validate_database(&self, errors: &mut Vec<ConfError>) {
@@ -384,6 +547,12 @@ impl SiteConf {
                 field: "database.url".into(),
                 reason: "cannot be empty".into(),
             });
+        } else if let Err(e) = url::Url::parse(&self.database.url) {
+            errors.push(ConfError::InvalidValue {
+                field: "database.url".into(),
+                reason: e.to_string(),
+                expected: Some("a URL such as 'postgres://user@host/db'".into()),
+            });
         }
         if self.database.max_connections == 0 {
             errors.push(ConfError::InvalidValue {
@@ -404,6 +573,22 @@ impl SiteConf {
         }
     }
 
+    fn validate_auth(&self, errors: &mut Vec<ConfError>) {
+        let ttls = [
+            ("auth.access_ttl", self.auth.access_ttl),
+            ("auth.refresh_ttl", self.auth.refresh_ttl),
+        ];
+        for (field, ttl) in ttls {
+            if ttl <= 0 {
+                errors.push(ConfError::InvalidValue {
+                    field: field.into(),
+                    reason: format!("must be positive, got {ttl}"),
+                    expected: Some("seconds > 0".into()),
+                });
+            }
+        }
+    }
+
     fn validate_paths(&self, errors: &mut Vec<ConfError>) {
         let base = PathBuf::from(&self.project_dir);
 
@@ -422,6 +607,23 @@ impl SiteConf {
     }
 }
 
+/// Overlays `patch` onto `base`, recursing into objects present in both.
+fn merge_values(base: &mut serde_json::Value, patch: serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(slot) => merge_values(slot, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (slot, value) => *slot = value,
+    }
+}
+
 fn apply_env_patches(conf: &mut SiteConf, prefix: Option<&str>) -> Result<(), ConfError> {
     let strip_prefix = |key: &str, pref: Option<&str>| -> String {
         pref.and_then(|p| key.strip_prefix(p))
@@ -446,6 +648,8 @@ fn apply_env_patches(conf: &mut SiteConf, prefix: Option<&str>) -> Result<(), Co
                 }
             },
             "secret_key" => conf.secret_key = value,
+            "secret_key_file" => conf.secret_key_file = Some(value),
+            "database_url_file" => conf.database_url_file = Some(value),
             "host" => conf.host = value,
             "port" => match value.parse::<u16>() {
                 Ok(p) => conf.port = p,
@@ -580,3 +784,54 @@ fn validate_file_writable(base: &PathBuf, file: &str, field: &str, errors: &mut
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_file_overlays_partial_tables_on_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("site.toml");
+        std::fs::write(&path, "port = 9000\n\n[http.cors]\nenabled = true\n").unwrap();
+
+        let conf = SiteConf::from_file(&path).unwrap();
+        assert_eq!(conf.port, 9000);
+        assert!(conf.http.cors.enabled);
+        assert_eq!(
+            conf.http.allow.auto_options,
+            HttpConf::default().allow.auto_options
+        );
+    }
+
+    #[test]
+    fn from_file_reports_the_path_on_parse_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.toml");
+        std::fs::write(&path, "port = \"not a number\"\n").unwrap();
+
+        let err = SiteConf::from_file(&path).unwrap_err();
+        assert!(matches!(&err, ConfError::File { path: p, .. } if p.ends_with("broken.toml")));
+    }
+
+    #[test]
+    fn validate_reports_every_problem_at_once() {
+        let mut conf = SiteConf::default().timezone("Mars/Olympus");
+        conf.database.url = "not a url".into();
+        conf.auth.access_ttl = 0;
+
+        let Err(ConfError::Many(errors)) = conf.validate() else {
+            panic!("expected ConfError::Many");
+        };
+        let fields: Vec<_> = errors
+            .iter()
+            .filter_map(|e| match e {
+                ConfError::InvalidValue { field, .. } => Some(field.as_str()),
+                _ => None,
+            })
+            .collect();
+        for field in ["tz", "database.url", "auth.access_ttl"] {
+            assert!(fields.contains(&field), "{field} missing from {fields:?}");
+        }
+    }
+}
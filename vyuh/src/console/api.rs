@@ -12,8 +12,9 @@ use crate::{
         query::{
             OperationQuery, TaskQuery, filter_operations, is_console_operation, task_limit_max,
         },
-        types::{ConfigOut, OperationOut, Page, SessionOut, TaskDetailOut, TaskOut},
+        types::{ConfigOut, OperationOut, Page, ScheduleOut, SessionOut, TaskDetailOut, TaskOut},
     },
+    emitters::EmitterError,
     routes::{Json, Path, Query},
 };
 
@@ -132,6 +133,36 @@ pub async fn task_detail(
     Ok(Json(TaskDetailOut::from(&record)))
 }
 
+pub async fn schedules(
+    site: Site,
+    ConsoleSessionUser(_user): ConsoleSessionUser,
+) -> Json<Vec<ScheduleOut>> {
+    Json(
+        site.console_schedules()
+            .iter()
+            .map(ScheduleOut::from)
+            .collect(),
+    )
+}
+
+pub async fn run_schedule(
+    site: Site,
+    ConsoleSessionUser(user): ConsoleSessionUser,
+    Path(name): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    if !user.can_operate() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    match site.console_run_schedule(&name).await {
+        Ok(()) => Ok(Json(serde_json::json!({ "ok": true }))),
+        Err(EmitterError::UnknownSchedule(_)) => Err(StatusCode::NOT_FOUND),
+        Err(err) => {
+            tracing::error!(schedule = %name, "Console schedule run failed: {}", err);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 pub async fn status(
     site: Site,
     ConsoleSessionUser(_user): ConsoleSessionUser,
@@ -38,6 +38,14 @@ pub struct ConsoleUser {
     pub role_names: Vec<&'static str>,
 }
 
+impl ConsoleUser {
+    /// Whether the user may trigger operations, not just inspect them.
+    pub fn can_operate(&self) -> bool {
+        let mask = ConsoleRole::Operator.to_role_type() | ConsoleRole::Admin.to_role_type();
+        self.roles & mask != 0
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct ConsoleRuntime {
     bootstrap: Arc<Mutex<Option<BootstrapToken>>>,
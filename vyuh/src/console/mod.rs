@@ -142,6 +142,18 @@ fn routes() -> crate::bundles::Bundle {
             Methods::GET,
             api::task_detail,
         ),
+        route!(
+            "console_api_schedules",
+            "/api/schedules",
+            Methods::GET,
+            api::schedules,
+        ),
+        route!(
+            "console_api_schedule_run",
+            "/api/schedules/{name}/run",
+            Methods::POST,
+            api::run_schedule,
+        ),
         route!(
             "console_api_status",
             "/api/status",
@@ -669,6 +681,96 @@ mod tests {
         assert!(!tasks.contains("No task records yet."));
     }
 
+    #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+    struct ConsoleTick;
+
+    async fn console_tick() -> Data<ConsoleTick> {
+        Data::new(ConsoleTick)
+    }
+
+    #[tokio::test]
+    async fn console_schedules_list_and_run_for_operators_only() {
+        let conf = SiteConf::default()
+            .host("example.com")
+            .log_init(false)
+            .console(ConsoleConf::default().enabled(true));
+        let bundle = app_bundle().merge(bundles::bundle([bundles::periodic::<ConsoleTick, _, _>(
+            console_tick,
+            crate::emitters::PeriodicConf {
+                interval: std::time::Duration::from_secs(3600),
+                target: crate::emitters::EmitTarget::Signal,
+            },
+        )]));
+        let site = Site::build(conf, bundle).await.unwrap();
+        let token = site
+            .console_runtime()
+            .and_then(|runtime| runtime.bootstrap_token())
+            .unwrap();
+        let client = TestClient::new(site);
+
+        client
+            .get("/console/api/schedules")
+            .send()
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        let login = client
+            .get(&format!("/console/login?token={token}"))
+            .send()
+            .await;
+        let cookie = login
+            .header(header::SET_COOKIE.as_str())
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .split(';')
+            .next()
+            .unwrap()
+            .to_string();
+
+        let schedules: serde_json::Value = client
+            .get("/console/api/schedules")
+            .header(header::COOKIE.as_str(), &cookie)
+            .send()
+            .await
+            .assert_status(StatusCode::OK)
+            .json()
+            .await;
+        let name = schedules[0]["name"].as_str().unwrap().to_string();
+        assert!(name.ends_with("console_tick"));
+        assert_eq!(schedules[0]["kind"], "periodic");
+        assert_eq!(schedules[0]["interval_secs"], 3600);
+
+        client
+            .post(&format!("/console/api/schedules/{name}/run"))
+            .send()
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        client
+            .post(&format!("/console/api/schedules/{name}/run"))
+            .header(header::COOKIE.as_str(), &cookie)
+            .send()
+            .await
+            .assert_status(StatusCode::OK);
+        client
+            .post("/console/api/schedules/missing/run")
+            .header(header::COOKIE.as_str(), &cookie)
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn console_viewers_cannot_operate() {
+        use crate::auth::BitRole;
+
+        let viewer = crate::console::ConsoleUser {
+            subject: "viewer".to_string(),
+            roles: crate::console::ConsoleRole::Viewer.to_role_type(),
+            role_names: vec!["viewer"],
+        };
+        assert!(!viewer.can_operate());
+    }
+
     #[tokio::test]
     async fn console_status_is_cached_within_ttl() {
         let conf = SiteConf::default()
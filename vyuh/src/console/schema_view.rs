@@ -161,6 +161,7 @@ fn arg_part(part: &ArgPart) -> (String, Option<&TypeSchema>, Option<String>) {
         }
         ArgPart::Security { scheme, .. } => (format!("security: {scheme}"), None, None),
         ArgPart::Zone => ("zone".into(), None, None),
+        ArgPart::Upgrade => ("websocket".into(), None, None),
         ArgPart::Ignore => ("runtime".into(), None, None),
     }
 }
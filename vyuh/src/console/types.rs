@@ -5,6 +5,7 @@ use crate::{
     Operation, OperationKind, Site,
     auth::JwtKeySource,
     callables::{ArgPart, ArgSpec, ReturnPart, ReturnSpec, TypeSchema},
+    emitters::ScheduleInfo,
     logging::LogSink,
     tasks::{TaskRecord, TaskStatus},
 };
@@ -108,6 +109,7 @@ fn arg_part(part: &ArgPart) -> (String, Option<String>, Option<String>) {
         ),
         ArgPart::Security { scheme, .. } => (format!("security: {scheme}"), None, None),
         ArgPart::Zone => ("zone".into(), None, None),
+        ArgPart::Upgrade => ("websocket".into(), None, None),
         ArgPart::Ignore => ("runtime".into(), None, None),
     }
 }
@@ -192,6 +194,25 @@ impl From<&TaskRecord> for TaskDetailOut {
     }
 }
 
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ScheduleOut {
+    pub name: String,
+    pub kind: String,
+    pub next_run: Option<String>,
+    pub interval_secs: Option<u64>,
+}
+
+impl From<&ScheduleInfo> for ScheduleOut {
+    fn from(info: &ScheduleInfo) -> Self {
+        Self {
+            name: info.name.clone(),
+            kind: info.kind.to_string(),
+            next_run: info.next_run.map(|value| value.to_rfc3339()),
+            interval_secs: info.interval_secs,
+        }
+    }
+}
+
 fn parse_json(value: &str) -> Option<serde_json::Value> {
     serde_json::from_str(value).ok()
 }
@@ -1,3 +1,4 @@
+use std::any::Any;
 use std::sync::Arc;
 
 use super::commons::{Arguments, Database};
@@ -6,6 +7,8 @@ use super::commons::{Arguments, Database};
 pub struct ArgValue {
     binder:
         Arc<dyn Fn(&mut Arguments<'static>) -> Result<(), sqlx::error::BoxDynError> + Send + Sync>,
+    /// SQL literal for `Statement::debug_query_with_args`; debug builds only.
+    literal: Option<Arc<str>>,
 }
 
 impl ArgValue {
@@ -18,11 +21,13 @@ impl ArgValue {
             + Sync
             + 'static,
     {
+        let literal = debug_literal(&val);
         Self {
             binder: Arc::new(move |args| {
                 use sqlx::Arguments as _;
                 args.add(val.clone())
             }),
+            literal,
         }
     }
 
@@ -32,6 +37,10 @@ impl ArgValue {
     ) -> Result<(), sqlx::error::BoxDynError> {
         (self.binder)(args)
     }
+
+    pub(crate) fn literal(&self) -> Option<Arc<str>> {
+        self.literal.clone()
+    }
 }
 
 impl<T> From<T> for ArgValue
@@ -42,3 +51,64 @@ where
         ArgValue::new(val)
     }
 }
+
+/// [`sql_literal`] of `val` in debug builds, `None` in release builds.
+pub(crate) fn debug_literal<T: Any>(val: &T) -> Option<Arc<str>> {
+    if !cfg!(debug_assertions) {
+        return None;
+    }
+    sql_literal(val).map(Arc::from)
+}
+
+/// `val` written as SQL, e.g. `'it''s'`, `30` or `NULL`, for the common
+/// scalar types and their `Option`s. Other types return `None`.
+fn sql_literal(val: &dyn Any) -> Option<String> {
+    macro_rules! plain {
+        ($($ty:ty),*) => {$(
+            if let Some(v) = val.downcast_ref::<$ty>() {
+                return Some(v.to_string());
+            }
+            if let Some(v) = val.downcast_ref::<Option<$ty>>() {
+                return Some(v.as_ref().map_or_else(|| "NULL".to_string(), |v| v.to_string()));
+            }
+        )*};
+    }
+    macro_rules! quoted {
+        ($($ty:ty),*) => {$(
+            if let Some(v) = val.downcast_ref::<$ty>() {
+                return Some(quote(&v.to_string()));
+            }
+            if let Some(v) = val.downcast_ref::<Option<$ty>>() {
+                return Some(v.as_ref().map_or_else(|| "NULL".to_string(), |v| quote(&v.to_string())));
+            }
+        )*};
+    }
+    plain!(
+        bool,
+        i8,
+        i16,
+        i32,
+        i64,
+        u8,
+        u16,
+        u32,
+        u64,
+        f32,
+        f64,
+        rust_decimal::Decimal
+    );
+    quoted!(
+        String,
+        &'static str,
+        uuid::Uuid,
+        chrono::NaiveDate,
+        chrono::NaiveTime,
+        chrono::NaiveDateTime,
+        chrono::DateTime<chrono::Utc>
+    );
+    None
+}
+
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
@@ -0,0 +1,144 @@
+use sqlx::Row as _;
+
+use crate::db::commons::{Arguments, Row};
+use crate::db::copy::CopyRow;
+
+/// Element type of a `#[column(array)]` field.
+///
+/// Postgres binds `Vec<T>` as a native array (`text[]`, `bigint[]`, ...).
+/// SQLite and MySQL have no arrays, so the list is stored as JSON instead.
+///
+/// The bounds follow the backend feature: SQLx array support here, serde on
+/// the other backends. A type that satisfies one set may not satisfy the
+/// other, so enabling `postgres` can reject an element type that compiled
+/// without it.
+#[cfg(feature = "postgres")]
+pub trait ArrayElement:
+    for<'q> sqlx::Encode<'q, sqlx::Postgres>
+    + for<'r> sqlx::Decode<'r, sqlx::Postgres>
+    + sqlx::Type<sqlx::Postgres>
+    + sqlx::postgres::PgHasArrayType
+    + Clone
+    + Send
+    + 'static
+{
+}
+
+#[cfg(feature = "postgres")]
+impl<T> ArrayElement for T where
+    T: for<'q> sqlx::Encode<'q, sqlx::Postgres>
+        + for<'r> sqlx::Decode<'r, sqlx::Postgres>
+        + sqlx::Type<sqlx::Postgres>
+        + sqlx::postgres::PgHasArrayType
+        + Clone
+        + Send
+        + 'static
+{
+}
+
+/// Element type of a `#[column(array)]` field.
+///
+/// Postgres binds `Vec<T>` as a native array (`text[]`, `bigint[]`, ...).
+/// SQLite and MySQL have no arrays, so the list is stored as JSON instead.
+///
+/// The bounds follow the backend feature: serde here, SQLx array support on
+/// Postgres. A type that satisfies one set may not satisfy the other, so
+/// enabling `postgres` can reject an element type that compiled without it.
+#[cfg(not(feature = "postgres"))]
+pub trait ArrayElement:
+    serde::Serialize + serde::de::DeserializeOwned + Clone + Send + 'static
+{
+}
+
+#[cfg(not(feature = "postgres"))]
+impl<T> ArrayElement for T where
+    T: serde::Serialize + serde::de::DeserializeOwned + Clone + Send + 'static
+{
+}
+
+/// Field type accepted by `#[column(array)]`: `Vec<T>`, or `Option<Vec<T>>`
+/// for a nullable column, where `None` is SQL `NULL`.
+pub trait ArrayColumn: Sized {
+    #[doc(hidden)]
+    fn bind_column(&self, args: &mut Arguments<'static>) -> Result<(), sqlx::Error>;
+
+    #[doc(hidden)]
+    fn copy_column(&self, row: &mut CopyRow) -> Result<(), sqlx::Error>;
+
+    #[doc(hidden)]
+    fn scan_column<I: sqlx::ColumnIndex<Row>>(row: &Row, index: I) -> Result<Self, sqlx::Error>;
+}
+
+impl<T: ArrayElement> ArrayColumn for Vec<T> {
+    fn copy_column(&self, row: &mut CopyRow) -> Result<(), sqlx::Error> {
+        #[cfg(feature = "postgres")]
+        return row.push(self);
+        #[cfg(not(feature = "postgres"))]
+        return row.push(&sqlx::types::Json(self.clone()));
+    }
+
+    fn bind_column(&self, args: &mut Arguments<'static>) -> Result<(), sqlx::Error> {
+        #[cfg(feature = "postgres")]
+        let value = self.clone();
+        #[cfg(not(feature = "postgres"))]
+        let value = sqlx::types::Json(self.clone());
+        sqlx::Arguments::add(args, value).map_err(sqlx::Error::Decode)
+    }
+
+    fn scan_column<I: sqlx::ColumnIndex<Row>>(row: &Row, index: I) -> Result<Self, sqlx::Error> {
+        #[cfg(feature = "postgres")]
+        let values = row.try_get::<Vec<T>, _>(index)?;
+        #[cfg(not(feature = "postgres"))]
+        let values = row.try_get::<sqlx::types::Json<Vec<T>>, _>(index)?.0;
+        Ok(values)
+    }
+}
+
+impl<T: ArrayElement> ArrayColumn for Option<Vec<T>> {
+    fn copy_column(&self, row: &mut CopyRow) -> Result<(), sqlx::Error> {
+        #[cfg(feature = "postgres")]
+        return row.push(self);
+        #[cfg(not(feature = "postgres"))]
+        return row.push(&self.clone().map(sqlx::types::Json));
+    }
+
+    fn bind_column(&self, args: &mut Arguments<'static>) -> Result<(), sqlx::Error> {
+        #[cfg(feature = "postgres")]
+        let value = self.clone();
+        #[cfg(not(feature = "postgres"))]
+        let value = self.clone().map(sqlx::types::Json);
+        sqlx::Arguments::add(args, value).map_err(sqlx::Error::Decode)
+    }
+
+    fn scan_column<I: sqlx::ColumnIndex<Row>>(row: &Row, index: I) -> Result<Self, sqlx::Error> {
+        #[cfg(feature = "postgres")]
+        let values = row.try_get::<Option<Vec<T>>, _>(index)?;
+        #[cfg(not(feature = "postgres"))]
+        let values = row
+            .try_get::<Option<sqlx::types::Json<Vec<T>>>, _>(index)?
+            .map(|json| json.0);
+        Ok(values)
+    }
+}
+
+#[doc(hidden)]
+pub fn bind_array<A: ArrayColumn>(
+    value: &A,
+    args: &mut Arguments<'static>,
+) -> Result<(), sqlx::Error> {
+    value.bind_column(args)
+}
+
+#[doc(hidden)]
+pub fn copy_array<A: ArrayColumn>(value: &A, row: &mut CopyRow) -> Result<(), sqlx::Error> {
+    value.copy_column(row)
+}
+
+#[doc(hidden)]
+pub fn scan_array<A, I>(row: &Row, index: I) -> Result<A, sqlx::Error>
+where
+    A: ArrayColumn,
+    I: sqlx::ColumnIndex<Row>,
+{
+    A::scan_column(row, index)
+}
@@ -0,0 +1,123 @@
+//! In-process memo for tagged SELECT results.
+//!
+//! Each [`DbPool`](crate::db::DbPool) owns one [`QueryCache`], shared by its
+//! clones and transactions. Entries are grouped by tag. Writes through the
+//! query builders bump the tag named after their table, which drops every
+//! result stored under it. The cache holds at most `capacity` results and
+//! evicts the oldest first.
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::db::commons::Arguments;
+
+type CachedValue = Arc<dyn Any + Send + Sync>;
+
+#[derive(Default)]
+struct TagEntries {
+    version: u64,
+    entries: HashMap<String, CachedValue>,
+}
+
+struct CacheState {
+    tags: HashMap<String, TagEntries>,
+    /// `(tag, key, version)` in insertion order, for eviction.
+    order: VecDeque<(String, String, u64)>,
+    capacity: usize,
+}
+
+/// Results memoized by `SelectQuery::all_cached` for one pool.
+#[derive(Clone)]
+pub struct QueryCache {
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl std::fmt::Debug for QueryCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock();
+        f.debug_struct("QueryCache")
+            .field("entries", &state.order.len())
+            .field("capacity", &state.capacity)
+            .finish()
+    }
+}
+
+impl QueryCache {
+    /// Empty cache holding at most `capacity` results; 0 disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CacheState {
+                tags: HashMap::new(),
+                order: VecDeque::new(),
+                capacity,
+            })),
+        }
+    }
+
+    /// Drops every cached result stored under `tag`.
+    pub fn invalidate(&self, tag: &str) {
+        let mut state = self.state.lock();
+        let entry = state.tags.entry(tag.to_string()).or_default();
+        entry.version = entry.version.wrapping_add(1);
+        entry.entries.clear();
+    }
+
+    /// Returns the cached value, or the tag version to pass to [`store`] on a miss.
+    ///
+    /// [`store`]: QueryCache::store
+    pub(crate) fn lookup<T>(&self, tag: &str, key: &str) -> Result<T, u64>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let state = self.state.lock();
+        let Some(entry) = state.tags.get(tag) else {
+            return Err(0);
+        };
+        entry
+            .entries
+            .get(key)
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+            .ok_or(entry.version)
+    }
+
+    /// Stores `value` unless the tag was invalidated since `version` was read,
+    /// evicting the oldest results beyond the capacity.
+    pub(crate) fn store<T>(&self, tag: &str, key: String, version: u64, value: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        let mut state = self.state.lock();
+        if state.capacity == 0 {
+            return;
+        }
+        let entry = state.tags.entry(tag.to_string()).or_default();
+        if entry.version != version {
+            return;
+        }
+        entry.entries.insert(key.clone(), Arc::new(value));
+        state.order.push_back((tag.to_string(), key, version));
+        while state.order.len() > state.capacity {
+            let Some((tag, key, version)) = state.order.pop_front() else {
+                break;
+            };
+            if let Some(entry) = state.tags.get_mut(&tag)
+                && entry.version == version
+            {
+                entry.entries.remove(&key);
+            }
+        }
+    }
+}
+
+/// Tag used for writes to `source`: the table name without any alias.
+pub(crate) fn table_tag(source: &str) -> &str {
+    source.split_whitespace().next().unwrap_or(source)
+}
+
+pub(crate) fn cache_key<T: 'static>(sql: &str, args: &Arguments<'static>) -> String {
+    format!("{:?}|{}|{:?}", TypeId::of::<T>(), sql, args)
+}
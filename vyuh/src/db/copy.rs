@@ -0,0 +1,131 @@
+//! Binary-format `COPY ... FROM STDIN` encoding for [`DbPool::copy_in`].
+//!
+//! [`DbPool::copy_in`]: crate::db::DbPool::copy_in
+
+use crate::db::commons::Database;
+#[cfg(feature = "postgres")]
+use crate::db::{executor::DbError, interfaces::Bindable, queries::validate_ident};
+
+/// Encoded rows are sent once the buffer reaches this size.
+#[cfg(feature = "postgres")]
+const SEND_BYTES: usize = 64 * 1024;
+
+/// Signature, flags and header-extension length that open a binary COPY.
+#[cfg(feature = "postgres")]
+const HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+
+/// One row's column values in COPY binary format, filled by
+/// `Bindable::copy_values` with the same encoders the insert path binds.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct CopyRow {
+    #[cfg(feature = "postgres")]
+    data: Vec<u8>,
+    fields: usize,
+}
+
+impl CopyRow {
+    /// Appends `value` as the next column. On backends without `COPY` the
+    /// value is ignored, since `copy_in` falls back to inserts there.
+    pub fn push<T>(&mut self, value: &T) -> Result<(), sqlx::Error>
+    where
+        T: for<'q> sqlx::Encode<'q, Database> + sqlx::Type<Database>,
+    {
+        #[cfg(feature = "postgres")]
+        {
+            let mut buf = sqlx::postgres::PgArgumentBuffer::default();
+            match value.encode_by_ref(&mut buf).map_err(sqlx::Error::Encode)? {
+                sqlx::encode::IsNull::Yes => self.data.extend_from_slice(&(-1i32).to_be_bytes()),
+                sqlx::encode::IsNull::No => {
+                    let len = i32::try_from(buf.len())
+                        .map_err(|_| sqlx::Error::Encode("copy_in value over 2 GiB".into()))?;
+                    self.data.extend_from_slice(&len.to_be_bytes());
+                    self.data.extend_from_slice(&buf);
+                }
+            }
+        }
+        #[cfg(not(feature = "postgres"))]
+        let _ = value;
+        self.fields += 1;
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    fn clear(&mut self) {
+        self.data.clear();
+        self.fields = 0;
+    }
+}
+
+/// Streams `rows` into `table` with one `COPY`, returning the rows copied.
+#[cfg(feature = "postgres")]
+pub(crate) async fn copy_rows<M: Bindable>(
+    conn: &mut sqlx::PgConnection,
+    table: &str,
+    rows: &[M],
+) -> Result<u64, DbError> {
+    let columns = M::bind_column_names();
+    validate_ident(table)?;
+    for column in &columns {
+        validate_ident(column)?;
+    }
+    let field_count = i16::try_from(columns.len())
+        .map_err(|_| DbError::Unsupported("copy_in of more than 32767 columns"))?;
+    let sql = format!(
+        "COPY {table} ({}) FROM STDIN (FORMAT binary)",
+        columns.join(", ")
+    );
+
+    let mut copy = conn.copy_in_raw(&sql).await?;
+    let mut row_buf = CopyRow::default();
+    let mut buf = HEADER.to_vec();
+    for row in rows {
+        row_buf.clear();
+        let encoded = row.copy_values(&mut row_buf).map_err(DbError::from);
+        let err = match encoded {
+            Err(err) => Some(err),
+            Ok(()) if row_buf.fields != columns.len() => Some(DbError::Unsupported(
+                "copy_in of a Bindable without derived copy_values; use insert_chunked",
+            )),
+            Ok(()) => None,
+        };
+        if let Some(err) = err {
+            copy.abort(err.to_string()).await?;
+            return Err(err);
+        }
+        buf.extend_from_slice(&field_count.to_be_bytes());
+        buf.extend_from_slice(&row_buf.data);
+        if buf.len() >= SEND_BYTES {
+            copy.send(buf.as_slice()).await?;
+            buf.clear();
+        }
+    }
+    buf.extend_from_slice(&(-1i16).to_be_bytes());
+    copy.send(buf.as_slice()).await?;
+    Ok(copy.finish().await?)
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_length_prefixed_and_null_is_minus_one() {
+        let mut row = CopyRow::default();
+        row.push(&7i64).unwrap();
+        row.push(&None::<String>).unwrap();
+        row.push(&"ab".to_string()).unwrap();
+        assert_eq!(row.fields, 3);
+        assert_eq!(
+            row.data,
+            [
+                &[0, 0, 0, 8][..],
+                &7i64.to_be_bytes(),
+                &[0xff, 0xff, 0xff, 0xff],
+                &[0, 0, 0, 2],
+                b"ab",
+            ]
+            .concat()
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::borrow::Cow;
+
+/// Postgres column type of a Rust field, for generated DDL.
+///
+/// Types without a mapping fail to compile instead of defaulting to `text`.
+/// Implement it for a custom type to pick its column type; `Option<T>` maps
+/// to `T` and marks the column nullable, and `Vec<T>` maps to `T[]`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no Postgres column type",
+    label = "implement `vyuh::db::PgType` for this type to choose one",
+    note = "`#[column(json)]` stores any serializable type as `jsonb`"
+)]
+pub trait PgType {
+    fn pg_type() -> Cow<'static, str>;
+
+    /// Whether a `NULL` fits the Rust type.
+    fn nullable() -> bool {
+        false
+    }
+}
+
+/// The Postgres column type for `T`, e.g. `rust_to_pg_type::<Vec<i32>>()` is
+/// `integer[]`.
+pub fn rust_to_pg_type<T: PgType + ?Sized>() -> Cow<'static, str> {
+    T::pg_type()
+}
+
+macro_rules! pg_types {
+    ($($ty:ty => $name:literal),* $(,)?) => {
+        $(
+            impl PgType for $ty {
+                fn pg_type() -> Cow<'static, str> {
+                    Cow::Borrowed($name)
+                }
+            }
+        )*
+    };
+}
+
+pg_types! {
+    bool => "boolean",
+    i8 => "smallint",
+    i16 => "smallint",
+    i32 => "integer",
+    i64 => "bigint",
+    f32 => "real",
+    f64 => "double precision",
+    rust_decimal::Decimal => "numeric",
+    String => "text",
+    str => "text",
+    Vec<u8> => "bytea",
+    [u8] => "bytea",
+    uuid::Uuid => "uuid",
+    serde_json::Value => "jsonb",
+    chrono::NaiveDate => "date",
+    chrono::NaiveTime => "time",
+    chrono::NaiveDateTime => "timestamp",
+    chrono::DateTime<chrono::Utc> => "timestamptz",
+    chrono::DateTime<chrono::FixedOffset> => "timestamptz",
+    chrono::DateTime<chrono::Local> => "timestamptz",
+}
+
+impl<T: PgType> PgType for Option<T> {
+    fn pg_type() -> Cow<'static, str> {
+        T::pg_type()
+    }
+
+    fn nullable() -> bool {
+        true
+    }
+}
+
+impl<T: PgType> PgType for Vec<T> {
+    fn pg_type() -> Cow<'static, str> {
+        Cow::Owned(format!("{}[]", T::pg_type()))
+    }
+}
+
+impl<T: PgType + ?Sized> PgType for Box<T> {
+    fn pg_type() -> Cow<'static, str> {
+        T::pg_type()
+    }
+
+    fn nullable() -> bool {
+        T::nullable()
+    }
+}
+
+impl<T> PgType for sqlx::types::Json<T> {
+    fn pg_type() -> Cow<'static, str> {
+        Cow::Borrowed("jsonb")
+    }
+}
@@ -0,0 +1,78 @@
+use sqlx::Row as _;
+
+use crate::db::commons::{Arguments, Row};
+use crate::db::copy::CopyRow;
+
+/// A fieldless enum stored in a column as text or as an integer.
+///
+/// Derive it with `#[derive(ColumnEnum)]`: text is the snake_case variant
+/// name and the integer is the discriminant. Pick the representation per
+/// field with `#[column(store_as = "text")]` or `#[column(store_as = "int")]`.
+pub trait ColumnEnum: Sized {
+    fn to_text(&self) -> &'static str;
+
+    fn from_text(text: &str) -> Option<Self>;
+
+    fn to_int(&self) -> i64;
+
+    fn from_int(value: i64) -> Option<Self>;
+}
+
+#[doc(hidden)]
+pub fn bind_enum_text<E: ColumnEnum>(
+    value: &E,
+    args: &mut Arguments<'static>,
+) -> Result<(), sqlx::Error> {
+    sqlx::Arguments::add(args, value.to_text().to_string()).map_err(sqlx::Error::Decode)
+}
+
+#[doc(hidden)]
+pub fn bind_enum_int<E: ColumnEnum>(
+    value: &E,
+    args: &mut Arguments<'static>,
+) -> Result<(), sqlx::Error> {
+    sqlx::Arguments::add(args, value.to_int()).map_err(sqlx::Error::Decode)
+}
+
+#[doc(hidden)]
+pub fn copy_enum_text<E: ColumnEnum>(value: &E, row: &mut CopyRow) -> Result<(), sqlx::Error> {
+    row.push(&value.to_text().to_string())
+}
+
+#[doc(hidden)]
+pub fn copy_enum_int<E: ColumnEnum>(value: &E, row: &mut CopyRow) -> Result<(), sqlx::Error> {
+    row.push(&value.to_int())
+}
+
+#[doc(hidden)]
+pub fn scan_enum_text<E, I>(row: &Row, index: I) -> Result<E, sqlx::Error>
+where
+    E: ColumnEnum,
+    I: sqlx::ColumnIndex<Row>,
+{
+    let text: String = row.try_get(index)?;
+    E::from_text(&text).ok_or_else(|| unknown_value(text))
+}
+
+/// Reads the integer at its stored width: Postgres will not decode a
+/// `SMALLINT` or `INTEGER` column as `i64`.
+#[doc(hidden)]
+pub fn scan_enum_int<E, I>(row: &Row, index: I) -> Result<E, sqlx::Error>
+where
+    E: ColumnEnum,
+    I: sqlx::ColumnIndex<Row> + Copy,
+{
+    let value = match row.try_get::<i64, _>(index) {
+        Ok(value) => value,
+        Err(err @ sqlx::Error::ColumnDecode { .. }) => match row.try_get::<i32, _>(index) {
+            Ok(value) => i64::from(value),
+            Err(_) => i64::from(row.try_get::<i16, _>(index).map_err(|_| err)?),
+        },
+        Err(err) => return Err(err),
+    };
+    E::from_int(value).ok_or_else(|| unknown_value(value))
+}
+
+fn unknown_value(value: impl std::fmt::Display) -> sqlx::Error {
+    sqlx::Error::Decode(format!("no enum variant for stored value `{value}`").into())
+}
@@ -1,6 +1,7 @@
 use std::hash::Hash;
 
-use crate::db::{Arguments, Row};
+use crate::auth::AuthUser;
+use crate::db::{ArgValue, Arguments, CopyRow, FilteredBuilder, QueryError, Row};
 
 pub trait Scannable: Sized {
     fn scan_column_names() -> Vec<String>;
@@ -9,6 +10,11 @@ pub trait Scannable: Sized {
 
     fn scan_row_unordered(row: &Row) -> Result<Self, sqlx::Error>;
 
+    /// Timestamp column set on soft-deleted rows, which selects then skip.
+    fn soft_delete_column() -> Option<&'static str> {
+        None
+    }
+
     fn scan_row(row: &Row) -> Result<Self, sqlx::Error> {
         let mut idx = 0;
         Self::scan_row_ordered(row, &mut idx)
@@ -19,6 +25,32 @@ pub trait Bindable {
     fn bind_values(&self, args: &mut Arguments<'static>) -> Result<(), sqlx::Error>;
 
     fn bind_column_names() -> Vec<String>;
+
+    /// Columns inserts set to `CURRENT_TIMESTAMP`, from `#[column(auto_now_add)]`.
+    fn auto_now_add_columns() -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Columns inserts and updates set to `CURRENT_TIMESTAMP`, from
+    /// `#[column(auto_now)]`.
+    fn auto_now_columns() -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The soft-delete column, from `#[schema(soft_delete = "...")]`, whose
+    /// set rows updates skip. Same value as `Scannable::soft_delete_column`.
+    fn soft_delete_guard() -> Option<&'static str> {
+        None
+    }
+
+    /// Encodes each bound column, in `bind_column_names` order, for
+    /// [`DbPool::copy_in`](crate::db::DbPool::copy_in). The derive fills it;
+    /// without it `copy_in` fails with `DbError::Unsupported`.
+    #[doc(hidden)]
+    fn copy_values(&self, row: &mut CopyRow) -> Result<(), sqlx::Error> {
+        let _ = row;
+        Ok(())
+    }
 }
 
 pub trait Model: Scannable + Bindable {
@@ -28,3 +60,28 @@ pub trait Model: Scannable + Bindable {
 
     fn primary_key_column() -> &'static str;
 }
+
+/// Row ownership used by [`FilteredBuilder::with_role_filter`](crate::db::FilteredBuilder::with_role_filter).
+pub trait Owned {
+    /// Column compared against the user's key.
+    fn owner_column() -> &'static str {
+        "owner_id"
+    }
+
+    /// Role bits that see every row instead of only their own.
+    fn bypass_roles() -> u64 {
+        0
+    }
+
+    /// Value compared against `owner_column` for `user`. Defaults to the key
+    /// as text; override it when the column is an integer or UUID.
+    fn owner_key(user: &AuthUser) -> Result<ArgValue, QueryError> {
+        Ok(ArgValue::new(user.key.to_string()))
+    }
+}
+
+/// Filter struct whose fields map to bound query conditions, usually derived.
+pub trait Filterable {
+    /// Appends one condition per filter field to `builder`.
+    fn apply_filters<B: FilteredBuilder>(&self, builder: B) -> B;
+}
@@ -1,5 +1,10 @@
 mod argvalue;
+mod arrays;
+mod cache;
 mod commons;
+mod copy;
+mod ddl;
+mod enums;
 mod executor;
 mod interfaces;
 mod placeholders;
@@ -9,14 +14,28 @@ mod scopes;
 pub mod mock;
 
 pub use argvalue::ArgValue;
+pub use arrays::{ArrayColumn, ArrayElement};
+#[doc(hidden)]
+pub use arrays::{bind_array, copy_array, scan_array};
+pub use cache::QueryCache;
 pub use commons::{Arguments, Database, Pool, QueryResult, Row};
+#[doc(hidden)]
+pub use copy::CopyRow;
+pub use ddl::{PgType, rust_to_pg_type};
+pub use enums::ColumnEnum;
+#[doc(hidden)]
+pub use enums::{
+    bind_enum_int, bind_enum_text, copy_enum_int, copy_enum_text, scan_enum_int, scan_enum_text,
+};
 pub use executor::*;
-pub use interfaces::{Bindable, Model, Scannable};
+pub use interfaces::{Bindable, Filterable, Model, Owned, Scannable};
+pub use queries::{
+    Col, Cond, FilterGroup, FilterOp, FilteredBuilder, LockMode, Page, QueryError, Statement, col,
+};
 pub use queries::{DeleteQuery, InsertQuery, SelectQuery, UpdateQuery};
-pub use queries::{FilteredBuilder, LockMode, Page, QueryError, Statement};
 pub use scopes::Scope;
 pub use sqlx::test as test_db;
-pub use vyuh_macros::{Bindable, Scannable};
+pub use vyuh_macros::{Bindable, ColumnEnum, Filterable, Scannable};
 
 /// Start a SELECT query against `table`.
 pub fn select(table: &str) -> SelectQuery {
@@ -37,3 +56,48 @@ pub fn update(table: &str) -> UpdateQuery {
 pub fn delete(table: &str) -> DeleteQuery {
     DeleteQuery::new(table)
 }
+
+/// Estimated row count of `table`, for dashboards where exactness is not needed.
+///
+/// The result is **approximate**: Postgres reads the planner statistic
+/// `pg_class.reltuples` (refreshed by `ANALYZE`/autovacuum) and MySQL reads
+/// `information_schema.TABLES.TABLE_ROWS`. Both return instantly regardless of
+/// table size but may lag recent writes. Postgres tables that were never
+/// analyzed, and SQLite, which keeps no such statistic, fall back to an exact
+/// `COUNT(*)`.
+pub async fn approx_count<S: DBSession>(session: &mut S, table: &str) -> Result<i64, DbError> {
+    queries::validate_ident(table)?;
+    let estimate = estimate_rows(session, table).await?;
+    match estimate {
+        Some(rows) if rows >= 0 => Ok(rows),
+        _ => select(table).with_deleted().count(session).await,
+    }
+}
+
+#[cfg(feature = "postgres")]
+async fn estimate_rows<S: DBSession>(session: &mut S, table: &str) -> Result<Option<i64>, DbError> {
+    // reltuples is -1 until the table is first analyzed (Postgres 14+).
+    let stmt = Statement::from_str(
+        "SELECT c.reltuples::BIGINT FROM pg_class c WHERE c.oid = to_regclass($1)",
+    )
+    .bind(table.to_string());
+    session.fetch_scalar::<i64>(stmt).await.map(Some)
+}
+
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+async fn estimate_rows<S: DBSession>(session: &mut S, table: &str) -> Result<Option<i64>, DbError> {
+    let stmt = Statement::from_str(
+        "SELECT CAST(TABLE_ROWS AS SIGNED) FROM information_schema.TABLES \
+         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?",
+    )
+    .bind(table.to_string());
+    session.fetch_scalar::<Option<i64>>(stmt).await
+}
+
+#[cfg(not(any(feature = "postgres", feature = "mysql")))]
+async fn estimate_rows<S: DBSession>(
+    _session: &mut S,
+    _table: &str,
+) -> Result<Option<i64>, DbError> {
+    Ok(None)
+}
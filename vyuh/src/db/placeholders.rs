@@ -475,6 +475,14 @@ impl Dialect {
         #[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
         return Self::Sqlite;
     }
+
+    /// Most bind parameters a single statement may carry.
+    pub(crate) const fn max_bind_params(self) -> usize {
+        match self {
+            Self::Postgres | Self::Mysql => 65535,
+            Self::Sqlite => 32766,
+        }
+    }
 }
 
 /// Errors that can occur during placeholder resolution
@@ -520,11 +528,13 @@ impl std::error::Error for PlaceholderError {
 /// Binds values from the map to the arguments and returns the transformed SQL.
 /// Returns an error if a placeholder is not found in the map.
 /// For PostgreSQL, reuses positions for placeholders that appear multiple times.
+/// `on_bind` runs after each value is bound.
 pub fn resolve_placeholders(
     sql: &str,
     arguments: &mut super::commons::Arguments<'static>,
     values: &std::collections::HashMap<String, super::argvalue::ArgValue>,
     dialect: Dialect,
+    mut on_bind: impl FnMut(&super::commons::Arguments<'static>, &super::argvalue::ArgValue),
 ) -> Result<String, PlaceholderError> {
     use sqlx::Arguments as _;
 
@@ -554,6 +564,7 @@ pub fn resolve_placeholders(
                                         as Arc<dyn std::error::Error + Send + Sync>,
                                 }
                             })?;
+                            on_bind(arguments, value);
                             let p = position;
                             bound_positions.insert(name, p);
                             position += 1;
@@ -574,6 +585,7 @@ pub fn resolve_placeholders(
                                 placeholder: name.to_string(),
                                 source: Arc::from(e) as Arc<dyn std::error::Error + Send + Sync>,
                             })?;
+                        on_bind(arguments, value);
                         position += 1;
                         output.push('?');
                     }
@@ -1246,7 +1258,7 @@ mod tests {
             super::super::argvalue::ArgValue::new("Alice".to_string()),
         );
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -1269,7 +1281,7 @@ mod tests {
             super::super::argvalue::ArgValue::new("Alice".to_string()),
         );
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Mysql);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Mysql, |_, _| {});
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -1288,7 +1300,7 @@ mod tests {
             super::super::argvalue::ArgValue::new(42i32),
         );
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Sqlite);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Sqlite, |_, _| {});
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "SELECT * FROM users WHERE id = ?");
     }
@@ -1300,7 +1312,7 @@ mod tests {
         let mut args = super::super::commons::Arguments::default();
         let values = HashMap::new(); // empty map
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_err());
         match result.unwrap_err() {
             PlaceholderError::MissingValue(name) => {
@@ -1321,7 +1333,7 @@ mod tests {
             super::super::argvalue::ArgValue::new(42i32),
         );
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "SELECT ':fake' FROM users WHERE id = $1");
     }
@@ -1337,7 +1349,7 @@ mod tests {
             super::super::argvalue::ArgValue::new(42i32),
         );
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_ok());
         assert_eq!(
             result.unwrap(),
@@ -1355,7 +1367,7 @@ mod tests {
         values.insert("b".to_string(), super::super::argvalue::ArgValue::new(2i32));
         values.insert("c".to_string(), super::super::argvalue::ArgValue::new(3i32));
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "$1$2$3");
     }
@@ -1367,7 +1379,7 @@ mod tests {
         let mut args = super::super::commons::Arguments::default();
         let values = HashMap::new();
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "");
     }
@@ -1394,7 +1406,7 @@ mod tests {
 
         assert_eq!(args.len(), 0);
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_ok());
         assert_eq!(args.len(), 3);
         assert_eq!(
@@ -1425,7 +1437,7 @@ mod tests {
             super::super::argvalue::ArgValue::new(5i32),
         );
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_ok());
         assert_eq!(args.len(), 4);
         assert_eq!(result.unwrap(), "WHERE status = $3 AND type = $4");
@@ -1443,7 +1455,7 @@ mod tests {
         );
         // name and email are missing
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_err());
         match result.unwrap_err() {
             PlaceholderError::MissingValue(name) => {
@@ -1466,7 +1478,7 @@ mod tests {
         );
 
         let initial_len = args.len();
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_err());
         // Arguments should have been modified up to the point of error
         assert!(args.len() > initial_len);
@@ -1489,7 +1501,7 @@ mod tests {
             super::super::argvalue::ArgValue::new("active".to_string()),
         );
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         assert!(result.is_ok());
         // :id should reuse $1, :status should be $2
         assert_eq!(
@@ -1512,7 +1524,7 @@ mod tests {
             super::super::argvalue::ArgValue::new(42i32),
         );
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Mysql);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Mysql, |_, _| {});
         assert!(result.is_ok());
         // MySQL uses ? for all, but only binds once
         assert_eq!(
@@ -1592,7 +1604,7 @@ mod tests {
             super::super::argvalue::ArgValue::new(42i32),
         );
 
-        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres);
+        let result = resolve_placeholders(sql, &mut args, &values, Dialect::Postgres, |_, _| {});
         // Currently works but not guaranteed
         assert!(result.is_ok());
     }
@@ -0,0 +1,173 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::{FilterOp, MATCH_NONE, QueryError};
+use crate::db::argvalue::ArgValue;
+use crate::db::commons::Database;
+
+/// Source of unique placeholder names, so two conditions on the same column
+/// never overwrite each other's binds.
+static NEXT_PARAM: AtomicU64 = AtomicU64::new(0);
+
+/// Starts a condition on `name`: `col("age").gte(18)`.
+///
+/// Values are always bound, never written into the SQL, and `name` may only
+/// contain ASCII letters, digits, underscores and dots; anything else fails
+/// the query with [`QueryError::InvalidColumn`].
+pub fn col(name: &str) -> Col {
+    Col {
+        name: name.to_string(),
+    }
+}
+
+/// A column awaiting a comparison; see [`col`].
+#[derive(Clone, Debug)]
+pub struct Col {
+    name: String,
+}
+
+/// A predicate with its bound values, added with
+/// [`FilteredBuilder::filter_cond`](super::FilteredBuilder::filter_cond).
+#[derive(Clone)]
+pub struct Cond {
+    pub(crate) sql: String,
+    pub(crate) binds: Vec<(String, ArgValue)>,
+    pub(crate) error: Option<QueryError>,
+}
+
+macro_rules! comparisons {
+    ($($(#[$doc:meta])* $method:ident => $op:ident),* $(,)?) => {
+        $(
+            $(#[$doc])*
+            pub fn $method<T>(self, value: T) -> Cond
+            where
+                T: Clone + for<'q> sqlx::Encode<'q, Database> + sqlx::Type<Database> + Send + Sync + 'static,
+            {
+                self.compare(FilterOp::$op, ArgValue::new(value))
+            }
+        )*
+    };
+}
+
+impl Col {
+    comparisons! {
+        /// `column = value`
+        eq => Eq,
+        /// `column <> value`
+        ne => Ne,
+        /// `column < value`
+        lt => Lt,
+        /// `column <= value`
+        lte => Lte,
+        /// `column > value`
+        gt => Gt,
+        /// `column >= value`
+        gte => Gte,
+    }
+
+    /// `column LIKE pattern`; `%` and `_` in `pattern` are wildcards.
+    pub fn like(self, pattern: impl Into<String>) -> Cond {
+        let param = next_param();
+        let sql = format!("{} LIKE :{param}", self.name);
+        self.cond(sql, vec![(param, ArgValue::new(pattern.into()))])
+    }
+
+    /// Case-insensitive [`Col::like`], portable across backends.
+    pub fn ilike(self, pattern: impl Into<String>) -> Cond {
+        let param = next_param();
+        let sql = format!("LOWER({}) LIKE LOWER(:{param})", self.name);
+        self.cond(sql, vec![(param, ArgValue::new(pattern.into()))])
+    }
+
+    /// `column IN (...)`; an empty `values` matches no rows.
+    pub fn in_<T>(self, values: impl IntoIterator<Item = T>) -> Cond
+    where
+        T: Clone
+            + for<'q> sqlx::Encode<'q, Database>
+            + sqlx::Type<Database>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let binds: Vec<(String, ArgValue)> = values
+            .into_iter()
+            .map(|value| (next_param(), ArgValue::new(value)))
+            .collect();
+        if binds.is_empty() {
+            return self.cond(MATCH_NONE.to_string(), binds);
+        }
+        let names: Vec<String> = binds.iter().map(|(name, _)| format!(":{name}")).collect();
+        let sql = format!("{} IN ({})", self.name, names.join(", "));
+        self.cond(sql, binds)
+    }
+
+    /// `column IS NULL`
+    pub fn is_null(self) -> Cond {
+        let sql = format!("{} IS NULL", self.name);
+        self.cond(sql, Vec::new())
+    }
+
+    /// `column IS NOT NULL`
+    pub fn is_not_null(self) -> Cond {
+        let sql = format!("{} IS NOT NULL", self.name);
+        self.cond(sql, Vec::new())
+    }
+
+    fn compare(self, op: FilterOp, value: ArgValue) -> Cond {
+        let param = next_param();
+        let sql = op.condition(&self.name, &param);
+        self.cond(sql, vec![(param, value)])
+    }
+
+    fn cond(self, sql: String, binds: Vec<(String, ArgValue)>) -> Cond {
+        let error = validate_column(&self.name).err();
+        Cond { sql, binds, error }
+    }
+}
+
+impl Cond {
+    /// `(self AND other)`
+    pub fn and(self, other: Cond) -> Cond {
+        self.join(other, "AND")
+    }
+
+    /// `(self OR other)`
+    pub fn or(self, other: Cond) -> Cond {
+        self.join(other, "OR")
+    }
+
+    fn join(mut self, other: Cond, op: &str) -> Cond {
+        self.sql = format!("({} {op} {})", self.sql, other.sql);
+        self.binds.extend(other.binds);
+        self.error = self.error.or(other.error);
+        self
+    }
+}
+
+/// `!cond` is `NOT (cond)`.
+impl std::ops::Not for Cond {
+    type Output = Cond;
+
+    fn not(self) -> Cond {
+        Cond {
+            sql: format!("NOT ({})", self.sql),
+            ..self
+        }
+    }
+}
+
+fn next_param() -> String {
+    format!("vyuh_c{}", NEXT_PARAM.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Stricter than `validate_ident`: no spaces, so a name cannot carry SQL.
+fn validate_column(name: &str) -> Result<(), QueryError> {
+    let valid = !name.is_empty()
+        && name.split('.').all(|part| {
+            !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(QueryError::InvalidColumn(name.to_string()))
+    }
+}
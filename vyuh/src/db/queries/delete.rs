@@ -1,13 +1,13 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-use super::{FilteredBuilder, QueryError, Statement};
-use crate::db::argvalue::ArgValue;
+use super::{DebugArgs, FilteredBuilder, QueryError, Statement};
+use crate::db::argvalue::{ArgValue, debug_literal};
+use crate::db::cache::table_tag;
 use crate::db::commons::Arguments;
 #[cfg(feature = "postgres")]
 use crate::db::commons::Row;
 use crate::db::executor::{DBSession, DbError};
-#[cfg(feature = "postgres")]
 use crate::db::interfaces::Scannable;
 use crate::db::placeholders::{Dialect, has_named_placeholder, resolve_placeholders};
 
@@ -15,8 +15,12 @@ use crate::db::placeholders::{Dialect, has_named_placeholder, resolve_placeholde
 pub struct DeleteQuery {
     source: String,
     filters: Vec<Cow<'static, str>>,
+    guards: Vec<Cow<'static, str>>,
+    soft_column: Option<&'static str>,
+    model_set: bool,
     args: Arguments<'static>,
     named_args: HashMap<String, ArgValue>,
+    debug_args: DebugArgs,
     error: Option<QueryError>,
 }
 
@@ -25,8 +29,12 @@ impl DeleteQuery {
         Self {
             source: source.to_string(),
             filters: Vec::new(),
+            guards: Vec::new(),
+            soft_column: None,
+            model_set: false,
             args: Arguments::default(),
             named_args: HashMap::new(),
+            debug_args: DebugArgs::default(),
             error: super::validate_ident(source).err(),
         }
     }
@@ -43,8 +51,10 @@ impl DeleteQuery {
         if self.error.is_some() {
             return self;
         }
-        if let Err(e) = self.args.add(val) {
-            self.error = Some(QueryError::BindError(e.to_string()));
+        let literal = debug_literal(&val);
+        match self.args.add(val) {
+            Ok(()) => self.debug_args.record(&self.args, literal),
+            Err(e) => self.error = Some(QueryError::BindError(e.to_string())),
         }
         self
     }
@@ -66,65 +76,120 @@ impl DeleteQuery {
         self
     }
 
+    /// Soft-deletes when `M` has a soft-delete column: sets it to the current
+    /// time on rows where it is still `NULL` instead of issuing `DELETE`.
+    ///
+    /// `execute` refuses to run until this or `with_deleted` is called.
+    pub fn for_model<M: Scannable>(mut self) -> Self {
+        if !self.model_set {
+            self.model_set = true;
+            self.soft_column = M::soft_delete_column();
+        }
+        self
+    }
+
+    /// Issues a plain `DELETE`, removing soft-deleted and live rows alike.
+    /// Tables without a model need this, or `for_model`, before `execute`.
+    pub fn with_deleted(mut self) -> Self {
+        self.model_set = true;
+        self.soft_column = None;
+        self
+    }
+
     // ── internal ──────────────────────────────────────────────────────────────
 
     fn build_filter_clause(&self) -> String {
-        if self.filters.is_empty() {
-            return String::new();
-        }
-        format!(" WHERE {}", self.filters.join(" AND "))
+        super::where_clause(&self.filters, &self.guards)
     }
 
-    fn resolve(mut self, sql: String) -> Result<(String, Arguments<'static>), QueryError> {
+    fn resolve(mut self, sql: String) -> Result<Statement, QueryError> {
         if let Some(err) = self.error {
             return Err(err);
         }
         if self.named_args.is_empty() && !has_named_placeholder(&sql) {
-            return Ok((sql, self.args));
+            return Ok(Statement::new(&sql, self.args).with_debug_args(self.debug_args));
         }
-        let final_sql =
-            resolve_placeholders(&sql, &mut self.args, &self.named_args, Dialect::active())?;
-        Ok((final_sql, self.args))
+        let final_sql = resolve_placeholders(
+            &sql,
+            &mut self.args,
+            &self.named_args,
+            Dialect::active(),
+            |args, value| self.debug_args.record(args, value.literal()),
+        )?;
+        Ok(Statement::new(&final_sql, self.args).with_debug_args(self.debug_args))
     }
 
-    fn into_statement_with_suffix(self, suffix: &str) -> Result<Statement, QueryError> {
-        let sql = format!(
-            "DELETE FROM {}{}{}",
-            self.source,
-            self.build_filter_clause(),
-            suffix,
-        );
-        let (final_sql, final_args) = self.resolve(sql)?;
-        Ok(Statement::new(&final_sql, final_args))
+    fn into_statement_with_suffix(mut self, suffix: &str) -> Result<Statement, QueryError> {
+        let sql = match self.soft_column {
+            Some(column) => {
+                self.guards.push(format!("{column} IS NULL").into());
+                format!(
+                    "UPDATE {} SET {column} = CURRENT_TIMESTAMP{}{}",
+                    self.source,
+                    self.build_filter_clause(),
+                    suffix,
+                )
+            }
+            None => format!(
+                "DELETE FROM {}{}{}",
+                self.source,
+                self.build_filter_clause(),
+                suffix,
+            ),
+        };
+        self.resolve(sql)
     }
 
     // ── terminal methods ──────────────────────────────────────────────────────
 
+    /// Deletes matching rows. Call `for_model` or `with_deleted` first.
     pub async fn execute<S: DBSession>(self, session: &mut S) -> Result<u64, DbError> {
+        if !self.model_set {
+            return Err(QueryError::ModelNotSet("delete").into());
+        }
+        let tag = table_tag(&self.source).to_string();
         let stmt = self.into_statement_with_suffix("")?;
-        session.execute(stmt).await
+        let result = session.execute(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 
     /// Execute and return all deleted rows via RETURNING * (Postgres only).
+    ///
+    /// Soft-deletes when `M` has a soft-delete column, as with `for_model`,
+    /// unless `with_deleted` was called.
     #[cfg(feature = "postgres")]
     pub async fn all<M, S>(self, session: &mut S) -> Result<Vec<M>, DbError>
     where
         M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
         S: DBSession,
     {
-        let stmt = self.into_statement_with_suffix(" RETURNING *")?;
-        session.fetch_all(stmt).await
+        let tag = table_tag(&self.source).to_string();
+        let stmt = self
+            .for_model::<M>()
+            .into_statement_with_suffix(" RETURNING *")?;
+        let result = session.fetch_all(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 
     /// Execute and return the first deleted row via RETURNING * (Postgres only).
+    ///
+    /// Soft-deletes when `M` has a soft-delete column, as with `for_model`,
+    /// unless `with_deleted` was called.
     #[cfg(feature = "postgres")]
     pub async fn first<M, S>(self, session: &mut S) -> Result<Option<M>, DbError>
     where
         M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
         S: DBSession,
     {
-        let stmt = self.into_statement_with_suffix(" RETURNING *")?;
-        session.fetch_optional(stmt).await
+        let tag = table_tag(&self.source).to_string();
+        let stmt = self
+            .for_model::<M>()
+            .into_statement_with_suffix(" RETURNING *")?;
+        let result = session.fetch_optional(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 }
 
@@ -134,12 +199,28 @@ impl FilteredBuilder for DeleteQuery {
         self
     }
 
+    fn or_filter(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
+        super::or_last(&mut self.filters, cond.into());
+        self
+    }
+
+    fn guard(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
+        self.guards.push(cond.into());
+        self
+    }
+
+    fn fail(mut self, err: QueryError) -> Self {
+        self.error.get_or_insert(err);
+        self
+    }
+
     fn bind_dyn(mut self, val: ArgValue) -> Self {
         if self.error.is_some() {
             return self;
         }
-        if let Err(e) = val.bind_value(&mut self.args) {
-            self.error = Some(QueryError::BindError(e.to_string()));
+        match val.bind_value(&mut self.args) {
+            Ok(()) => self.debug_args.record(&self.args, val.literal()),
+            Err(e) => self.error = Some(QueryError::BindError(e.to_string())),
         }
         self
     }
@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+use super::{FilteredBuilder, QueryError};
+use crate::db::argvalue::ArgValue;
+use crate::db::commons::Database;
+
+/// Conditions collected by [`FilteredBuilder::or_group`] or
+/// [`FilteredBuilder::and_group`], added to the outer query as one
+/// parenthesized predicate. Binds are forwarded to the outer query in order.
+pub struct FilterGroup {
+    join: &'static str,
+    conds: Vec<Cow<'static, str>>,
+    guards: Vec<Cow<'static, str>>,
+    binds: Vec<Bind>,
+    error: Option<QueryError>,
+}
+
+enum Bind {
+    Positional(ArgValue),
+    Named(String, ArgValue),
+}
+
+impl FilterGroup {
+    pub(crate) fn new(join: &'static str) -> Self {
+        Self {
+            join,
+            conds: Vec::new(),
+            guards: Vec::new(),
+            binds: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Binds a positional `?` value used by a condition in this group.
+    pub fn bind<T>(self, val: T) -> Self
+    where
+        T: Clone
+            + for<'q> sqlx::Encode<'q, Database>
+            + sqlx::Type<Database>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.bind_dyn(ArgValue::new(val))
+    }
+
+    /// Binds a `:name` value used by a condition in this group.
+    pub fn bind_as<T>(self, name: &str, val: T) -> Self
+    where
+        T: Clone
+            + for<'q> sqlx::Encode<'q, Database>
+            + sqlx::Type<Database>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.bind_named_dyn(name, ArgValue::new(val))
+    }
+
+    /// Adds the group to `builder`. An empty group adds no condition, so a
+    /// group of `filter_optional` calls that are all `None` matches every row.
+    pub(crate) fn apply<B: FilteredBuilder>(self, builder: B) -> B {
+        if let Some(err) = self.error {
+            return builder.fail(err);
+        }
+        let builder = if self.conds.is_empty() {
+            builder
+        } else {
+            let conds: Vec<String> = self.conds.iter().map(|cond| format!("({cond})")).collect();
+            builder.filter(format!("({})", conds.join(self.join)))
+        };
+        // Guards narrow the whole query, never just one branch of the group.
+        let builder = self
+            .guards
+            .into_iter()
+            .fold(builder, |builder, guard| builder.guard(guard));
+        self.binds
+            .into_iter()
+            .fold(builder, |builder, bind| match bind {
+                Bind::Positional(value) => builder.bind_dyn(value),
+                Bind::Named(name, value) => builder.bind_named_dyn(&name, value),
+            })
+    }
+}
+
+impl FilteredBuilder for FilterGroup {
+    fn filter(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
+        self.conds.push(cond.into());
+        self
+    }
+
+    fn or_filter(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
+        super::or_last(&mut self.conds, cond.into());
+        self
+    }
+
+    fn bind_dyn(mut self, val: ArgValue) -> Self {
+        self.binds.push(Bind::Positional(val));
+        self
+    }
+
+    fn bind_named_dyn(mut self, name: &str, val: ArgValue) -> Self {
+        self.binds.push(Bind::Named(name.to_string(), val));
+        self
+    }
+
+    fn guard(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
+        self.guards.push(cond.into());
+        self
+    }
+
+    fn fail(mut self, err: QueryError) -> Self {
+        self.error.get_or_insert(err);
+        self
+    }
+}
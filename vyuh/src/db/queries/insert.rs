@@ -1,18 +1,21 @@
 use super::{QueryError, Statement};
+use crate::db::cache::table_tag;
 use crate::db::commons::Arguments;
 #[cfg(feature = "postgres")]
-use crate::db::commons::Row;
+use crate::db::commons::{Database, Row};
 use crate::db::executor::{DBSession, DbError};
 use crate::db::interfaces::Bindable;
-#[cfg(feature = "postgres")]
-use crate::db::interfaces::Scannable;
+use crate::db::placeholders::Dialect;
 
 /// Builder for INSERT queries. Constructed via `db::insert(table)`.
 pub struct InsertQuery {
     source: String,
     args: Arguments<'static>,
     sql: Option<String>,
+    #[cfg(feature = "postgres")]
+    returning: Vec<String>,
     error: Option<QueryError>,
+    no_rows: bool,
 }
 
 impl InsertQuery {
@@ -21,7 +24,10 @@ impl InsertQuery {
             source: source.to_string(),
             args: Arguments::default(),
             sql: None,
+            #[cfg(feature = "postgres")]
+            returning: Vec::new(),
             error: super::validate_ident(source).err(),
+            no_rows: false,
         }
     }
 
@@ -31,13 +37,16 @@ impl InsertQuery {
             return self;
         }
         let cols = M::bind_column_names();
-        if cols.is_empty() {
+        let stamped = stamped_columns::<M>();
+        if cols.is_empty() && stamped.is_empty() {
             self.error = Some(QueryError::BindError("no columns to insert".to_string()));
             return self;
         }
-        let mut sql = format!("INSERT INTO {} ({}) VALUES (", self.source, cols.join(", "));
+        let mut sql = insert_prefix(&self.source, &cols, &stamped);
+        sql.push('(');
         match self.bind_row_placeholders(item, cols.len(), &mut sql) {
             Ok(()) => {
+                push_timestamps(&mut sql, !cols.is_empty(), stamped.len());
                 sql.push(')');
                 self.sql = Some(sql);
             }
@@ -46,30 +55,34 @@ impl InsertQuery {
         self
     }
 
-    /// Bind multiple rows for bulk insertion.
+    /// Bind multiple rows for bulk insertion. An empty slice makes the query
+    /// a no-op: `execute` returns 0, `all` an empty list, `first` `None`, and
+    /// `one`/`scalar` `DbError::DoesNotExist`, without touching the database.
     pub fn rows<M: Bindable>(mut self, items: &[M]) -> Self {
         if self.error.is_some() {
             return self;
         }
         if items.is_empty() {
-            self.error = Some(QueryError::BindError(
-                "cannot insert empty list".to_string(),
-            ));
+            self.no_rows = true;
             return self;
         }
         let cols = M::bind_column_names();
-        if cols.is_empty() {
+        let stamped = stamped_columns::<M>();
+        if cols.is_empty() && stamped.is_empty() {
             self.error = Some(QueryError::BindError("no columns to insert".to_string()));
             return self;
         }
-        let mut sql = format!("INSERT INTO {} ({}) VALUES ", self.source, cols.join(", "));
+        let mut sql = insert_prefix(&self.source, &cols, &stamped);
         for (i, item) in items.iter().enumerate() {
             if i > 0 {
                 sql.push_str(", ");
             }
             sql.push('(');
             match self.bind_row_placeholders(item, cols.len(), &mut sql) {
-                Ok(()) => sql.push(')'),
+                Ok(()) => {
+                    push_timestamps(&mut sql, !cols.is_empty(), stamped.len());
+                    sql.push(')');
+                }
                 Err(e) => {
                     self.error = Some(e);
                     return self;
@@ -80,6 +93,13 @@ impl InsertQuery {
         self
     }
 
+    /// Largest number of `M` rows one `.rows()` statement can bind on the
+    /// active backend.
+    pub fn max_rows_per_statement<M: Bindable>() -> usize {
+        let cols = M::bind_column_names().len().max(1);
+        (Dialect::active().max_bind_params() / cols).max(1)
+    }
+
     /// INSERT ... ON CONFLICT DO NOTHING (Postgres only).
     #[cfg(feature = "postgres")]
     pub fn upsert<M: Bindable>(mut self, item: &M, conflict_cols: &[&str]) -> Self {
@@ -102,10 +122,12 @@ impl InsertQuery {
         if self.error.is_some() {
             return self;
         }
-        let cols = M::bind_column_names();
+        let created = M::auto_now_add_columns();
+        let cols = M::bind_column_names()
+            .into_iter()
+            .chain(M::auto_now_columns());
         let set_clause: Vec<String> = cols
-            .iter()
-            .filter(|c| !conflict_cols.contains(&c.as_str()))
+            .filter(|c| !conflict_cols.contains(&c.as_str()) && !created.contains(c))
             .map(|c| format!("{} = EXCLUDED.{}", c, c))
             .collect();
         if let Some(ref mut sql) = self.sql {
@@ -117,8 +139,27 @@ impl InsertQuery {
         self
     }
 
+    /// Limits `RETURNING` to `columns` instead of `*` (Postgres only). The
+    /// row type passed to `one`/`first`/`all` must decode exactly these
+    /// columns; a missing column fails at decode time with `DbError::Fatal`.
+    #[cfg(feature = "postgres")]
+    pub fn returning_columns(mut self, columns: &[&str]) -> Self {
+        for column in columns {
+            if let Err(err) = super::validate_ident(column) {
+                self.error.get_or_insert(err);
+            }
+            self.returning.push(column.to_string());
+        }
+        self
+    }
+
     // ── internal ──────────────────────────────────────────────────────────────
 
+    /// True when `.rows(&[])` left nothing to insert.
+    fn is_noop(&self) -> bool {
+        self.no_rows && self.sql.is_none() && self.error.is_none()
+    }
+
     fn bind_row_placeholders<M: Bindable>(
         &mut self,
         item: &M,
@@ -155,44 +196,121 @@ impl InsertQuery {
         Ok(Statement::new(&format!("{}{}", base, suffix), self.args))
     }
 
+    #[cfg(feature = "postgres")]
+    fn returning_clause(&self) -> String {
+        if self.returning.is_empty() {
+            " RETURNING *".to_string()
+        } else {
+            format!(" RETURNING {}", self.returning.join(", "))
+        }
+    }
+
     // ── terminal methods ──────────────────────────────────────────────────────
 
     pub async fn execute<S: DBSession>(self, session: &mut S) -> Result<u64, DbError> {
+        if self.is_noop() {
+            return Ok(0);
+        }
+        let tag = table_tag(&self.source).to_string();
         let stmt = self.into_statement_with_suffix("")?;
-        session.execute(stmt).await
+        let result = session.execute(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 
-    /// Execute and return the first inserted row via RETURNING * (Postgres only).
+    /// Execute and return the first inserted row via RETURNING (Postgres only).
     #[cfg(feature = "postgres")]
     pub async fn one<M, S>(self, session: &mut S) -> Result<M, DbError>
     where
-        M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
+        M: for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
         S: DBSession,
     {
-        let stmt = self.into_statement_with_suffix(" RETURNING *")?;
-        session.fetch_one(stmt).await
+        if self.is_noop() {
+            return Err(DbError::DoesNotExist);
+        }
+        let tag = table_tag(&self.source).to_string();
+        let returning = self.returning_clause();
+        let stmt = self.into_statement_with_suffix(&returning)?;
+        let result = session.fetch_one(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 
-    /// Execute and return all inserted rows via RETURNING * (Postgres only).
+    /// Execute and return all inserted rows via RETURNING (Postgres only).
     #[cfg(feature = "postgres")]
     pub async fn all<M, S>(self, session: &mut S) -> Result<Vec<M>, DbError>
     where
-        M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
+        M: for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
         S: DBSession,
     {
-        let stmt = self.into_statement_with_suffix(" RETURNING *")?;
-        session.fetch_all(stmt).await
+        if self.is_noop() {
+            return Ok(Vec::new());
+        }
+        let tag = table_tag(&self.source).to_string();
+        let returning = self.returning_clause();
+        let stmt = self.into_statement_with_suffix(&returning)?;
+        let result = session.fetch_all(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 
-    /// Execute and return the first inserted row if any via RETURNING * (Postgres only).
+    /// Execute and return the first inserted row if any via RETURNING (Postgres only).
     #[cfg(feature = "postgres")]
     pub async fn first<M, S>(self, session: &mut S) -> Result<Option<M>, DbError>
     where
-        M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
+        M: for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
+        S: DBSession,
+    {
+        if self.is_noop() {
+            return Ok(None);
+        }
+        let tag = table_tag(&self.source).to_string();
+        let returning = self.returning_clause();
+        let stmt = self.into_statement_with_suffix(&returning)?;
+        let result = session.fetch_optional(stmt).await;
+        session.invalidate_tag(&tag);
+        result
+    }
+
+    /// Execute and decode the first column of the first returned row, e.g.
+    /// a generated id after `.returning_columns(&["id"])` (Postgres only).
+    #[cfg(feature = "postgres")]
+    pub async fn scalar<T, S>(self, session: &mut S) -> Result<T, DbError>
+    where
+        for<'d> T: sqlx::Decode<'d, Database> + sqlx::Type<Database> + Send + Unpin + 'static,
         S: DBSession,
     {
-        let stmt = self.into_statement_with_suffix(" RETURNING *")?;
-        session.fetch_optional(stmt).await
+        if self.is_noop() {
+            return Err(DbError::DoesNotExist);
+        }
+        let tag = table_tag(&self.source).to_string();
+        let returning = self.returning_clause();
+        let stmt = self.into_statement_with_suffix(&returning)?;
+        let result = session.fetch_scalar(stmt).await;
+        session.invalidate_tag(&tag);
+        result
+    }
+}
+
+/// `auto_now_add` then `auto_now` columns, which inserts set in SQL.
+fn stamped_columns<M: Bindable>() -> Vec<String> {
+    let mut cols = M::auto_now_add_columns();
+    cols.extend(M::auto_now_columns());
+    cols
+}
+
+fn insert_prefix(source: &str, cols: &[String], stamped: &[String]) -> String {
+    let all: Vec<&str> = cols.iter().chain(stamped).map(String::as_str).collect();
+    format!("INSERT INTO {} ({}) VALUES ", source, all.join(", "))
+}
+
+/// Appends one `CURRENT_TIMESTAMP` per stamped column after the placeholders.
+fn push_timestamps(sql: &mut String, after_binds: bool, count: usize) {
+    for i in 0..count {
+        if after_binds || i > 0 {
+            sql.push_str(", ");
+        }
+        sql.push_str("CURRENT_TIMESTAMP");
     }
 }
 
@@ -1,15 +1,22 @@
+pub(crate) mod cond;
 pub(crate) mod delete;
+pub(crate) mod group;
 pub(crate) mod insert;
 pub(crate) mod select;
 pub(crate) mod update;
 
+pub use cond::{Col, Cond, col};
 pub use delete::DeleteQuery;
+pub use group::FilterGroup;
 pub use insert::InsertQuery;
 pub use select::SelectQuery;
 pub use update::UpdateQuery;
 
-use crate::db::argvalue::ArgValue;
+use crate::auth::AuthUser;
+use crate::db::argvalue::{ArgValue, debug_literal};
 use crate::db::commons::{Arguments, Database};
+use crate::db::interfaces::{Filterable, Owned};
+use crate::db::placeholders::{Dialect, PlaceholderIter, PlaceholderPart};
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -17,6 +24,7 @@ pub struct Statement {
     pub sql: String,
     pub args: Arguments<'static>,
     pub(crate) error: Option<Arc<sqlx::error::BoxDynError>>,
+    debug_args: DebugArgs,
 }
 
 impl Statement {
@@ -25,6 +33,7 @@ impl Statement {
             sql: sql.to_string(),
             args,
             error: None,
+            debug_args: DebugArgs::default(),
         }
     }
 
@@ -33,8 +42,12 @@ impl Statement {
         T: for<'q> sqlx::Encode<'q, Database> + sqlx::Type<Database> + Send + 'static,
     {
         use sqlx::Arguments as _;
+        let literal = debug_literal(&val);
         match self.args.add(val) {
-            Ok(()) => self,
+            Ok(()) => {
+                self.debug_args.record(&self.args, literal);
+                self
+            }
             Err(e) => {
                 self.error = Some(Arc::new(e));
                 self
@@ -43,11 +56,38 @@ impl Statement {
     }
 
     pub fn from_str(sql: &str) -> Self {
-        Self {
-            sql: sql.to_string(),
-            args: Arguments::default(),
-            error: None,
+        Self::new(sql, Arguments::default())
+    }
+
+    /// Attaches the literals recorded while a query builder bound `args`.
+    pub(crate) fn with_debug_args(mut self, debug_args: DebugArgs) -> Self {
+        self.debug_args = debug_args;
+        self
+    }
+
+    /// The SQL followed by the bound values, e.g.
+    /// `SELECT * FROM users WHERE name = $1 -- $1 = 'alice'`.
+    ///
+    /// Values are recorded in debug builds only, for strings, numbers, bools,
+    /// UUIDs, dates and their `Option`s. Other types, `Bindable` rows,
+    /// arguments passed to [`Statement::new`], and all values in release
+    /// builds show as `?`.
+    pub fn debug_query_with_args(&self) -> String {
+        let total = sqlx::Arguments::len(&self.args);
+        if total == 0 {
+            return self.sql.clone();
         }
+        let sigil = match Dialect::active() {
+            Dialect::Postgres => '$',
+            Dialect::Mysql | Dialect::Sqlite => '?',
+        };
+        let values: Vec<String> = (0..total)
+            .map(|i| {
+                let value = self.debug_args.get(i).unwrap_or("?");
+                format!("{sigil}{} = {value}", i + 1)
+            })
+            .collect();
+        format!("{} -- {}", self.sql, values.join(", "))
     }
 
     /// Returns the SQL and arguments, or a bind error if one occurred.
@@ -59,6 +99,28 @@ impl Statement {
     }
 }
 
+/// SQL literals of bound values by argument position, for
+/// [`Statement::debug_query_with_args`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DebugArgs(Vec<Option<Arc<str>>>);
+
+impl DebugArgs {
+    /// Records `literal` for the value just added to `args`. Positions bound
+    /// without a record, such as `Bindable` rows, stay unknown.
+    pub(crate) fn record(&mut self, args: &Arguments<'static>, literal: Option<Arc<str>>) {
+        if literal.is_none() {
+            return;
+        }
+        let position = sqlx::Arguments::len(args).saturating_sub(1);
+        self.0.resize(position, None);
+        self.0.push(literal);
+    }
+
+    fn get(&self, position: usize) -> Option<&str> {
+        self.0.get(position)?.as_deref()
+    }
+}
+
 #[derive(Clone, Debug, thiserror::Error)]
 pub enum QueryError {
     #[error("bind error: {0}")]
@@ -77,6 +139,12 @@ pub enum QueryError {
         "invalid identifier '{0}': only alphanumerics, underscores, dots, and spaces are allowed"
     )]
     InvalidIdentifier(String),
+    #[error("invalid column '{0}': only letters, digits, underscores, and dots are allowed")]
+    InvalidColumn(String),
+    #[error("ORDER BY must start with the DISTINCT ON columns, but '{0}' follows another column")]
+    DistinctOnConflict(String),
+    #[error("{0} needs for_model::<M>() to apply soft deletes, or with_deleted() to ignore them")]
+    ModelNotSet(&'static str),
 }
 
 /// A page of results from a paginated query.
@@ -96,11 +164,226 @@ pub enum LockMode {
     Share,
 }
 
+/// Comparison applied by a [`Filterable`] field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    Contains,
+    IContains,
+    StartsWith,
+    EndsWith,
+}
+
+impl FilterOp {
+    /// SQL condition comparing `column` with the named placeholder `param`.
+    pub fn condition(self, column: &str, param: &str) -> String {
+        let op = match self {
+            Self::Eq => "=",
+            Self::Ne => "<>",
+            Self::Lt => "<",
+            Self::Lte => "<=",
+            Self::Gt => ">",
+            Self::Gte => ">=",
+            Self::IContains => return format!("LOWER({column}) LIKE LOWER(:{param}) ESCAPE '!'"),
+            Self::Contains | Self::StartsWith | Self::EndsWith => {
+                return format!("{column} LIKE :{param} ESCAPE '!'");
+            }
+        };
+        format!("{column} {op} :{param}")
+    }
+
+    /// LIKE pattern matching `value` literally, with `!` as the escape char.
+    pub fn pattern(self, value: impl std::fmt::Display) -> String {
+        let mut escaped = String::new();
+        for ch in value.to_string().chars() {
+            if matches!(ch, '!' | '%' | '_') {
+                escaped.push('!');
+            }
+            escaped.push(ch);
+        }
+        match self {
+            Self::StartsWith => format!("{escaped}%"),
+            Self::EndsWith => format!("%{escaped}"),
+            Self::Contains | Self::IContains => format!("%{escaped}%"),
+            _ => escaped,
+        }
+    }
+}
+
 /// Trait for query builders that support filtering and argument binding.
 pub trait FilteredBuilder: Sized {
+    /// Adds a raw SQL predicate joined with `AND`. The string is trusted: it
+    /// is sent as written, so never format user input into it. Bind values
+    /// instead, or build the predicate with [`FilteredBuilder::filter_cond`].
     fn filter(self, cond: impl Into<std::borrow::Cow<'static, str>>) -> Self;
+    /// Joins `cond` to the most recent condition with `OR`, as
+    /// `(previous OR cond)`; with no previous condition it acts like `filter`.
+    fn or_filter(self, cond: impl Into<std::borrow::Cow<'static, str>>) -> Self;
     fn bind_dyn(self, val: ArgValue) -> Self;
     fn bind_named_dyn(self, name: &str, val: ArgValue) -> Self;
+    /// Records an error that fails the query when it runs.
+    #[doc(hidden)]
+    fn fail(self, err: QueryError) -> Self;
+
+    /// Adds a framework predicate ANDed around every user filter. `or_filter`
+    /// never joins to it, so it can only narrow the query.
+    #[doc(hidden)]
+    fn guard(self, cond: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        self.filter(cond)
+    }
+
+    /// Adds a predicate built with [`col`], whose values are always bound:
+    /// `.filter_cond(col("email").eq(input))`.
+    fn filter_cond(self, cond: Cond) -> Self {
+        if let Some(err) = cond.error {
+            return self.fail(err);
+        }
+        cond.binds
+            .into_iter()
+            .fold(self.filter(cond.sql), |builder, (name, value)| {
+                builder.bind_named_dyn(&name, value)
+            })
+    }
+
+    /// Adds the conditions built in `build`, joined by `OR` and parenthesized:
+    /// `.or_group(|g| g.filter("a").filter("b")).filter("c")` is
+    /// `(a OR b) AND c`. Binds made inside the group apply as usual.
+    fn or_group(self, build: impl FnOnce(FilterGroup) -> FilterGroup) -> Self {
+        build(FilterGroup::new(" OR ")).apply(self)
+    }
+
+    /// Like [`FilteredBuilder::or_group`] but joined by `AND`, for nesting
+    /// inside an OR group: `(a AND b) OR c`.
+    fn and_group(self, build: impl FnOnce(FilterGroup) -> FilterGroup) -> Self {
+        build(FilterGroup::new(" AND ")).apply(self)
+    }
+
+    /// Adds `column <op> :param` bound to `value`. `column` is trusted SQL.
+    fn filter_op(self, column: &str, op: FilterOp, param: &str, value: ArgValue) -> Self {
+        self.filter(op.condition(column, param))
+            .bind_named_dyn(param, value)
+    }
+
+    /// Adds `column IN (...)` with one `:param_N` placeholder per value.
+    /// An empty `values` matches no rows instead of emitting `IN ()`.
+    fn filter_in<T>(self, column: &str, param: &str, values: impl IntoIterator<Item = T>) -> Self
+    where
+        T: Clone
+            + for<'q> sqlx::Encode<'q, Database>
+            + sqlx::Type<Database>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let values: Vec<ArgValue> = values.into_iter().map(ArgValue::new).collect();
+        let builder = self.filter(in_list_condition(column, param, values.len()));
+        values
+            .into_iter()
+            .enumerate()
+            .fold(builder, |builder, (i, value)| {
+                builder.bind_named_dyn(&format!("{param}_{i}"), value)
+            })
+    }
+
+    /// Adds `cond` only when `value` is `Some`. The value is bound to every
+    /// `:name` placeholder in `cond`, or positionally if it has none.
+    fn filter_optional<T>(self, cond: &str, value: Option<T>) -> Self
+    where
+        T: Clone
+            + for<'q> sqlx::Encode<'q, Database>
+            + sqlx::Type<Database>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let Some(value) = value else {
+            return self;
+        };
+        let value = ArgValue::new(value);
+        let names: Vec<&str> = PlaceholderIter::new(cond)
+            .filter_map(|part| match part {
+                PlaceholderPart::Placeholder(name) => Some(name),
+                PlaceholderPart::Sql(_) => None,
+            })
+            .collect();
+        let builder = self.filter(cond.to_string());
+        if names.is_empty() {
+            return builder.bind_dyn(value);
+        }
+        names.into_iter().fold(builder, |builder, name| {
+            builder.bind_named_dyn(name, value.clone())
+        })
+    }
+
+    /// Applies every condition of a [`Filterable`] struct.
+    fn filter_with<F: Filterable>(self, filters: &F) -> Self {
+        filters.apply_filters(self)
+    }
+
+    /// Restricts rows to those owned by `user` unless a bypass role is held.
+    fn with_role_filter<M: Owned>(self, user: &AuthUser) -> Self {
+        if user.roles & M::bypass_roles() != 0 {
+            return self;
+        }
+        match M::owner_key(user) {
+            Ok(key) => self
+                .guard(format!("{} = :vyuh_owner", M::owner_column()))
+                .bind_named_dyn("vyuh_owner", key),
+            Err(err) => self.fail(err),
+        }
+    }
+}
+
+/// ` WHERE (f1) AND (f2) AND (g1)`, or empty with no conditions.
+///
+/// Every condition is parenthesized so an `OR` inside a user filter cannot
+/// escape past the `AND`. `guards` are predicates the framework adds itself,
+/// such as the soft-delete filter; they are kept apart from `filters` so
+/// `or_filter` never rewrites them.
+pub(crate) fn where_clause(
+    filters: &[std::borrow::Cow<'static, str>],
+    guards: &[std::borrow::Cow<'static, str>],
+) -> String {
+    if filters.is_empty() && guards.is_empty() {
+        return String::new();
+    }
+    let parts: Vec<String> = filters
+        .iter()
+        .chain(guards)
+        .map(|cond| format!("({cond})"))
+        .collect();
+    format!(" WHERE {}", parts.join(" AND "))
+}
+
+/// Replaces the last condition with `((last) OR (cond))`.
+pub(crate) fn or_last(
+    conds: &mut Vec<std::borrow::Cow<'static, str>>,
+    cond: std::borrow::Cow<'static, str>,
+) {
+    let joined = match conds.pop() {
+        Some(last) => format!("(({last}) OR ({cond}))").into(),
+        None => cond,
+    };
+    conds.push(joined);
+}
+
+/// Predicate that matches no rows. Empty collections filter to this, since
+/// `IN ()` is a syntax error on every backend and dropping the filter would
+/// widen the query to the whole table.
+pub(crate) const MATCH_NONE: &str = "1 = 0";
+
+/// `column IN (:param_0, ...)` over `count` placeholders, or [`MATCH_NONE`].
+pub(crate) fn in_list_condition(column: &str, param: &str, count: usize) -> String {
+    if count == 0 {
+        return MATCH_NONE.to_string();
+    }
+    let names: Vec<String> = (0..count).map(|i| format!(":{param}_{i}")).collect();
+    format!("{column} IN ({})", names.join(", "))
 }
 
 /// Validate that a SQL identifier contains only safe characters.
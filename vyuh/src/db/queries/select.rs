@@ -2,8 +2,9 @@ use indexmap::IndexMap;
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-use super::{FilteredBuilder, LockMode, Page, QueryError, Statement};
-use crate::db::argvalue::ArgValue;
+use super::{DebugArgs, FilteredBuilder, LockMode, Page, QueryError, Statement};
+use crate::db::argvalue::{ArgValue, debug_literal};
+use crate::db::cache::{self, table_tag};
 use crate::db::commons::{Arguments, Row};
 use crate::db::executor::{DBSession, DbError};
 use crate::db::interfaces::Scannable;
@@ -16,14 +17,21 @@ pub struct SelectQuery {
     source: String,
     alias_map: IndexMap<Cow<'static, str>, Cow<'static, str>>,
     filters: Vec<Cow<'static, str>>,
+    guards: Vec<Cow<'static, str>>,
     group_by: Vec<String>,
     having: Vec<Cow<'static, str>>,
+    distinct: bool,
+    distinct_on: Vec<String>,
     order_by: Vec<(String, bool)>,
     limit: Option<(usize, usize)>,
     select_exprs: IndexMap<String, Scope>,
     lock_mode: Option<LockMode>,
+    cache_tag: Option<String>,
+    soft_delete: SoftDelete,
+    columns: Option<Vec<String>>,
     args: Arguments<'static>,
     named_args: HashMap<String, ArgValue>,
+    debug_args: DebugArgs,
     error: Option<QueryError>,
 }
 
@@ -33,14 +41,21 @@ impl SelectQuery {
             source: source.to_string(),
             alias_map: IndexMap::new(),
             filters: Vec::new(),
+            guards: Vec::new(),
             group_by: Vec::new(),
             having: Vec::new(),
+            distinct: false,
+            distinct_on: Vec::new(),
             order_by: Vec::new(),
             limit: None,
             select_exprs: IndexMap::new(),
             lock_mode: None,
+            cache_tag: None,
+            soft_delete: SoftDelete::Pending,
+            columns: None,
             args: Arguments::default(),
             named_args: HashMap::new(),
+            debug_args: DebugArgs::default(),
             error: super::validate_ident(source).err(),
         }
     }
@@ -64,6 +79,25 @@ impl SelectQuery {
         self
     }
 
+    /// Emit `SELECT DISTINCT`.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Emit `SELECT DISTINCT ON (...)` (Postgres only). The columns are moved
+    /// to the front of ORDER BY at build time; building fails if an ORDER BY
+    /// entry that is not one of them would have to precede them.
+    pub fn distinct_on(mut self, columns: &[&str]) -> Self {
+        for column in columns {
+            if let Err(err) = super::validate_ident(column) {
+                self.error.get_or_insert(err);
+            }
+            self.distinct_on.push(column.to_string());
+        }
+        self
+    }
+
     /// Add an ORDER BY clause. Earlier calls have higher precedence.
     pub fn order_by(mut self, column: &str, ascending: bool) -> Self {
         self.order_by.push((column.to_string(), ascending));
@@ -101,8 +135,10 @@ impl SelectQuery {
         if self.error.is_some() {
             return self;
         }
-        if let Err(e) = self.args.add(val) {
-            self.error = Some(QueryError::BindError(e.to_string()));
+        let literal = debug_literal(&val);
+        match self.args.add(val) {
+            Ok(()) => self.debug_args.record(&self.args, literal),
+            Err(e) => self.error = Some(QueryError::BindError(e.to_string())),
         }
         self
     }
@@ -138,13 +174,52 @@ impl SelectQuery {
         self
     }
 
+    /// Tag used by [`SelectQuery::all_cached`]. Defaults to the source table.
+    pub fn cache_tag(mut self, tag: impl Into<String>) -> Self {
+        self.cache_tag = Some(tag.into());
+        self
+    }
+
+    /// Includes rows a soft-delete model marks as deleted. `count` and
+    /// `exists` on a table without a model need this, or `for_model`.
+    pub fn with_deleted(mut self) -> Self {
+        self.soft_delete = SoftDelete::Settled;
+        self
+    }
+
+    /// Applies `M`'s soft-delete filter now, for `count` and `exists`, which
+    /// do not know the model and refuse to run until this or `with_deleted`
+    /// is called. Row-returning terminals apply it on their own. `count`
+    /// also projects `M`'s columns, so `distinct` counts the rows `all` returns.
+    pub fn for_model<M: Scannable>(mut self) -> Self {
+        self.exclude_deleted::<M>();
+        self.columns.get_or_insert_with(M::scan_column_names);
+        self
+    }
+
     // ── internal builders ─────────────────────────────────────────────────────
 
-    fn build_filter_clause(&self) -> String {
-        if self.filters.is_empty() {
-            return String::new();
+    fn exclude_deleted<M: Scannable>(&mut self) {
+        if self.soft_delete == SoftDelete::Settled {
+            return;
+        }
+        self.soft_delete = SoftDelete::Settled;
+        if let Some(column) = M::soft_delete_column() {
+            self.guards.push(format!("{column} IS NULL").into());
         }
-        format!(" WHERE {}", self.filters.join(" AND "))
+    }
+
+    /// Model-less terminals cannot tell whether `source` holds soft-deleted
+    /// rows, so they only run once the caller has decided.
+    fn require_model(&self, terminal: &'static str) -> Result<(), QueryError> {
+        match self.soft_delete {
+            SoftDelete::Pending => Err(QueryError::ModelNotSet(terminal)),
+            SoftDelete::Settled => Ok(()),
+        }
+    }
+
+    fn build_filter_clause(&self) -> String {
+        super::where_clause(&self.filters, &self.guards)
     }
 
     fn build_group_by_clause(&self) -> String {
@@ -161,6 +236,44 @@ impl SelectQuery {
         format!(" HAVING {}", self.having.join(" AND "))
     }
 
+    fn build_distinct_clause(&self) -> String {
+        if !self.distinct_on.is_empty() {
+            format!("DISTINCT ON ({}) ", self.distinct_on.join(", "))
+        } else if self.distinct {
+            "DISTINCT ".to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    /// Reorders ORDER BY so the DISTINCT ON columns lead it, as Postgres
+    /// requires. Missing columns are inserted ascending after any that lead.
+    fn align_order_with_distinct_on(&mut self) {
+        if self.distinct_on.is_empty() {
+            return;
+        }
+        let leading = self
+            .order_by
+            .iter()
+            .take_while(|(col, _)| self.distinct_on.contains(col))
+            .count();
+        if let Some((col, _)) = self.order_by[leading..]
+            .iter()
+            .find(|(col, _)| self.distinct_on.contains(col))
+        {
+            self.error
+                .get_or_insert(QueryError::DistinctOnConflict(col.clone()));
+            return;
+        }
+        let missing: Vec<(String, bool)> = self
+            .distinct_on
+            .iter()
+            .filter(|col| !self.order_by[..leading].iter().any(|(c, _)| c == *col))
+            .map(|col| (col.clone(), true))
+            .collect();
+        self.order_by.splice(leading..leading, missing);
+    }
+
     fn build_order_by_clause(&self) -> String {
         if self.order_by.is_empty() {
             return String::new();
@@ -188,24 +301,45 @@ impl SelectQuery {
         }
     }
 
-    fn resolve(mut self, sql: String) -> Result<(String, Arguments<'static>), QueryError> {
+    fn resolve(mut self, sql: String) -> Result<Statement, QueryError> {
         if let Some(err) = self.error {
             return Err(err);
         }
         if self.named_args.is_empty() && !has_named_placeholder(&sql) {
-            return Ok((sql, self.args));
+            return Ok(Statement::new(&sql, self.args).with_debug_args(self.debug_args));
         }
-        let final_sql =
-            resolve_placeholders(&sql, &mut self.args, &self.named_args, Dialect::active())?;
-        Ok((final_sql, self.args))
+        let final_sql = resolve_placeholders(
+            &sql,
+            &mut self.args,
+            &self.named_args,
+            Dialect::active(),
+            |args, value| self.debug_args.record(args, value.literal()),
+        )?;
+        Ok(Statement::new(&final_sql, self.args).with_debug_args(self.debug_args))
     }
 
     fn build_select_sql<M: Scannable>(&mut self) -> String {
+        self.exclude_deleted::<M>();
+        self.align_order_with_distinct_on();
+        let mut sql = format!("SELECT {}", self.build_distinct_clause());
+        self.push_projection(&mut sql, &M::scan_column_names());
+        sql.push_str(" FROM ");
+        sql.push_str(&self.source);
+        sql.push_str(&self.build_filter_clause());
+        sql.push_str(&self.build_group_by_clause());
+        sql.push_str(&self.build_having_clause());
+        sql.push_str(&self.build_order_by_clause());
+        sql.push_str(&self.build_limit_clause());
+        sql.push_str(self.build_lock_clause());
+        sql
+    }
+
+    /// Appends the scanned columns, mapping dotted prefixes to table aliases
+    /// and substituting `select_expr` scopes.
+    fn push_projection(&self, sql: &mut String, col_names: &[String]) {
         let mut aliases = self.alias_map.clone();
-        let mut sql = String::from("SELECT ");
-        let col_names = M::scan_column_names();
         let mut first = true;
-        for col in &col_names {
+        for col in col_names {
             if !first {
                 sql.push_str(", ");
             }
@@ -230,20 +364,20 @@ impl SelectQuery {
                 sql.push_str(col);
             }
         }
-        sql.push_str(" FROM ");
-        sql.push_str(&self.source);
-        sql.push_str(&self.build_filter_clause());
-        sql.push_str(&self.build_group_by_clause());
-        sql.push_str(&self.build_having_clause());
-        sql.push_str(&self.build_order_by_clause());
-        sql.push_str(&self.build_limit_clause());
-        sql.push_str(self.build_lock_clause());
-        sql
     }
 
+    /// Counts over the same projection `build_select_sql` emits, so DISTINCT
+    /// compares the model's columns. Without a model it falls back to `*`.
     fn build_count_sql(&mut self) -> String {
+        let mut projection = String::new();
+        match &self.columns {
+            Some(columns) => self.push_projection(&mut projection, columns),
+            None => projection.push('*'),
+        }
         let inner = format!(
-            "SELECT * FROM {}{}{}{}",
+            "SELECT {}{} FROM {}{}{}{}",
+            self.build_distinct_clause(),
+            projection,
             self.source,
             self.build_filter_clause(),
             self.build_group_by_clause(),
@@ -261,8 +395,8 @@ impl SelectQuery {
     {
         self.limit = Some((self.limit.map_or(0, |(o, _)| o), 1));
         let sql = self.build_select_sql::<M>();
-        let (sql, args) = self.resolve(sql)?;
-        session.fetch_one(Statement::new(&sql, args)).await
+        let stmt = self.resolve(sql)?;
+        session.fetch_one(stmt).await
     }
 
     pub async fn all<M, S>(mut self, session: &mut S) -> Result<Vec<M>, DbError>
@@ -271,8 +405,36 @@ impl SelectQuery {
         S: DBSession,
     {
         let sql = self.build_select_sql::<M>();
-        let (sql, args) = self.resolve(sql)?;
-        session.fetch_all(Statement::new(&sql, args)).await
+        let stmt = self.resolve(sql)?;
+        session.fetch_all(stmt).await
+    }
+
+    /// Like [`SelectQuery::all`], but memoizes rows in the pool's
+    /// [`QueryCache`](crate::db::QueryCache) until the cache tag is
+    /// invalidated by a write to the table of the same name. Sessions without
+    /// a cache, such as transactions, always query.
+    pub async fn all_cached<M, S>(mut self, session: &mut S) -> Result<Vec<M>, DbError>
+    where
+        M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Clone + Send + Sync + Unpin + 'static,
+        S: DBSession,
+    {
+        let tag = match self.cache_tag.take() {
+            Some(tag) => tag,
+            None => table_tag(&self.source).to_string(),
+        };
+        let sql = self.build_select_sql::<M>();
+        let stmt = self.resolve(sql)?;
+        let Some(cache) = session.query_cache().cloned() else {
+            return session.fetch_all(stmt).await;
+        };
+        let key = cache::cache_key::<M>(&stmt.sql, &stmt.args);
+        let version = match cache.lookup::<Vec<M>>(&tag, &key) {
+            Ok(rows) => return Ok(rows),
+            Err(version) => version,
+        };
+        let rows: Vec<M> = session.fetch_all(stmt).await?;
+        cache.store(&tag, key, version, rows.clone());
+        Ok(rows)
     }
 
     pub async fn first<M, S>(mut self, session: &mut S) -> Result<Option<M>, DbError>
@@ -282,19 +444,23 @@ impl SelectQuery {
     {
         self.limit = Some((self.limit.map_or(0, |(o, _)| o), 1));
         let sql = self.build_select_sql::<M>();
-        let (sql, args) = self.resolve(sql)?;
-        session.fetch_optional(Statement::new(&sql, args)).await
+        let stmt = self.resolve(sql)?;
+        session.fetch_optional(stmt).await
     }
 
+    /// Counts matching rows. Call `for_model` or `with_deleted` first.
     pub async fn count<S: DBSession>(mut self, session: &mut S) -> Result<i64, DbError> {
+        self.require_model("count")?;
         self.order_by.clear();
         self.limit = None;
         let sql = self.build_count_sql();
-        let (sql, args) = self.resolve(sql)?;
-        session.fetch_scalar(Statement::new(&sql, args)).await
+        let stmt = self.resolve(sql)?;
+        session.fetch_scalar(stmt).await
     }
 
+    /// Whether any row matches. Call `for_model` or `with_deleted` first.
     pub async fn exists<S: DBSession>(mut self, session: &mut S) -> Result<bool, DbError> {
+        self.require_model("exists")?;
         self.order_by.clear();
         self.limit = None;
         let inner = format!(
@@ -305,16 +471,17 @@ impl SelectQuery {
             self.build_having_clause(),
         );
         let sql = format!("SELECT EXISTS ({})", inner);
-        let (sql, args) = self.resolve(sql)?;
-        session.fetch_scalar(Statement::new(&sql, args)).await
+        let stmt = self.resolve(sql)?;
+        session.fetch_scalar(stmt).await
     }
 
     /// Fetch a paginated result set along with the total count.
-    pub async fn page<M, S>(self, session: &mut S) -> Result<Page<M>, DbError>
+    pub async fn page<M, S>(mut self, session: &mut S) -> Result<Page<M>, DbError>
     where
         M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
         S: DBSession,
     {
+        self = self.for_model::<M>();
         let (page_num, per_page) = match self.limit {
             Some((offset, count)) if count > 0 => (offset / count + 1, count),
             _ => (1, usize::MAX),
@@ -339,18 +506,41 @@ impl SelectQuery {
     }
 }
 
+/// Whether the model's soft-delete filter still has to be added.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SoftDelete {
+    Pending,
+    Settled,
+}
+
 impl FilteredBuilder for SelectQuery {
     fn filter(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
         self.filters.push(cond.into());
         self
     }
 
+    fn or_filter(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
+        super::or_last(&mut self.filters, cond.into());
+        self
+    }
+
+    fn guard(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
+        self.guards.push(cond.into());
+        self
+    }
+
+    fn fail(mut self, err: QueryError) -> Self {
+        self.error.get_or_insert(err);
+        self
+    }
+
     fn bind_dyn(mut self, val: ArgValue) -> Self {
         if self.error.is_some() {
             return self;
         }
-        if let Err(e) = val.bind_value(&mut self.args) {
-            self.error = Some(QueryError::BindError(e.to_string()));
+        match val.bind_value(&mut self.args) {
+            Ok(()) => self.debug_args.record(&self.args, val.literal()),
+            Err(e) => self.error = Some(QueryError::BindError(e.to_string())),
         }
         self
     }
@@ -1,8 +1,9 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-use super::{FilteredBuilder, QueryError, Statement};
-use crate::db::argvalue::ArgValue;
+use super::{DebugArgs, FilteredBuilder, QueryError, Statement};
+use crate::db::argvalue::{ArgValue, debug_literal};
+use crate::db::cache::table_tag;
 use crate::db::commons::Arguments;
 #[cfg(feature = "postgres")]
 use crate::db::commons::Row;
@@ -16,8 +17,12 @@ use crate::db::placeholders::{Dialect, has_named_placeholder, resolve_placeholde
 pub struct UpdateQuery {
     source: String,
     filters: Vec<Cow<'static, str>>,
+    guards: Vec<Cow<'static, str>>,
+    soft_column: Option<&'static str>,
+    with_deleted: bool,
     args: Arguments<'static>,
     named_args: HashMap<String, ArgValue>,
+    debug_args: DebugArgs,
     set_sql: Option<String>,
     error: Option<QueryError>,
 }
@@ -27,8 +32,12 @@ impl UpdateQuery {
         Self {
             source: source.to_string(),
             filters: Vec::new(),
+            guards: Vec::new(),
+            soft_column: None,
+            with_deleted: false,
             args: Arguments::default(),
             named_args: HashMap::new(),
+            debug_args: DebugArgs::default(),
             set_sql: None,
             error: super::validate_ident(source).err(),
         }
@@ -46,8 +55,10 @@ impl UpdateQuery {
         if self.error.is_some() {
             return self;
         }
-        if let Err(e) = self.args.add(val) {
-            self.error = Some(QueryError::BindError(e.to_string()));
+        let literal = debug_literal(&val);
+        match self.args.add(val) {
+            Ok(()) => self.debug_args.record(&self.args, literal),
+            Err(e) => self.error = Some(QueryError::BindError(e.to_string())),
         }
         self
     }
@@ -69,14 +80,25 @@ impl UpdateQuery {
         self
     }
 
+    /// Includes rows a soft-delete model marks as deleted.
+    pub fn with_deleted(mut self) -> Self {
+        self.with_deleted = true;
+        self
+    }
+
     /// Specify SET columns from a Bindable item.
+    ///
+    /// When `M` is a soft-delete model, rows already marked deleted are left
+    /// untouched unless [`UpdateQuery::with_deleted`] is called.
     pub fn set<M: Bindable>(mut self, item: &M) -> Self {
         use sqlx::Arguments as _;
         if self.error.is_some() {
             return self;
         }
+        self.soft_column = M::soft_delete_guard();
         let cols = M::bind_column_names();
-        if cols.is_empty() {
+        let stamped = M::auto_now_columns();
+        if cols.is_empty() && stamped.is_empty() {
             self.error = Some(QueryError::BindError("no columns to update".to_string()));
             return self;
         }
@@ -97,6 +119,11 @@ impl UpdateQuery {
             .iter()
             .enumerate()
             .map(|(i, col)| format!("{} = {}", col, placeholder_at(before + i)))
+            .chain(
+                stamped
+                    .iter()
+                    .map(|col| format!("{col} = CURRENT_TIMESTAMP")),
+            )
             .collect();
         self.set_sql = Some(set_parts.join(", "));
         self
@@ -105,28 +132,33 @@ impl UpdateQuery {
     // ── internal ──────────────────────────────────────────────────────────────
 
     fn build_filter_clause(&self) -> String {
-        if self.filters.is_empty() {
-            return String::new();
-        }
-        format!(" WHERE {}", self.filters.join(" AND "))
+        super::where_clause(&self.filters, &self.guards)
     }
 
-    fn resolve(mut self, sql: String) -> Result<(String, Arguments<'static>), QueryError> {
+    fn resolve(mut self, sql: String) -> Result<Statement, QueryError> {
         if let Some(err) = self.error {
             return Err(err);
         }
         if self.named_args.is_empty() && !has_named_placeholder(&sql) {
-            return Ok((sql, self.args));
+            return Ok(Statement::new(&sql, self.args).with_debug_args(self.debug_args));
         }
-        let final_sql =
-            resolve_placeholders(&sql, &mut self.args, &self.named_args, Dialect::active())?;
-        Ok((final_sql, self.args))
+        let final_sql = resolve_placeholders(
+            &sql,
+            &mut self.args,
+            &self.named_args,
+            Dialect::active(),
+            |args, value| self.debug_args.record(args, value.literal()),
+        )?;
+        Ok(Statement::new(&final_sql, self.args).with_debug_args(self.debug_args))
     }
 
-    fn into_statement_with_suffix(self, suffix: &str) -> Result<Statement, QueryError> {
+    fn into_statement_with_suffix(mut self, suffix: &str) -> Result<Statement, QueryError> {
         if let Some(ref err) = self.error {
             return Err(err.clone());
         }
+        if let Some(column) = self.soft_column.filter(|_| !self.with_deleted) {
+            self.guards.push(format!("{column} IS NULL").into());
+        }
         let set_sql = self.set_sql.as_deref().ok_or_else(|| {
             QueryError::BindError("no SET data — call .set(item) before executing".to_string())
         })?;
@@ -137,15 +169,17 @@ impl UpdateQuery {
             self.build_filter_clause(),
             suffix,
         );
-        let (final_sql, final_args) = self.resolve(sql)?;
-        Ok(Statement::new(&final_sql, final_args))
+        self.resolve(sql)
     }
 
     // ── terminal methods ──────────────────────────────────────────────────────
 
     pub async fn execute<S: DBSession>(self, session: &mut S) -> Result<u64, DbError> {
+        let tag = table_tag(&self.source).to_string();
         let stmt = self.into_statement_with_suffix("")?;
-        session.execute(stmt).await
+        let result = session.execute(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 
     /// Execute and return updated rows via RETURNING * (Postgres only).
@@ -155,8 +189,11 @@ impl UpdateQuery {
         M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
         S: DBSession,
     {
+        let tag = table_tag(&self.source).to_string();
         let stmt = self.into_statement_with_suffix(" RETURNING *")?;
-        session.fetch_one(stmt).await
+        let result = session.fetch_one(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 
     #[cfg(feature = "postgres")]
@@ -165,8 +202,11 @@ impl UpdateQuery {
         M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
         S: DBSession,
     {
+        let tag = table_tag(&self.source).to_string();
         let stmt = self.into_statement_with_suffix(" RETURNING *")?;
-        session.fetch_all(stmt).await
+        let result = session.fetch_all(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 
     #[cfg(feature = "postgres")]
@@ -175,8 +215,11 @@ impl UpdateQuery {
         M: Scannable + for<'r> sqlx::FromRow<'r, Row> + Send + Unpin + 'static,
         S: DBSession,
     {
+        let tag = table_tag(&self.source).to_string();
         let stmt = self.into_statement_with_suffix(" RETURNING *")?;
-        session.fetch_optional(stmt).await
+        let result = session.fetch_optional(stmt).await;
+        session.invalidate_tag(&tag);
+        result
     }
 }
 
@@ -186,12 +229,28 @@ impl FilteredBuilder for UpdateQuery {
         self
     }
 
+    fn or_filter(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
+        super::or_last(&mut self.filters, cond.into());
+        self
+    }
+
+    fn guard(mut self, cond: impl Into<Cow<'static, str>>) -> Self {
+        self.guards.push(cond.into());
+        self
+    }
+
+    fn fail(mut self, err: QueryError) -> Self {
+        self.error.get_or_insert(err);
+        self
+    }
+
     fn bind_dyn(mut self, val: ArgValue) -> Self {
         if self.error.is_some() {
             return self;
         }
-        if let Err(e) = val.bind_value(&mut self.args) {
-            self.error = Some(QueryError::BindError(e.to_string()));
+        match val.bind_value(&mut self.args) {
+            Ok(()) => self.debug_args.record(&self.args, val.literal()),
+            Err(e) => self.error = Some(QueryError::BindError(e.to_string())),
         }
         self
     }
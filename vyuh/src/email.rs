@@ -0,0 +1,142 @@
+//! Transactional email delivered by a durable task.
+//!
+//! Applications expose a [`Mailer`] service facade and add [`bundle()`] to the
+//! site. Handlers render the body, then submit an [`Email`] through
+//! `site.tasks()`; the `send_email` task hands it to the mailer.
+
+use futures::future::BoxFuture;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::{
+    Data, Error,
+    bundles::{self, Bundle},
+    services::{Service, ServiceError, ServiceExposer, ServiceRef},
+    tasks::TaskHandlerConf,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum EmailError {
+    #[error("invalid email: {0}")]
+    Invalid(String),
+
+    #[error("email transport error: {0}")]
+    Transport(String),
+}
+
+/// One outgoing message. Serialized as the `send_email` task input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Email {
+    pub to: Vec<String>,
+    #[serde(default)]
+    pub cc: Vec<String>,
+    pub from: Option<String>,
+    pub subject: String,
+    pub text: String,
+    pub html: Option<String>,
+}
+
+impl Email {
+    pub fn new(to: impl Into<String>, subject: impl Into<String>) -> Self {
+        Self {
+            to: vec![to.into()],
+            cc: Vec::new(),
+            from: None,
+            subject: subject.into(),
+            text: String::new(),
+            html: None,
+        }
+    }
+
+    /// Adds a carbon-copy recipient, sent in the `Cc:` header.
+    pub fn cc(mut self, cc: impl Into<String>) -> Self {
+        self.cc.push(cc.into());
+        self
+    }
+
+    /// Overrides the mailer's default sender.
+    pub fn from(mut self, from: impl Into<String>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    pub fn text(mut self, body: impl Into<String>) -> Self {
+        self.text = body.into();
+        self
+    }
+
+    pub fn html(mut self, body: impl Into<String>) -> Self {
+        self.html = Some(body.into());
+        self
+    }
+
+    /// `To`, `Cc`, `From`, and `Subject` header values, for mailers that
+    /// build the message themselves. Empty `Cc` and unset `From` are left out.
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = vec![("To", self.to.join(", "))];
+        if !self.cc.is_empty() {
+            headers.push(("Cc", self.cc.join(", ")));
+        }
+        if let Some(from) = &self.from {
+            headers.push(("From", from.clone()));
+        }
+        headers.push(("Subject", self.subject.clone()));
+        headers
+    }
+}
+
+/// Delivery backend, exposed as `dyn Mailer` by a site service.
+pub trait Mailer: Send + Sync + 'static {
+    fn send<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, Result<(), EmailError>>;
+}
+
+/// Built-in task handler that delivers `email` through the site mailer.
+pub async fn send_email(
+    mailer: ServiceRef<dyn Mailer>,
+    Data(email): Data<Email>,
+) -> Result<(), Error> {
+    if email.to.is_empty() {
+        return Err(Error::other(EmailError::Invalid("no recipients".into())));
+    }
+    mailer.send(&email).await.map_err(Error::other)
+}
+
+/// Bundle registering the `send_email` task.
+pub fn bundle() -> Bundle {
+    bundles::bundle([bundles::task(
+        send_email,
+        TaskHandlerConf::new("send_email"),
+    )])
+}
+
+/// Mailer that keeps sent messages in memory, for tests and local development.
+#[derive(Debug, Default)]
+pub struct MemoryMailer {
+    sent: Mutex<Vec<Email>>,
+}
+
+impl MemoryMailer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages delivered so far, oldest first.
+    pub fn sent(&self) -> Vec<Email> {
+        self.sent.lock().clone()
+    }
+}
+
+impl Mailer for MemoryMailer {
+    fn send<'a>(&'a self, email: &'a Email) -> BoxFuture<'a, Result<(), EmailError>> {
+        self.sent.lock().push(email.clone());
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl Service for MemoryMailer {
+    fn expose(exposer: &mut ServiceExposer<Self>) -> Result<(), ServiceError> {
+        exposer.expose(|service| service as Arc<dyn Mailer>)
+    }
+}
@@ -1,6 +1,7 @@
 use rust_silos::{File as SiloFile, Silo, SiloSet};
 use std::io::Read;
 use std::path::Path;
+use std::sync::Arc;
 
 /// Wrapper around rust-silos File with sync/async read methods
 pub struct File {
@@ -43,17 +44,30 @@ impl File {
 #[derive(Clone, Debug)]
 pub struct Dir {
     inner: Silo,
+    cache_control: Option<Arc<str>>,
 }
 
 impl Dir {
     pub fn empty() -> Self {
+        Self::new(Silo::new(""))
+    }
+
+    pub const fn new(silo: Silo) -> Self {
         Self {
-            inner: Silo::new(""),
+            inner: silo,
+            cache_control: None,
         }
     }
 
-    pub const fn new(silo: Silo) -> Self {
-        Self { inner: silo }
+    /// `Cache-Control` sent for public files served from this dir, replacing
+    /// the default (`no-cache` for HTML, a year and `immutable` otherwise).
+    pub fn with_cache_control(mut self, value: impl Into<Arc<str>>) -> Self {
+        self.cache_control = Some(value.into());
+        self
+    }
+
+    pub(crate) fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
     }
 
     pub fn is_embedded(&self) -> bool {
@@ -75,7 +89,7 @@ impl Dir {
 
 impl From<Silo> for Dir {
     fn from(silo: Silo) -> Self {
-        Self { inner: silo }
+        Self::new(silo)
     }
 }
 
@@ -165,6 +165,9 @@ pub enum EmitterError {
     #[error("Invalid debounce configuration: {0}")]
     InvalidDebounce(String),
 
+    #[error("Unknown schedule: {0}")]
+    UnknownSchedule(String),
+
     #[error("Other error: {0}")]
     OtherError(#[from] Box<dyn std::error::Error + Send + Sync>),
 
@@ -447,6 +450,16 @@ impl EmitterRegistry {
     }
 }
 
+/// Snapshot of a cron or periodic emitter.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleInfo {
+    pub name: String,
+    pub kind: &'static str,
+    /// Next cron fire time; periodic emitters report `interval_secs` instead.
+    pub next_run: Option<chrono::DateTime<chrono::Utc>>,
+    pub interval_secs: Option<u64>,
+}
+
 #[derive(Clone)]
 pub struct EmitterEngine {
     sources: HashMap<(TypeId, u8), Arc<Emitter>>,
@@ -454,6 +467,63 @@ pub struct EmitterEngine {
 }
 
 impl EmitterEngine {
+    /// Lists timer-driven emitters sorted by handler name.
+    pub(crate) fn schedules(&self) -> Vec<ScheduleInfo> {
+        let mut infos = self
+            .sources
+            .values()
+            .filter_map(|emitter| {
+                let name = emitter.source.spec().name.clone();
+                match &emitter.source {
+                    EmitterSource::Cron { schedule, .. } => Some(ScheduleInfo {
+                        name,
+                        kind: "cron",
+                        next_run: schedule.upcoming(chrono::Utc).next(),
+                        interval_secs: None,
+                    }),
+                    EmitterSource::Periodic { interval, .. } => Some(ScheduleInfo {
+                        name,
+                        kind: "periodic",
+                        next_run: None,
+                        interval_secs: Some(interval.as_secs()),
+                    }),
+                    EmitterSource::PgNotify { .. } => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        infos.sort_by(|a, b| a.name.cmp(&b.name));
+        infos
+    }
+
+    /// Runs the named timer emitter once, outside its schedule.
+    pub(crate) async fn run_once(&self, site: &Site, name: &str) -> Result<(), EmitterError> {
+        let (handler, target) = self
+            .sources
+            .values()
+            .find_map(|emitter| match &emitter.source {
+                EmitterSource::Cron { handler, .. } | EmitterSource::Periodic { handler, .. }
+                    if handler.inspect().name == name =>
+                {
+                    Some((handler.clone(), emitter.target))
+                }
+                _ => None,
+            })
+            .ok_or_else(|| EmitterError::UnknownSchedule(name.to_string()))?;
+        let ctx = EmitterContext {
+            site: site.clone(),
+            payload: DataBox::new(String::new()),
+            iter_count: 0,
+            last_time: None,
+        };
+        let payload = handler
+            .call(ctx)
+            .await
+            .map_err(|err| EmitterError::OtherError(Box::new(err)))?;
+        site.dispatch_payload(payload, target)
+            .await
+            .map_err(|err| EmitterError::OtherError(Box::new(err)))
+    }
+
     async fn dispatch(&self, site: &Site, payload: DataBox, target: EmitTarget) {
         if let Err(err) = site.dispatch_payload(payload, target).await {
             tracing::error!(target = ?target, "Error dispatching emitter payload: {}", err);
@@ -9,6 +9,7 @@ use axum::{
 };
 use serde::Serialize;
 use smallvec::SmallVec;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
@@ -119,6 +120,7 @@ pub struct ErrorConf {
     html_handler: Option<HttpErrorViewHandler>,
     command_renderer: Option<CommandErrorRenderer>,
     http_mode: HttpErrorRenderMode,
+    log_validation: bool,
 }
 
 impl std::fmt::Debug for ErrorConf {
@@ -138,6 +140,7 @@ impl std::fmt::Debug for ErrorConf {
                 &self.command_renderer.as_ref().map(|_| "<custom>"),
             )
             .field("http_mode", &self.http_mode)
+            .field("log_validation", &self.log_validation)
             .finish()
     }
 }
@@ -183,7 +186,23 @@ impl ErrorConf {
         self
     }
 
-    pub(crate) async fn render(&self, ctx: ErrorRequestContext, report: ErrorReport) -> Response {
+    /// Emits a `vyuh::validation` tracing event for every rejected request.
+    pub fn log_validation(mut self, enabled: bool) -> Self {
+        self.log_validation = enabled;
+        self
+    }
+
+    /// `route_not_found` marks the router fallback's `404`, the only error
+    /// that gets the built-in HTML page when no HTML renderer is configured.
+    pub(crate) async fn render(
+        &self,
+        ctx: ErrorRequestContext,
+        report: ErrorReport,
+        route_not_found: bool,
+    ) -> Response {
+        if self.log_validation && report.source == ErrorSourceKind::Validation {
+            log_validation_failure(&ctx, &report);
+        }
         if let Some(handler) = &self.handler {
             return handler(ctx, report).await;
         }
@@ -194,6 +213,9 @@ impl ErrorConf {
                 if let Some(handler) = &self.html_handler {
                     return handler(ctx, view).await;
                 }
+                if route_not_found {
+                    return default_html_error(report, view);
+                }
             }
             ErrorRenderTarget::Json => {
                 if let Some(handler) = &self.json_handler {
@@ -232,6 +254,82 @@ impl ErrorConf {
     }
 }
 
+/// Minimal HTML `404` page for browsers when no HTML renderer is configured.
+fn default_html_error(report: ErrorReport, view: ErrorView) -> Response {
+    let reason = view.status.canonical_reason().unwrap_or("Error");
+    let title = format!("{} {reason}", view.status.as_u16());
+    let body = format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title></head>\n\
+         <body><h1>{title}</h1><p>{}</p></body></html>\n",
+        minijinja::HtmlEscape(&view.message),
+    );
+    let mut response = (view.status, axum::response::Html(body)).into_response();
+    response.extensions_mut().insert(report);
+    response
+}
+
+/// Response extension set by [`not_found_fallback`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RouteNotFound;
+
+/// Router fallback: a negotiated `404` for paths no route matches.
+pub(crate) async fn not_found_fallback() -> Response {
+    let mut response = ErrorReport::new(
+        StatusCode::NOT_FOUND,
+        ErrorSourceKind::Framework,
+        "NOT_FOUND",
+        "Not found",
+    )
+    .into_response();
+    response.extensions_mut().insert(RouteNotFound);
+    response
+}
+
+fn log_validation_failure(ctx: &ErrorRequestContext, report: &ErrorReport) {
+    let mut counts = BTreeMap::new();
+    if let Some(errors) = &report.errors {
+        collect_error_codes(errors, "", &mut counts);
+    }
+    let fields = counts
+        .iter()
+        .map(|(key, count)| format!("{}={}", key, count))
+        .collect::<Vec<_>>()
+        .join(",");
+    tracing::info!(
+        target: "vyuh::validation",
+        method = %ctx.method,
+        path = %ctx.path,
+        issues = counts.values().sum::<usize>(),
+        fields = %fields,
+        "request validation failed"
+    );
+}
+
+/// Counts `field:code` pairs in the nested error tree built by `to_nested_errors`.
+fn collect_error_codes(value: &serde_json::Value, path: &str, out: &mut BTreeMap<String, usize>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_error_codes(child, &child_path, out);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                match item.get("code").and_then(|code| code.as_str()) {
+                    Some(code) => *out.entry(format!("{}:{}", path, code)).or_default() += 1,
+                    None => collect_error_codes(item, &format!("{}[{}]", path, idx), out),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 impl ErrorReport {
     pub fn new(
         status: StatusCode,
@@ -758,6 +856,16 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<crate::SiteError> for Error {
+    fn from(err: crate::SiteError) -> Self {
+        match err {
+            crate::SiteError::StorageError(err) => Self::from(err),
+            crate::SiteError::DatabaseError(err) => Self::from(err),
+            other => Self::other(other),
+        }
+    }
+}
+
 impl From<crate::tasks::TaskError> for Error {
     fn from(err: crate::tasks::TaskError) -> Self {
         Self::other(err)
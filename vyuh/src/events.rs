@@ -0,0 +1,125 @@
+//! Typed in-process broadcast bus.
+//!
+//! Unlike signals, events have no registered handlers: any code holding the
+//! bus can subscribe and receive every event of a type published after that.
+//! Slow subscribers skip events once they fall `capacity` events behind.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tokio::sync::broadcast;
+
+pub use tokio::sync::broadcast::error::RecvError;
+
+/// Default number of buffered events per type.
+pub const DEFAULT_EVENT_CAPACITY: usize = 256;
+
+/// Site-scoped broadcast bus keyed by event type.
+#[derive(Clone)]
+pub struct EventBus {
+    capacity: usize,
+    senders: Arc<Mutex<HashMap<TypeId, Box<dyn Any + Send + Sync>>>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_CAPACITY)
+    }
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("capacity", &self.capacity)
+            .field("types", &self.senders.lock().len())
+            .finish()
+    }
+}
+
+impl EventBus {
+    /// Creates a bus buffering up to `capacity` events per type.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            senders: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Publishes `event` and returns how many subscribers will receive it.
+    pub fn publish<T>(&self, event: T) -> usize
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.sender::<T>().send(event).unwrap_or(0)
+    }
+
+    /// Subscribes to events of type `T` published from now on.
+    pub fn subscribe<T>(&self) -> EventReceiver<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        EventReceiver {
+            inner: self.sender::<T>().subscribe(),
+        }
+    }
+
+    fn sender<T>(&self) -> broadcast::Sender<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let mut senders = self.senders.lock();
+        if let Some(sender) = senders
+            .get(&TypeId::of::<T>())
+            .and_then(|sender| sender.downcast_ref::<broadcast::Sender<T>>())
+        {
+            return sender.clone();
+        }
+        let (sender, _) = broadcast::channel::<T>(self.capacity);
+        senders.insert(TypeId::of::<T>(), Box::new(sender.clone()));
+        sender
+    }
+}
+
+/// Receiving half of an [`EventBus`] subscription.
+pub struct EventReceiver<T> {
+    inner: broadcast::Receiver<T>,
+}
+
+impl<T: Clone> EventReceiver<T> {
+    /// Waits for the next event. Lagged subscribers get `RecvError::Lagged`.
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        self.inner.recv().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct NoteSaved(u32);
+
+    #[tokio::test]
+    async fn publish_reaches_every_subscriber() {
+        let bus = EventBus::default();
+        let mut first = bus.subscribe::<NoteSaved>();
+        let mut second = bus.subscribe::<NoteSaved>();
+
+        assert_eq!(bus.publish(NoteSaved(7)), 2);
+
+        assert_eq!(first.recv().await.ok(), Some(NoteSaved(7)));
+        assert_eq!(second.recv().await.ok(), Some(NoteSaved(7)));
+    }
+
+    #[tokio::test]
+    async fn publish_without_subscribers_is_dropped() {
+        let bus = EventBus::default();
+        assert_eq!(bus.publish(NoteSaved(1)), 0);
+
+        let mut late = bus.subscribe::<NoteSaved>();
+        bus.publish(NoteSaved(2));
+        assert_eq!(late.recv().await.ok(), Some(NoteSaved(2)));
+    }
+}
@@ -13,12 +13,23 @@ pub enum FileStorageError {
     #[error("file storage path escapes storage root: {0}")]
     EscapesRoot(PathBuf),
 
+    #[error("upload exceeds {limit} bytes")]
+    TooLarge { limit: u64 },
+
+    #[error("upload stream failed: {0}")]
+    Stream(String),
+
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 }
 
 impl From<FileStorageError> for crate::Error {
     fn from(value: FileStorageError) -> Self {
-        crate::Error::other(value)
+        match value {
+            FileStorageError::InvalidName { .. }
+            | FileStorageError::TooLarge { .. }
+            | FileStorageError::Stream(_) => crate::Error::bad_request(value.to_string()),
+            other => crate::Error::other(other),
+        }
     }
 }
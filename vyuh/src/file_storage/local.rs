@@ -1,6 +1,7 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::pin::Pin;
 
+use axum_extra::extract::multipart::Field;
 use tokio::io::AsyncWriteExt;
 use uuid::Uuid;
 
@@ -53,6 +54,54 @@ impl LocalStorage {
         <Self as StorageBackend>::url(self, name)
     }
 
+    /// Streams a multipart field into the storage root without buffering it.
+    ///
+    /// The client filename must be a single plain path segment; anything with
+    /// separators or `..` is rejected. The stored name is prefixed with a
+    /// UUID so uploads never overwrite each other. A partial file is removed
+    /// when the stream fails or grows past `max_bytes`.
+    pub async fn save_field(
+        &self,
+        mut field: Field,
+        max_bytes: u64,
+    ) -> Result<SavedFile, FileStorageError> {
+        let name = StorageName::new(format!(
+            "{}-{}",
+            Uuid::now_v7(),
+            upload_file_name(field.file_name())?
+        ))?;
+        tokio::fs::create_dir_all(&self.root).await?;
+        let path = name.join_to(&self.root)?;
+        let mut out = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await?;
+        let written = async {
+            let mut size = 0u64;
+            while let Some(chunk) = field
+                .chunk()
+                .await
+                .map_err(|err| FileStorageError::Stream(err.body_text()))?
+            {
+                size += chunk.len() as u64;
+                if size > max_bytes {
+                    return Err(FileStorageError::TooLarge { limit: max_bytes });
+                }
+                out.write_all(&chunk).await?;
+            }
+            out.flush().await?;
+            Ok(())
+        }
+        .await;
+        if let Err(err) = written {
+            drop(out);
+            let _ = tokio::fs::remove_file(&path).await;
+            return Err(err);
+        }
+        Ok(SavedFile::new(name, path, self.base_url.clone()))
+    }
+
     fn generated_name(&self, file: &UploadedFile) -> Result<StorageName, FileStorageError> {
         let extension = file
             .file_name()
@@ -92,6 +141,29 @@ impl LocalStorage {
     }
 }
 
+/// Accepts a client filename only if it is one plain segment, then replaces
+/// anything outside `[A-Za-z0-9._-]` so the stored name stays portable.
+fn upload_file_name(name: Option<&str>) -> Result<String, FileStorageError> {
+    let name = name.unwrap_or_default();
+    let invalid = |reason: &str| FileStorageError::InvalidName {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    };
+    let mut components = Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) if !name.contains(['/', '\\']) => {}
+        (None, _) => return Err(invalid("cannot be empty")),
+        _ => return Err(invalid("must be a plain file name")),
+    }
+    Ok(name
+        .chars()
+        .map(|ch| match ch {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => ch,
+            _ => '_',
+        })
+        .collect())
+}
+
 impl StorageBackend for LocalStorage {
     fn save<'a>(
         &'a self,
@@ -0,0 +1,191 @@
+//! One-time messages carried across a redirect in a signed cookie.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use axum::extract::{FromRequestParts, Request};
+use axum::http::{HeaderValue, header, request::Parts};
+use axum::middleware::Next;
+use axum::response::{IntoResponseParts, Response, ResponseParts};
+use axum_extra::extract::CookieJar;
+use axum_extra::extract::cookie::{Cookie, SameSite};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Site,
+    auth::AuthError,
+    callables::{ArgPart, IntoArgPart, IntoReturnPart, ReturnPart},
+};
+
+/// Cookie holding the signed messages.
+pub const FLASH_COOKIE: &str = "vyuh_flash";
+
+/// Unread messages expire after this long.
+const FLASH_TTL_SECS: i64 = 300;
+
+/// JWT `typ` for flash payloads; the authenticator rejects it as a login token.
+const FLASH_TYP: &str = "vyuh-flash";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FlashLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlashMessage {
+    pub level: FlashLevel,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FlashClaims {
+    flash: Vec<FlashMessage>,
+    exp: i64,
+}
+
+/// Flash messages for the current request.
+///
+/// Extracting it reads the messages set by the previous response and clears
+/// the cookie. Add messages and return it beside the body to show them on the
+/// next request: `(flash.success("Saved"), Redirect::to("/orders"))`.
+/// The payload is signed with the site's JWT key, so clients cannot forge it.
+#[derive(Debug)]
+pub struct Flash {
+    site: Site,
+    incoming: Vec<FlashMessage>,
+    outgoing: Vec<FlashMessage>,
+}
+
+impl Flash {
+    /// Messages set by the previous response.
+    pub fn messages(&self) -> &[FlashMessage] {
+        &self.incoming
+    }
+
+    /// Queues a message for the next request.
+    pub fn push(mut self, level: FlashLevel, text: impl Into<String>) -> Self {
+        self.outgoing.push(FlashMessage {
+            level,
+            text: text.into(),
+        });
+        self
+    }
+
+    pub fn info(self, text: impl Into<String>) -> Self {
+        self.push(FlashLevel::Info, text)
+    }
+
+    pub fn success(self, text: impl Into<String>) -> Self {
+        self.push(FlashLevel::Success, text)
+    }
+
+    pub fn warning(self, text: impl Into<String>) -> Self {
+        self.push(FlashLevel::Warning, text)
+    }
+
+    pub fn error(self, text: impl Into<String>) -> Self {
+        self.push(FlashLevel::Error, text)
+    }
+}
+
+/// Set by `flash_middleware` when the request carries a flash cookie, and
+/// flipped once a handler has extracted it.
+#[derive(Clone)]
+struct FlashRead(Arc<AtomicBool>);
+
+impl FromRequestParts<Site> for Flash {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, site: &Site) -> Result<Self, Self::Rejection> {
+        let jar = CookieJar::from_headers(&parts.headers);
+        let incoming = jar
+            .get(FLASH_COOKIE)
+            .and_then(|cookie| {
+                site.auth()
+                    .verify_claims::<FlashClaims>(FLASH_TYP, cookie.value())
+                    .ok()
+            })
+            .map(|claims| claims.flash)
+            .unwrap_or_default();
+        if let Some(read) = parts.extensions.get::<FlashRead>() {
+            read.0.store(true, Ordering::Relaxed);
+        }
+        Ok(Self {
+            site: site.clone(),
+            incoming,
+            outgoing: Vec::new(),
+        })
+    }
+}
+
+impl IntoArgPart for Flash {
+    fn into_arg_part() -> ArgPart {
+        ArgPart::Ignore
+    }
+}
+
+impl IntoResponseParts for Flash {
+    type Error = AuthError;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        if self.outgoing.is_empty() {
+            return Ok(res);
+        }
+        let claims = FlashClaims {
+            flash: self.outgoing,
+            exp: chrono::Utc::now().timestamp() + FLASH_TTL_SECS,
+        };
+        let token = self.site.auth().sign_claims(FLASH_TYP, &claims)?;
+        let cookie = flash_cookie(token, FLASH_TTL_SECS);
+        let value = HeaderValue::try_from(cookie).map_err(|_| AuthError::InvalidToken)?;
+        res.headers_mut().append(header::SET_COOKIE, value);
+        Ok(res)
+    }
+}
+
+impl<T: IntoReturnPart> IntoReturnPart for (Flash, T) {
+    fn into_return_part() -> ReturnPart {
+        T::into_return_part()
+    }
+}
+
+fn flash_cookie(value: String, max_age_secs: i64) -> String {
+    Cookie::build((FLASH_COOKIE, value))
+        .path("/")
+        .max_age(time::Duration::seconds(max_age_secs))
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .build()
+        .to_string()
+}
+
+/// Expires the flash cookie after a handler read it, unless the response
+/// already sets a fresh one.
+pub(crate) async fn flash_middleware(mut req: Request, next: Next) -> Response {
+    let jar = CookieJar::from_headers(req.headers());
+    if jar.get(FLASH_COOKIE).is_none() {
+        return next.run(req).await;
+    }
+    let read = FlashRead(Arc::new(AtomicBool::new(false)));
+    req.extensions_mut().insert(read.clone());
+    let mut response = next.run(req).await;
+    let replaced = response
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.starts_with(&format!("{FLASH_COOKIE}=")));
+    if read.0.load(Ordering::Relaxed)
+        && !replaced
+        && let Ok(value) = HeaderValue::try_from(flash_cookie(String::new(), 0))
+    {
+        response.headers_mut().append(header::SET_COOKIE, value);
+    }
+    response
+}
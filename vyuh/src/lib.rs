@@ -12,13 +12,17 @@ pub mod channels;
 pub mod commands;
 pub mod console;
 pub mod db;
+pub mod email;
 pub mod embed;
 pub mod emitters;
 pub mod errors;
+pub mod events;
 pub mod file_storage;
+pub mod flash;
 pub mod logging;
 pub mod middlewares;
 pub(crate) mod notifiers;
+pub mod outbox;
 pub mod prelude;
 pub(crate) mod roles;
 pub(crate) mod schedulers;
@@ -45,6 +49,7 @@ pub use errors::{
 pub use file_storage::{
     FileStorageError, LocalStorage, SavedFile, StorageBackend, StorageName, UploadConf,
 };
+pub use rust_decimal;
 pub use schemars;
 pub use serde;
 pub use site::{Site, SiteConfig, SiteError};
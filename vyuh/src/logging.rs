@@ -5,7 +5,9 @@ use std::{
 };
 use thiserror::Error;
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{EnvFilter, Layer, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry, fmt, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
 
 #[derive(Debug, Error)]
 pub enum LoggingError {
@@ -39,6 +41,9 @@ pub enum LoggingError {
         value: String,
         source: tracing_subscriber::filter::ParseError,
     },
+
+    #[error("Failed to reload log filter for rule '{rule}': {source}")]
+    Reload { rule: String, source: reload::Error },
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -294,17 +299,49 @@ impl LoggingConf {
     }
 }
 
+type FilterHandle = reload::Handle<EnvFilter, Registry>;
+
 /// Returned guard must be kept alive until shutdown
 pub struct LoggingGuard {
     _file_guards: Vec<WorkerGuard>,
+    filters: Vec<(String, FilterHandle)>,
 }
 
 impl LoggingGuard {
     pub(crate) fn noop() -> Self {
         Self {
             _file_guards: Vec::new(),
+            filters: Vec::new(),
         }
     }
+
+    /// Re-resolves the filter of every installed rule from `conf` and the
+    /// environment. Rules added to `conf`, and rules that were off at
+    /// startup, have no layer and need a restart. No filter is swapped
+    /// unless all of them parse.
+    pub(crate) fn reload_filters(&self, conf: &LoggingConf) -> Result<(), LoggingError> {
+        let env_prefix = conf.resolved_env_prefix();
+        let global_filter = std::env::var(env_prefix).ok();
+        let mut updates = Vec::new();
+        for (name, handle) in &self.filters {
+            let Some(rule) = conf.rules.iter().find(|rule| &rule.name == name) else {
+                continue;
+            };
+            let filter = rule
+                .build_filter(env_prefix, global_filter.as_ref())?
+                .unwrap_or_else(|| EnvFilter::new("off"));
+            updates.push((name, handle, filter));
+        }
+        for (name, handle, filter) in updates {
+            handle
+                .reload(filter)
+                .map_err(|source| LoggingError::Reload {
+                    rule: name.clone(),
+                    source,
+                })?;
+        }
+        Ok(())
+    }
 }
 
 fn resolve_log_dir(project_dir: &Path, dir: &str) -> PathBuf {
@@ -321,9 +358,7 @@ pub(crate) fn init_tracing(
     conf: &LoggingConf,
 ) -> Result<LoggingGuard, LoggingError> {
     if conf.rules.is_empty() {
-        return Ok(LoggingGuard {
-            _file_guards: vec![],
-        });
+        return Ok(LoggingGuard::noop());
     }
 
     conf.validate()?;
@@ -332,6 +367,7 @@ pub(crate) fn init_tracing(
     let global_filter = std::env::var(env_prefix).ok();
 
     let mut guards = Vec::new();
+    let mut filters = Vec::new();
     type BoxedLayer = Box<dyn Layer<tracing_subscriber::Registry> + Send + Sync + 'static>;
 
     let mut layers: Vec<BoxedLayer> = Vec::new();
@@ -340,6 +376,8 @@ pub(crate) fn init_tracing(
         let Some(filter) = rule.build_filter(env_prefix, global_filter.as_ref())? else {
             continue;
         };
+        let (filter, handle) = reload::Layer::new(filter);
+        filters.push((rule.name.clone(), handle));
 
         match &rule.sink {
             LogSink::File { dir, rotation } => {
@@ -408,15 +446,14 @@ pub(crate) fn init_tracing(
     }
 
     if layers.is_empty() {
-        return Ok(LoggingGuard {
-            _file_guards: vec![],
-        });
+        return Ok(LoggingGuard::noop());
     }
 
     tracing_subscriber::registry().with(layers).try_init()?;
 
     Ok(LoggingGuard {
         _file_guards: guards,
+        filters,
     })
 }
 
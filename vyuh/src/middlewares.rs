@@ -1,15 +1,33 @@
-use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
 
 use axum::{
+    body::Body,
     extract::{Request, State},
     http::{HeaderName, HeaderValue, Method, StatusCode, header},
     middleware::Next,
     response::{IntoResponse, Redirect, Response},
 };
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
+use tower_http::compression::{
+    CompressionLayer,
+    predicate::{DefaultPredicate, Predicate},
+};
+use tower_http::cors::{self, AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+use tracing::Instrument;
 
 use crate::{
     callables::{Operation, OperationKind, ReturnPart},
+    conf::ConfError,
     errors::{ErrorReport, ErrorSourceKind},
     routes::Methods,
 };
@@ -27,6 +45,20 @@ pub struct HttpConf {
     pub security_headers: SecurityHeadersConf,
     #[serde(default)]
     pub shutdown: ShutdownConf,
+    #[serde(default)]
+    pub retry: RetryConf,
+    #[serde(default)]
+    pub maintenance: MaintenanceConf,
+    #[serde(default)]
+    pub health: crate::routes::HealthConf,
+    #[serde(default)]
+    pub rate_limit: crate::routes::RateLimitConf,
+    #[serde(default)]
+    pub csrf: crate::routes::CsrfConf,
+    #[serde(default)]
+    pub route_inspector: crate::routes::RouteInspectorConf,
+    #[serde(default)]
+    pub allow: crate::routes::AllowConf,
 }
 
 impl Default for HttpConf {
@@ -42,6 +74,13 @@ impl Default for HttpConf {
             body_limit: BodyLimitConf::default(),
             security_headers: SecurityHeadersConf::default(),
             shutdown: ShutdownConf::default(),
+            retry: RetryConf::default(),
+            maintenance: MaintenanceConf::default(),
+            health: crate::routes::HealthConf::default(),
+            rate_limit: crate::routes::RateLimitConf::default(),
+            csrf: crate::routes::CsrfConf::default(),
+            route_inspector: crate::routes::RouteInspectorConf::default(),
+            allow: crate::routes::AllowConf::default(),
         }
     }
 }
@@ -117,10 +156,62 @@ impl Default for CompressionConf {
     }
 }
 
+impl CompressionConf {
+    /// gzip, br, deflate, or zstd, negotiated from `Accept-Encoding`.
+    pub(crate) fn layer(&self) -> CompressionLayer<CompressiblePredicate> {
+        CompressionLayer::new().compress_when(CompressiblePredicate::default())
+    }
+}
+
+/// Content types that gain nothing from a second compression pass.
+const PRECOMPRESSED_TYPES: &[&str] = &[
+    "application/gzip",
+    "application/x-gzip",
+    "application/zip",
+    "application/zstd",
+    "application/x-7z-compressed",
+    "application/x-bzip2",
+    "font/woff",
+    "audio/",
+    "video/",
+];
+
+/// tower-http's default predicate (skips tiny bodies, images, gRPC, and
+/// `text/event-stream`) plus already-compressed formats. Responses that
+/// already carry `Content-Encoding` are never re-encoded.
+#[derive(Clone, Default)]
+pub(crate) struct CompressiblePredicate(DefaultPredicate);
+
+impl Predicate for CompressiblePredicate {
+    fn should_compress<B: axum::body::HttpBody>(&self, response: &Response<B>) -> bool {
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        !PRECOMPRESSED_TYPES
+            .iter()
+            .any(|prefix| content_type.starts_with(prefix))
+            && self.0.should_compress(response)
+    }
+}
+
+/// Cross-origin settings. `permissive` allows everything and ignores the
+/// lists; turn it off to send only the configured origins, methods, and headers.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CorsConf {
     pub enabled: bool,
     pub permissive: bool,
+    /// Exact origins such as `https://app.example.com`, or `*` for any.
+    pub allow_origins: Vec<String>,
+    /// Method names, or `*` for any.
+    pub allow_methods: Vec<String>,
+    /// Request header names, or `*` for any.
+    pub allow_headers: Vec<String>,
+    pub allow_credentials: bool,
+    /// How long browsers may cache a preflight response.
+    pub max_age_secs: Option<u64>,
 }
 
 impl Default for CorsConf {
@@ -128,10 +219,111 @@ impl Default for CorsConf {
         Self {
             enabled: false,
             permissive: true,
+            allow_origins: Vec::new(),
+            allow_methods: ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE"]
+                .map(String::from)
+                .to_vec(),
+            allow_headers: vec!["content-type".into(), "authorization".into()],
+            allow_credentials: false,
+            max_age_secs: None,
         }
     }
 }
 
+impl CorsConf {
+    /// Builds the layer. Entries that fail `validate` are skipped.
+    pub(crate) fn layer(&self) -> CorsLayer {
+        if self.permissive {
+            return CorsLayer::permissive();
+        }
+        let origins: AllowOrigin = if is_wildcard(&self.allow_origins) {
+            cors::Any.into()
+        } else {
+            parse_all(&self.allow_origins, |o| HeaderValue::from_str(o).ok()).into()
+        };
+        let methods: AllowMethods = if is_wildcard(&self.allow_methods) {
+            cors::Any.into()
+        } else {
+            parse_all(&self.allow_methods, |m| {
+                Method::from_bytes(m.as_bytes()).ok()
+            })
+            .into()
+        };
+        let headers: AllowHeaders = if is_wildcard(&self.allow_headers) {
+            cors::Any.into()
+        } else {
+            parse_all(&self.allow_headers, |h| {
+                HeaderName::from_bytes(h.as_bytes()).ok()
+            })
+            .into()
+        };
+        let layer = CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .allow_credentials(self.allow_credentials);
+        match self.max_age_secs {
+            Some(secs) => layer.max_age(Duration::from_secs(secs)),
+            None => layer,
+        }
+    }
+
+    pub(crate) fn validate(&self, errors: &mut Vec<ConfError>) {
+        if !self.enabled || self.permissive {
+            return;
+        }
+        if self.allow_origins.is_empty() {
+            errors.push(cors_error(
+                "allow_origins",
+                "must not be empty when permissive is off".into(),
+            ));
+        }
+        self.check_list(errors, "allow_origins", &self.allow_origins, |o| {
+            HeaderValue::from_str(o).is_ok() && o.contains("://")
+        });
+        self.check_list(errors, "allow_methods", &self.allow_methods, |m| {
+            Method::from_bytes(m.as_bytes()).is_ok()
+        });
+        self.check_list(errors, "allow_headers", &self.allow_headers, |h| {
+            HeaderName::from_bytes(h.as_bytes()).is_ok()
+        });
+    }
+
+    fn check_list(
+        &self,
+        errors: &mut Vec<ConfError>,
+        field: &str,
+        values: &[String],
+        is_valid: impl Fn(&str) -> bool,
+    ) {
+        if self.allow_credentials && is_wildcard(values) {
+            errors.push(cors_error(
+                field,
+                "'*' cannot be combined with allow_credentials; browsers reject it".into(),
+            ));
+        }
+        for value in values.iter().filter(|v| *v != "*" && !is_valid(v)) {
+            errors.push(cors_error(field, format!("'{value}' is not valid")));
+        }
+    }
+}
+
+fn cors_error(field: &str, reason: String) -> ConfError {
+    ConfError::InvalidValue {
+        field: format!("http.cors.{field}"),
+        reason,
+        expected: Some("an explicit list, or '*' without credentials".into()),
+    }
+}
+
+fn is_wildcard(values: &[String]) -> bool {
+    values.iter().any(|value| value == "*")
+}
+
+fn parse_all<T>(values: &[String], parse: impl Fn(&str) -> Option<T>) -> Vec<T> {
+    values.iter().filter_map(|value| parse(value)).collect()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeoutConf {
     pub enabled: bool,
@@ -199,6 +391,84 @@ fn default_shutdown_grace_period_ms() -> u64 {
     10_000
 }
 
+/// Server-side retry of safe requests that fail with a transient database error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryConf {
+    pub enabled: bool,
+    pub max_retries: u32,
+    pub backoff_ms: u64,
+    pub budget_ms: u64,
+    pub max_body_bytes: usize,
+}
+
+impl Default for RetryConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_retries: 1,
+            backoff_ms: 25,
+            budget_ms: 2_000,
+            max_body_bytes: 64 * 1024,
+        }
+    }
+}
+
+/// Answers every request outside `allow` with `503` while enabled. The flag
+/// can also be flipped at runtime with `Site::set_maintenance`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceConf {
+    pub enabled: bool,
+    /// Path prefixes that stay reachable, matched on segment boundaries.
+    pub allow: Vec<String>,
+    pub message: String,
+    pub retry_after_secs: Option<u64>,
+}
+
+impl Default for MaintenanceConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allow: vec!["/healthz".into()],
+            message: "The site is down for maintenance.".into(),
+            retry_after_secs: None,
+        }
+    }
+}
+
+/// Runtime maintenance flag shared by the site and its middleware.
+#[derive(Debug)]
+pub(crate) struct MaintenanceSwitch {
+    enabled: AtomicBool,
+    conf: MaintenanceConf,
+}
+
+impl MaintenanceSwitch {
+    pub(crate) fn new(conf: MaintenanceConf) -> Self {
+        Self {
+            enabled: AtomicBool::new(conf.enabled),
+            conf,
+        }
+    }
+
+    pub(crate) fn set(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    fn allows(&self, path: &str) -> bool {
+        self.conf.allow.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum SlashAction {
     Rewrite(String),
@@ -375,6 +645,39 @@ fn rewrite_uri(uri: &axum::http::Uri, target_path: &str) -> Option<axum::http::U
     axum::http::Uri::from_parts(parts).ok()
 }
 
+/// The current request's id, as read or generated by the request-id middleware.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl axum::extract::FromRequestParts<crate::Site> for RequestId {
+    type Rejection = ErrorReport;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        _site: &crate::Site,
+    ) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<RequestId>().cloned().ok_or_else(|| {
+            ErrorReport::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorSourceKind::Framework,
+                "request_id_disabled",
+                "Request ids are disabled in http.request_id.",
+            )
+        })
+    }
+}
+
+impl crate::callables::IntoArgPart for RequestId {
+    fn into_arg_part() -> crate::callables::ArgPart {
+        crate::callables::ArgPart::Ignore
+    }
+}
+
+/// Longest client-supplied request id that is trusted as-is.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Reuses the incoming request id or generates one, runs the request inside a
+/// `request` span carrying it, and echoes it in the response.
 pub(crate) async fn request_id_middleware(
     State(conf): State<RequestIdConf>,
     mut req: Request,
@@ -385,12 +688,25 @@ pub(crate) async fn request_id_middleware(
     let request_id = req
         .headers()
         .get(&header_name)
-        .cloned()
-        .unwrap_or_else(|| HeaderValue::from_str(&uuid::Uuid::now_v7().to_string()).unwrap());
-    req.headers_mut()
-        .insert(header_name.clone(), request_id.clone());
-    let mut response = next.run(req).await;
-    response.headers_mut().insert(header_name, request_id);
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LEN)
+        .map(str::to_owned)
+        .unwrap_or_else(|| uuid::Uuid::now_v7().to_string());
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.uri().path(),
+    );
+    let value = HeaderValue::from_str(&request_id).ok();
+    req.extensions_mut().insert(RequestId(request_id));
+    if let Some(value) = &value {
+        req.headers_mut().insert(header_name.clone(), value.clone());
+    }
+    let mut response = next.run(req).instrument(span).await;
+    if let Some(value) = value {
+        response.headers_mut().insert(header_name, value);
+    }
     response
 }
 
@@ -420,21 +736,216 @@ pub(crate) async fn body_limit_middleware(
 
 pub(crate) async fn timeout_middleware(
     State(conf): State<TimeoutConf>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let deadline = RequestDeadline::new(conf.timeout_ms);
+    req.extensions_mut().insert(deadline.clone());
+    let response = next.run(req);
+    tokio::pin!(response);
+    loop {
+        let (at, _) = deadline.get();
+        tokio::select! {
+            response = &mut response => return response,
+            _ = tokio::time::sleep_until(at.into()) => {
+                // A route-level `RouteTimeout` may have moved the deadline.
+                let (at, timeout_ms) = deadline.get();
+                if at <= Instant::now() {
+                    return timeout_response(timeout_ms);
+                }
+            }
+        }
+    }
+}
+
+fn timeout_response(timeout_ms: u64) -> Response {
+    ErrorReport::new(
+        StatusCode::GATEWAY_TIMEOUT,
+        ErrorSourceKind::Framework,
+        "request_timeout",
+        format!("Request exceeded {} ms.", timeout_ms),
+    )
+    .into_response()
+}
+
+/// The site-wide timeout's deadline, shared so a route can replace it.
+#[derive(Clone)]
+struct RequestDeadline(Arc<parking_lot::Mutex<(Instant, u64)>>);
+
+impl RequestDeadline {
+    fn new(timeout_ms: u64) -> Self {
+        let at = Instant::now() + Duration::from_millis(timeout_ms);
+        Self(Arc::new(parking_lot::Mutex::new((at, timeout_ms))))
+    }
+
+    fn get(&self) -> (Instant, u64) {
+        *self.0.lock()
+    }
+
+    fn reset(&self, timeout_ms: u64) {
+        *self.0.lock() = (
+            Instant::now() + Duration::from_millis(timeout_ms),
+            timeout_ms,
+        );
+    }
+}
+
+/// Per-route timeout that replaces the site-wide `HttpConf::timeout`, longer
+/// or shorter, and answers 504 on expiry.
+///
+/// Set it with `#[route(timeout_ms = 120_000)]` or `BundlePart::layer`. The
+/// limit counts from when the route is matched.
+#[derive(Debug, Clone, Copy)]
+pub struct RouteTimeout {
+    timeout_ms: u64,
+}
+
+impl RouteTimeout {
+    pub fn from_millis(timeout_ms: u64) -> Self {
+        Self { timeout_ms }
+    }
+}
+
+impl<S> tower::Layer<S> for RouteTimeout {
+    type Service = RouteTimeoutService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RouteTimeoutService {
+            inner,
+            timeout_ms: self.timeout_ms,
+        }
+    }
+}
+
+impl crate::routes::Middleware for RouteTimeout {
+    type Layer = Self;
+
+    fn into_layer(self) -> Self::Layer {
+        self
+    }
+}
+
+/// Service produced by [`RouteTimeout`].
+#[derive(Debug, Clone)]
+pub struct RouteTimeoutService<S> {
+    inner: S,
+    timeout_ms: u64,
+}
+
+impl<S> tower::Service<Request> for RouteTimeoutService<S>
+where
+    S: tower::Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let timeout_ms = self.timeout_ms;
+        // Take the service that was polled ready; leave a fresh clone behind.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            // The site-wide middleware enforces the moved deadline itself.
+            if let Some(deadline) = req.extensions().get::<RequestDeadline>() {
+                deadline.reset(timeout_ms);
+                return inner.call(req).await;
+            }
+            let limit = Duration::from_millis(timeout_ms);
+            match tokio::time::timeout(limit, inner.call(req)).await {
+                Ok(response) => response,
+                Err(_) => Ok(timeout_response(timeout_ms)),
+            }
+        })
+    }
+}
+
+pub(crate) async fn maintenance_middleware(
+    State(switch): State<Arc<MaintenanceSwitch>>,
     req: Request,
     next: Next,
 ) -> Response {
-    match tokio::time::timeout(Duration::from_millis(conf.timeout_ms), next.run(req)).await {
-        Ok(response) => response,
-        Err(_) => ErrorReport::new(
-            StatusCode::GATEWAY_TIMEOUT,
-            ErrorSourceKind::Framework,
-            "request_timeout",
-            format!("Request exceeded {} ms.", conf.timeout_ms),
+    if !switch.is_enabled() || switch.allows(req.uri().path()) {
+        return next.run(req).await;
+    }
+    let mut response = ErrorReport::new(
+        StatusCode::SERVICE_UNAVAILABLE,
+        ErrorSourceKind::Framework,
+        "maintenance",
+        switch.conf.message.clone(),
+    )
+    .into_response();
+    if let Some(secs) = switch.conf.retry_after_secs {
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+    }
+    response
+}
+
+pub(crate) async fn retry_middleware(
+    State(conf): State<RetryConf>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if !is_safe_method(req.method()) {
+        return next.run(req).await;
+    }
+    let (parts, body) = req.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, conf.max_body_bytes).await else {
+        return ErrorReport::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorSourceKind::Parse,
+            "request_body_too_large",
+            format!("Request body exceeds {} bytes.", conf.max_body_bytes),
         )
-        .into_response(),
+        .into_response();
+    };
+
+    let started = Instant::now();
+    let budget = Duration::from_millis(conf.budget_ms);
+    let backoff = Duration::from_millis(conf.backoff_ms);
+    let mut attempt = 0;
+    loop {
+        let req = Request::from_parts(parts.clone(), Body::from(bytes.clone()));
+        let response = next.clone().run(req).await;
+        if attempt >= conf.max_retries
+            || !is_transient_failure(&response)
+            || started.elapsed() + backoff >= budget
+        {
+            return response;
+        }
+        attempt += 1;
+        tracing::debug!(
+            method = %parts.method,
+            path = %parts.uri.path(),
+            attempt,
+            "retrying request after transient database error"
+        );
+        tokio::time::sleep(backoff).await;
     }
 }
 
+fn is_safe_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// Only database-sourced 503s are transient; integrity conflicts are final.
+fn is_transient_failure(response: &Response) -> bool {
+    response
+        .extensions()
+        .get::<ErrorReport>()
+        .is_some_and(|report| {
+            report.source == ErrorSourceKind::Database
+                && report.status == StatusCode::SERVICE_UNAVAILABLE
+        })
+}
+
 pub(crate) async fn security_headers_middleware(
     State(conf): State<SecurityHeadersConf>,
     req: Request,
@@ -473,6 +984,13 @@ mod tests {
         assert_eq!(HttpConf::default().shutdown.grace_period_ms, 10_000);
     }
 
+    #[test]
+    fn retry_is_disabled_by_default() {
+        let conf = HttpConf::default().retry;
+        assert!(!conf.enabled);
+        assert_eq!(conf.max_retries, 1);
+    }
+
     #[test]
     fn empty_shutdown_conf_uses_default_grace() {
         let parsed = serde_json::from_str::<ShutdownConf>("{}");
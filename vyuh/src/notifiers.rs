@@ -18,7 +18,6 @@ impl CancellationNotifier {
         self.0.cancelled().await;
     }
 
-    #[cfg(test)]
     pub(crate) fn is_notified(&self) -> bool {
         self.0.is_cancelled()
     }
@@ -0,0 +1,271 @@
+//! Transactional outbox for reliable side effects.
+//!
+//! Handlers call [`enqueue`] on the transaction that holds their own writes,
+//! so a message exists exactly when that transaction commits. The [`Outbox`]
+//! service polls undelivered rows and hands each one to an [`OutboxHandler`].
+//! Delivery is at-least-once: a row is marked done only after its handler
+//! succeeds, so handlers must tolerate duplicates.
+
+use std::{sync::Arc, time::Duration};
+
+use futures::future::BoxFuture;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    Site,
+    db::{self, Bindable, DBSession, DbError, FilteredBuilder, Scannable, Statement},
+    services::{Service, ServiceError, ServiceRunner},
+    tasks::TaskError,
+};
+
+const TABLE: &str = "vyuh_outbox";
+
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS vyuh_outbox (
+    id VARCHAR(36) PRIMARY KEY,
+    topic VARCHAR(255) NOT NULL,
+    payload TEXT NOT NULL,
+    attempts BIGINT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at BIGINT NOT NULL,
+    dispatched_at BIGINT,
+    next_attempt_at BIGINT
+)";
+
+#[derive(Debug, thiserror::Error)]
+pub enum OutboxError {
+    #[error(transparent)]
+    Db(#[from] DbError),
+
+    #[error("outbox payload error: {0}")]
+    Payload(#[from] serde_json::Error),
+
+    #[error("outbox dispatch failed: {0}")]
+    Dispatch(String),
+}
+
+impl From<TaskError> for OutboxError {
+    fn from(err: TaskError) -> Self {
+        Self::Dispatch(err.to_string())
+    }
+}
+
+/// One outbox row. `id` is a UUIDv7, so it also orders rows by creation.
+#[derive(Debug, Clone, PartialEq, Bindable, Scannable)]
+pub struct OutboxMessage {
+    pub id: String,
+    pub topic: String,
+    pub payload: String,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub dispatched_at: Option<i64>,
+    /// Unix millis before which a failed message is not retried.
+    pub next_attempt_at: Option<i64>,
+}
+
+impl OutboxMessage {
+    /// Decodes the JSON payload.
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T, OutboxError> {
+        Ok(serde_json::from_str(&self.payload)?)
+    }
+}
+
+#[derive(Bindable)]
+struct Delivered {
+    dispatched_at: i64,
+}
+
+#[derive(Bindable)]
+struct Failed {
+    attempts: i64,
+    last_error: Option<String>,
+    next_attempt_at: i64,
+}
+
+/// Creates the outbox table if it does not exist.
+pub async fn run_migrations<S: DBSession>(session: &mut S) -> Result<(), OutboxError> {
+    session.execute(Statement::from_str(CREATE_TABLE)).await?;
+    Ok(())
+}
+
+/// Writes `payload` to the outbox under `topic` using `session`, usually an
+/// open transaction.
+pub async fn enqueue<S, T>(session: &mut S, topic: &str, payload: &T) -> Result<String, OutboxError>
+where
+    S: DBSession,
+    T: Serialize,
+{
+    let message = OutboxMessage {
+        id: uuid::Uuid::now_v7().to_string(),
+        topic: topic.to_string(),
+        payload: serde_json::to_string(payload)?,
+        attempts: 0,
+        last_error: None,
+        created_at: chrono::Utc::now().timestamp_millis(),
+        dispatched_at: None,
+        next_attempt_at: None,
+    };
+    db::insert(TABLE).row(&message).execute(session).await?;
+    Ok(message.id)
+}
+
+/// Delivers outbox messages to their destination.
+pub trait OutboxHandler: Send + Sync + 'static {
+    fn dispatch<'a>(
+        &'a self,
+        site: &'a Site,
+        message: &'a OutboxMessage,
+    ) -> BoxFuture<'a, Result<(), OutboxError>>;
+}
+
+/// Handler that submits each message as a task named after its topic.
+pub struct TaskOutbox;
+
+impl OutboxHandler for TaskOutbox {
+    fn dispatch<'a>(
+        &'a self,
+        site: &'a Site,
+        message: &'a OutboxMessage,
+    ) -> BoxFuture<'a, Result<(), OutboxError>> {
+        Box::pin(async move {
+            let input: serde_json::Value = message.payload()?;
+            site.tasks().submit_named(&message.topic, input).await?;
+            Ok(())
+        })
+    }
+}
+
+/// Site service that polls the outbox and dispatches pending messages.
+#[derive(Clone)]
+pub struct Outbox {
+    handler: Arc<dyn OutboxHandler>,
+    poll_interval: Duration,
+    batch_size: usize,
+    max_attempts: i64,
+    retry_backoff: Duration,
+    max_retry_backoff: Duration,
+}
+
+impl Outbox {
+    pub fn new(handler: impl OutboxHandler) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            poll_interval: Duration::from_secs(1),
+            batch_size: 100,
+            max_attempts: 10,
+            retry_backoff: Duration::from_secs(1),
+            max_retry_backoff: Duration::from_secs(300),
+        }
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size.max(1);
+        self
+    }
+
+    /// Failed attempts after which a message is no longer retried.
+    pub fn max_attempts(mut self, attempts: i64) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Delay before the first retry of a failed message. Each further failure
+    /// doubles it, up to `max`.
+    pub fn retry_backoff(mut self, base: Duration, max: Duration) -> Self {
+        self.retry_backoff = base;
+        self.max_retry_backoff = max;
+        self
+    }
+
+    fn retry_delay(&self, attempts: i64) -> Duration {
+        let doublings = attempts.saturating_sub(1).clamp(0, 31) as u32;
+        self.retry_backoff
+            .saturating_mul(1 << doublings)
+            .min(self.max_retry_backoff)
+    }
+
+    /// Dispatches one batch of due messages and returns how many were
+    /// delivered. Failed messages wait out their backoff before the next try.
+    pub async fn dispatch_pending(&self, site: &Site) -> Result<usize, OutboxError> {
+        let mut pool = site.db();
+        let pending: Vec<OutboxMessage> = db::select(TABLE)
+            .filter(
+                "dispatched_at IS NULL AND attempts < :max_attempts \
+                 AND (next_attempt_at IS NULL OR next_attempt_at <= :now)",
+            )
+            .bind_as("max_attempts", self.max_attempts)
+            .bind_as("now", chrono::Utc::now().timestamp_millis())
+            .order_by("id", true)
+            .slice(0, self.batch_size)
+            .all(&mut pool)
+            .await?;
+        let mut delivered = 0;
+        for message in &pending {
+            let now = chrono::Utc::now();
+            let update = match self.handler.dispatch(site, message).await {
+                Ok(()) => {
+                    delivered += 1;
+                    db::update(TABLE).set(&Delivered {
+                        dispatched_at: now.timestamp_millis(),
+                    })
+                }
+                Err(err) => {
+                    tracing::warn!(id = %message.id, topic = %message.topic, "outbox dispatch failed: {err}");
+                    let attempts = message.attempts + 1;
+                    let delay = self.retry_delay(attempts);
+                    db::update(TABLE).set(&Failed {
+                        attempts,
+                        last_error: Some(err.to_string()),
+                        next_attempt_at: now
+                            .timestamp_millis()
+                            .saturating_add(i64::try_from(delay.as_millis()).unwrap_or(i64::MAX)),
+                    })
+                }
+            };
+            update
+                .filter("id = :id")
+                .bind_as("id", message.id.clone())
+                .execute(&mut pool)
+                .await?;
+        }
+        Ok(delivered)
+    }
+
+    async fn poll(self, site: Site) -> Result<(), ServiceError> {
+        if let Err(err) = run_migrations(&mut site.db()).await {
+            tracing::warn!("outbox migration failed: {err}");
+        }
+        let shutdown = site.shutdown_notifier();
+        loop {
+            match self.dispatch_pending(&site).await {
+                // A fully delivered batch may have more behind it; poll again
+                // without sleeping, but let shutdown and other tasks run first.
+                Ok(delivered) if delivered == self.batch_size => {
+                    if shutdown.is_notified() {
+                        return Ok(());
+                    }
+                    tokio::task::yield_now().await;
+                    continue;
+                }
+                Ok(_) => {}
+                Err(err) => tracing::warn!("outbox poll failed: {err}"),
+            }
+            tokio::select! {
+                _ = shutdown.notified() => return Ok(()),
+                _ = tokio::time::sleep(self.poll_interval) => {}
+            }
+        }
+    }
+}
+
+impl Service for Outbox {
+    fn run(&mut self, runner: &mut ServiceRunner) -> Result<(), ServiceError> {
+        let outbox = self.clone();
+        runner.run("outbox", move |site: Site| outbox.clone().poll(site))
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::MethodRouter;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Site,
+    callables::Operation,
+    errors::{ErrorReport, ErrorSourceKind},
+    routes::{AxumRouter, Methods},
+};
+
+/// Method discovery for registered paths.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AllowConf {
+    /// Answer `OPTIONS` with 204 and an `Allow` header on every route path
+    /// that has no `OPTIONS` handler of its own.
+    pub auto_options: bool,
+    /// Answer unregistered methods on a known path with 405 and an `Allow`
+    /// header listing the registered ones.
+    pub method_not_allowed: bool,
+}
+
+impl Default for AllowConf {
+    fn default() -> Self {
+        Self {
+            auto_options: true,
+            method_not_allowed: true,
+        }
+    }
+}
+
+/// Methods registered per route path. `HEAD` is included wherever `GET` is,
+/// since the router answers it for `GET` handlers.
+pub(crate) fn allowed_methods<'a>(
+    routes: impl Iterator<Item = &'a Operation>,
+) -> BTreeMap<String, Methods> {
+    let mut paths: BTreeMap<String, Methods> = BTreeMap::new();
+    for op in routes {
+        let mut methods = op.methods;
+        if methods.contains(Methods::GET) {
+            methods |= Methods::HEAD;
+        }
+        paths
+            .entry(op.path.clone())
+            .and_modify(|m| *m |= methods)
+            .or_insert(methods);
+    }
+    paths
+}
+
+/// `Allow` header value, e.g. `GET, HEAD, OPTIONS`.
+pub(crate) fn allow_header(methods: Methods) -> HeaderValue {
+    HeaderValue::from_str(&methods.to_vec().join(", "))
+        .unwrap_or_else(|_| HeaderValue::from_static("OPTIONS"))
+}
+
+/// Adds the `OPTIONS` responder and the 405 fallback to every path in
+/// `paths`, as enabled in `conf`.
+pub(crate) fn method_routes(
+    mut router: AxumRouter<Site>,
+    paths: &BTreeMap<String, Methods>,
+    conf: &AllowConf,
+) -> AxumRouter<Site> {
+    for (path, methods) in paths {
+        // `ANY` routes already answer every method.
+        if *methods == Methods::ANY {
+            continue;
+        }
+        let options = conf.auto_options && !methods.contains(Methods::OPTIONS);
+        let allowed = if conf.auto_options {
+            *methods | Methods::OPTIONS
+        } else {
+            *methods
+        };
+        let allow = allow_header(allowed);
+        let mut extra = MethodRouter::new();
+        if options {
+            let allow = allow.clone();
+            extra = extra
+                .options(move || async move { (StatusCode::NO_CONTENT, [(header::ALLOW, allow)]) });
+        }
+        if conf.method_not_allowed {
+            extra = extra.fallback(move || async move { not_allowed(allow) });
+        } else if !options {
+            continue;
+        }
+        router = router.route(path, extra);
+    }
+    router
+}
+
+fn not_allowed(allow: HeaderValue) -> Response {
+    let mut response = ErrorReport::new(
+        StatusCode::METHOD_NOT_ALLOWED,
+        ErrorSourceKind::Framework,
+        "method_not_allowed",
+        "The method is not allowed for this path.",
+    )
+    .into_response();
+    response.headers_mut().insert(header::ALLOW, allow);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(path: &str, methods: Methods) -> Operation {
+        Operation {
+            methods,
+            ..Operation::from_api_doc("route", path)
+        }
+    }
+
+    #[test]
+    fn methods_are_merged_per_path() {
+        let ops = [
+            op("/notes", Methods::GET),
+            op("/notes", Methods::POST),
+            op("/notes/{id}", Methods::DELETE),
+        ];
+        let paths = allowed_methods(ops.iter());
+        assert_eq!(
+            allow_header(paths["/notes"]),
+            HeaderValue::from_static("GET, POST, HEAD")
+        );
+        assert_eq!(paths["/notes/{id}"], Methods::DELETE);
+    }
+}
@@ -0,0 +1,254 @@
+use axum::body::{Body, Bytes, to_bytes};
+use axum::extract::{FromRequest, FromRequestParts, Multipart, Request, State};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header, request::Parts};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum_extra::extract::CookieJar;
+use axum_extra::extract::cookie::{Cookie, SameSite};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Site,
+    callables::{ArgPart, IntoArgPart},
+    errors::{ErrorReport, ErrorSourceKind},
+};
+
+/// JWT `typ` for the CSRF cookie; the authenticator rejects it as a login token.
+const CSRF_TYP: &str = "vyuh-csrf";
+
+/// Double-submit-cookie CSRF protection for browser form posts.
+///
+/// Every response without a valid token cookie gets one: a nonce signed with
+/// the site's auth key. Unsafe requests (anything but GET, HEAD, OPTIONS,
+/// TRACE) must echo the nonce in `header` or the urlencoded or multipart form
+/// `field`, or are rejected with 403. Requests carrying `Authorization: Bearer` are exempt,
+/// since browsers never attach that header cross-site on their own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsrfConf {
+    pub enabled: bool,
+    pub cookie: String,
+    pub header: String,
+    pub field: String,
+    /// Lifetime of a token; a fresh one is issued once it expires.
+    pub ttl_secs: i64,
+    pub secure: bool,
+}
+
+impl Default for CsrfConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cookie: "vyuh_csrf".into(),
+            header: "x-csrf-token".into(),
+            field: "csrf_token".into(),
+            ttl_secs: 12 * 60 * 60,
+            secure: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CsrfClaims {
+    nonce: String,
+    exp: i64,
+}
+
+/// The current request's CSRF token, for embedding in forms and pages.
+///
+/// Empty when CSRF protection is disabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrfToken {
+    token: String,
+    field: String,
+}
+
+impl CsrfToken {
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// `<input type="hidden" name="csrf_token" value="...">` for a form body.
+    pub fn input(&self) -> String {
+        format!(
+            r#"<input type="hidden" name="{}" value="{}">"#,
+            self.field, self.token
+        )
+    }
+}
+
+impl FromRequestParts<Site> for CsrfToken {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, site: &Site) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<CsrfToken>()
+            .cloned()
+            .unwrap_or(Self {
+                token: String::new(),
+                field: site.conf().http.csrf.field.clone(),
+            }))
+    }
+}
+
+impl IntoArgPart for CsrfToken {
+    fn into_arg_part() -> ArgPart {
+        ArgPart::Ignore
+    }
+}
+
+pub(crate) async fn csrf_middleware(
+    State(site): State<Site>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let conf = &site.conf().http.csrf;
+    let existing = CookieJar::from_headers(req.headers())
+        .get(&conf.cookie)
+        .and_then(|cookie| {
+            site.auth()
+                .verify_claims::<CsrfClaims>(CSRF_TYP, cookie.value())
+                .ok()
+        })
+        .map(|claims| claims.nonce);
+
+    if is_unsafe(req.method()) && !is_bearer(req.headers()) {
+        let (parts, body) = req.into_parts();
+        let limit = usize::try_from(site.conf().http.body_limit.max_bytes).unwrap_or(usize::MAX);
+        let (submitted, body) = match submitted_token(&parts.headers, body, conf, limit).await {
+            Ok(found) => found,
+            Err(response) => return response,
+        };
+        let valid =
+            matches!((&existing, &submitted), (Some(nonce), Some(sent)) if same(nonce, sent));
+        if !valid {
+            return rejected();
+        }
+        req = Request::from_parts(parts, body);
+    }
+
+    let nonce = existing
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+    req.extensions_mut().insert(CsrfToken {
+        token: nonce.clone(),
+        field: conf.field.clone(),
+    });
+    let mut response = next.run(req).await;
+    if existing.is_none() {
+        match issue_cookie(&site, conf, nonce) {
+            Some(value) => {
+                response.headers_mut().append(header::SET_COOKIE, value);
+            }
+            None => tracing::warn!("failed to sign CSRF cookie"),
+        }
+    }
+    response
+}
+
+fn is_unsafe(method: &Method) -> bool {
+    !matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+    )
+}
+
+fn is_bearer(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split_once(' '))
+        .is_some_and(|(scheme, token)| {
+            scheme.eq_ignore_ascii_case("bearer") && !token.trim().is_empty()
+        })
+}
+
+/// Reads the token from the header, or from a urlencoded or multipart form
+/// body of at most `limit` bytes, which is buffered and handed back for the
+/// handler.
+async fn submitted_token(
+    headers: &HeaderMap,
+    body: Body,
+    conf: &CsrfConf,
+    limit: usize,
+) -> Result<(Option<String>, Body), Response> {
+    if let Some(token) = headers.get(&conf.header).and_then(|v| v.to_str().ok()) {
+        return Ok((Some(token.to_string()), body));
+    }
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let multipart = content_type.starts_with("multipart/form-data");
+    if !multipart && !content_type.starts_with("application/x-www-form-urlencoded") {
+        return Ok((None, body));
+    }
+    let bytes = to_bytes(body, limit).await.map_err(|_| {
+        ErrorReport::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorSourceKind::Parse,
+            "request_body_too_large",
+            format!("Form bodies are limited to {limit} bytes."),
+        )
+        .into_response()
+    })?;
+    let token = if multipart {
+        multipart_field(headers, bytes.clone(), &conf.field).await
+    } else {
+        serde_urlencoded::from_bytes::<Vec<(String, String)>>(&bytes)
+            .ok()
+            .and_then(|pairs| pairs.into_iter().find(|(key, _)| *key == conf.field))
+            .map(|(_, value)| value)
+    };
+    Ok((token, Body::from(bytes)))
+}
+
+/// The text of the first multipart part named `name`. Parts before it are
+/// skipped unread, so put the token field first in large upload forms.
+async fn multipart_field(headers: &HeaderMap, bytes: Bytes, name: &str) -> Option<String> {
+    let mut req = Request::new(Body::from(bytes));
+    *req.headers_mut() = headers.clone();
+    let mut multipart = Multipart::from_request(req, &()).await.ok()?;
+    while let Some(field) = multipart.next_field().await.ok()? {
+        if field.name() == Some(name) {
+            return field.text().await.ok();
+        }
+    }
+    None
+}
+
+fn issue_cookie(site: &Site, conf: &CsrfConf, nonce: String) -> Option<HeaderValue> {
+    let claims = CsrfClaims {
+        nonce,
+        exp: chrono::Utc::now().timestamp() + conf.ttl_secs,
+    };
+    let token = site.auth().sign_claims(CSRF_TYP, &claims).ok()?;
+    let cookie = Cookie::build((conf.cookie.as_str(), token))
+        .path("/")
+        .max_age(time::Duration::seconds(conf.ttl_secs))
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .secure(conf.secure)
+        .build();
+    HeaderValue::try_from(cookie.to_string()).ok()
+}
+
+/// Constant-time comparison, so response timing leaks nothing about the nonce.
+fn same(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+fn rejected() -> Response {
+    ErrorReport::new(
+        StatusCode::FORBIDDEN,
+        ErrorSourceKind::Framework,
+        "csrf_failed",
+        "CSRF token missing or invalid.",
+    )
+    .into_response()
+}
@@ -0,0 +1,102 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Site, bundles,
+    db::{DBSession, PoolStats, Statement},
+    routes::{Methods, RouteConf},
+};
+
+/// Built-in liveness and readiness endpoints for orchestrator probes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthConf {
+    pub enabled: bool,
+    /// Liveness path: 200 with uptime while the database answers `SELECT 1`.
+    pub path: String,
+    /// Readiness path: like `path`, but also 503 until the pool has connected.
+    pub ready_path: String,
+}
+
+impl Default for HealthConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: "/healthz".into(),
+            ready_path: "/healthz/ready".into(),
+        }
+    }
+}
+
+/// Probe response body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub status: String,
+    pub uptime_secs: u64,
+    pub database: String,
+    pub pool: PoolStats,
+}
+
+pub(crate) fn bundle(conf: &HealthConf) -> bundles::Bundle {
+    let route = |name: &'static str, path: &str| RouteConf {
+        name: name.into(),
+        methods: Methods::GET,
+        path: path.to_string().into(),
+        slash: None,
+    };
+    bundles::bundle([
+        bundles::route(live, route("vyuh_health", &conf.path)),
+        bundles::route(ready, route("vyuh_ready", &conf.ready_path)),
+    ])
+}
+
+async fn live(site: Site) -> Response {
+    let database = ping(&site).await;
+    report(&site, database)
+}
+
+async fn ready(site: Site) -> Response {
+    if !site.db().has_connected() {
+        // A lazy pool has not opened a connection yet. Start one so a later
+        // probe can succeed, but do not report ready before it exists.
+        let warm = site.clone();
+        tokio::spawn(async move {
+            let _ = ping(&warm).await;
+        });
+        return report(&site, Err("not connected".into()));
+    }
+    let database = ping(&site).await;
+    report(&site, database)
+}
+
+async fn ping(site: &Site) -> Result<(), String> {
+    site.db()
+        .execute(Statement::from_str("SELECT 1"))
+        .await
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+fn report(site: &Site, database: Result<(), String>) -> Response {
+    // Probes are unauthenticated, so the database error is only logged.
+    let (status, database) = match database {
+        Ok(()) => (StatusCode::OK, "ok"),
+        Err(err) => {
+            tracing::warn!("health check failed: {err}");
+            (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+        }
+    };
+    let body = HealthReport {
+        status: if status.is_success() {
+            "ok"
+        } else {
+            "unavailable"
+        }
+        .into(),
+        uptime_secs: site.uptime().as_secs(),
+        database: database.into(),
+        pool: site.pool_stats(),
+    };
+    (status, axum::Json(body)).into_response()
+}
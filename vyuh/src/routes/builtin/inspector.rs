@@ -0,0 +1,113 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Site,
+    auth::MaybeUser,
+    bundles,
+    callables::{ArgPart, Operation},
+    errors::{ErrorReport, ErrorSourceKind},
+    routes::{Methods, RouteConf},
+};
+
+/// Built-in JSON listing of every named route, for development.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RouteInspectorConf {
+    pub enabled: bool,
+    pub path: String,
+    /// Answer only requests from a logged-in user. Defaults to on in release
+    /// builds and off in debug builds.
+    pub require_auth: bool,
+}
+
+impl Default for RouteInspectorConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: "/_routes".into(),
+            require_auth: !cfg!(debug_assertions),
+        }
+    }
+}
+
+/// One route in the inspector listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteInfo {
+    pub name: String,
+    pub methods: Vec<String>,
+    pub path: String,
+    pub summary: Option<String>,
+    pub params: Vec<RouteParam>,
+}
+
+/// A handler argument and where it is extracted from, e.g. `path` or `query`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteParam {
+    pub name: String,
+    pub source: String,
+}
+
+pub(crate) fn bundle(conf: &RouteInspectorConf) -> bundles::Bundle {
+    bundles::bundle([bundles::route(
+        routes,
+        RouteConf {
+            name: "vyuh_route_inspector".into(),
+            methods: Methods::GET,
+            path: conf.path.clone().into(),
+            slash: None,
+        },
+    )])
+}
+
+async fn routes(site: Site, user: MaybeUser) -> Response {
+    if site.conf().http.route_inspector.require_auth && user.0.is_none() {
+        return ErrorReport::new(
+            StatusCode::UNAUTHORIZED,
+            ErrorSourceKind::Auth,
+            "unauthorized",
+            "The route inspector requires a logged-in user.",
+        )
+        .into_response();
+    }
+    let routes: Vec<RouteInfo> = site
+        .iter_routes()
+        .filter(|op| !op.hidden)
+        .map(route_info)
+        .collect();
+    axum::Json(routes).into_response()
+}
+
+fn route_info(op: &Operation) -> RouteInfo {
+    RouteInfo {
+        name: op.name.clone(),
+        methods: op.methods.to_vec().into_iter().map(String::from).collect(),
+        path: op.path.clone(),
+        summary: op.summary.clone(),
+        params: op
+            .args
+            .iter()
+            .filter_map(|arg| {
+                Some(RouteParam {
+                    name: arg.name.clone(),
+                    source: arg_source(&arg.part)?.to_string(),
+                })
+            })
+            .collect(),
+    }
+}
+
+fn arg_source(part: &ArgPart) -> Option<&'static str> {
+    Some(match part {
+        ArgPart::Ignore => return None,
+        ArgPart::Header(_) => "header",
+        ArgPart::Cookie(_) => "cookie",
+        ArgPart::Query(_) => "query",
+        ArgPart::Path(_) => "path",
+        ArgPart::Body(..) => "body",
+        ArgPart::Security { .. } => "security",
+        ArgPart::Zone => "zone",
+        ArgPart::Upgrade => "upgrade",
+    })
+}
@@ -1,5 +1,15 @@
+pub(crate) mod allow;
 #[cfg(feature = "cors")]
 mod cors;
+pub(crate) mod csrf;
+pub(crate) mod health;
+pub(crate) mod inspector;
+pub(crate) mod rate_limit;
 
+pub use allow::AllowConf;
 #[cfg(feature = "cors")]
 pub use cors::CorsMiddleware;
+pub use csrf::{CsrfConf, CsrfToken};
+pub use health::{HealthConf, HealthReport};
+pub use inspector::{RouteInfo, RouteInspectorConf, RouteParam};
+pub use rate_limit::RateLimitConf;
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, FromRequestParts, Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Site,
+    auth::MaybeUser,
+    conf::ConfError,
+    errors::{ErrorReport, ErrorSourceKind},
+};
+
+/// Token-bucket rate limiting keyed by client IP.
+///
+/// Each client may burst up to `requests` and then refills at
+/// `requests / per_ms`. With `per_user`, requests carrying a valid access
+/// token are budgeted per user key instead of per IP.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConf {
+    pub enabled: bool,
+    pub requests: u32,
+    pub per_ms: u64,
+    pub per_user: bool,
+}
+
+impl Default for RateLimitConf {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            requests: 60,
+            per_ms: 60_000,
+            per_user: false,
+        }
+    }
+}
+
+impl RateLimitConf {
+    pub(crate) fn validate(&self, errors: &mut Vec<ConfError>) {
+        if !self.enabled {
+            return;
+        }
+        if self.requests == 0 || self.per_ms == 0 {
+            errors.push(ConfError::InvalidValue {
+                field: "http.rate_limit".into(),
+                reason: "requests and per_ms must both be greater than zero".into(),
+                expected: None,
+            });
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// In-memory buckets shared by every router built from one site.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    conf: RateLimitConf,
+    buckets: HashMap<String, Bucket>,
+    last_gc: Instant,
+}
+
+impl LimiterState {
+    fn capacity(&self) -> f64 {
+        f64::from(self.conf.requests.max(1))
+    }
+
+    fn window(&self) -> Duration {
+        Duration::from_millis(self.conf.per_ms.max(1))
+    }
+}
+
+impl RateLimiter {
+    pub(crate) fn new(conf: RateLimitConf) -> Self {
+        Self {
+            state: Mutex::new(LimiterState {
+                conf,
+                buckets: HashMap::new(),
+                last_gc: Instant::now(),
+            }),
+        }
+    }
+
+    /// Swaps in new budgets. Existing buckets keep their tokens, capped at
+    /// the new capacity on their next request.
+    pub(crate) fn reconfigure(&self, conf: RateLimitConf) {
+        self.state.lock().conf = conf;
+    }
+
+    fn per_user(&self) -> bool {
+        self.state.lock().conf.per_user
+    }
+
+    /// Takes one token for `key`, or returns how long until one is available.
+    fn acquire(&self, key: &str, now: Instant) -> Result<(), Duration> {
+        let mut state = self.state.lock();
+        if !state.conf.enabled {
+            return Ok(());
+        }
+        let (capacity, window) = (state.capacity(), state.window());
+        let per_sec = capacity / window.as_secs_f64();
+        // A bucket idle for a full window has refilled, so dropping it is free.
+        if now.duration_since(state.last_gc) >= window {
+            state
+                .buckets
+                .retain(|_, bucket| now.duration_since(bucket.updated) < window);
+            state.last_gc = now;
+        }
+        let bucket = state.buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_sec).min(capacity);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_sec))
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.state.lock().buckets.len()
+    }
+}
+
+pub(crate) async fn rate_limit_middleware(
+    State(site): State<Site>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Some(limiter) = site.rate_limiter() else {
+        return next.run(req).await;
+    };
+    let (mut parts, body) = req.into_parts();
+    let mut key = None;
+    if limiter.per_user() {
+        let user = match MaybeUser::from_request_parts(&mut parts, &site).await {
+            Ok(MaybeUser(user)) => user,
+            Err(err) => return err.into_response(),
+        };
+        key = user.map(|user| format!("user:{}", user.key));
+    }
+    let key = key.unwrap_or_else(|| {
+        parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map_or_else(|| "ip:unknown".into(), |info| format!("ip:{}", info.0.ip()))
+    });
+    match limiter.acquire(&key, Instant::now()) {
+        Ok(()) => next.run(Request::from_parts(parts, body)).await,
+        Err(wait) => too_many_requests(wait),
+    }
+}
+
+fn too_many_requests(wait: Duration) -> Response {
+    let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    let mut response = ErrorReport::new(
+        StatusCode::TOO_MANY_REQUESTS,
+        ErrorSourceKind::Framework,
+        "rate_limited",
+        "Too many requests.",
+    )
+    .into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(secs.max(1)));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(requests: u32, per_ms: u64) -> RateLimiter {
+        RateLimiter::new(RateLimitConf {
+            enabled: true,
+            requests,
+            per_ms,
+            per_user: false,
+        })
+    }
+
+    #[test]
+    fn bucket_refills_over_the_window() {
+        let limiter = limiter(2, 1_000);
+        let start = Instant::now();
+        assert!(limiter.acquire("a", start).is_ok());
+        assert!(limiter.acquire("a", start).is_ok());
+        let wait = limiter.acquire("a", start).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+        assert!(limiter.acquire("b", start).is_ok());
+        assert!(limiter.acquire("a", start + wait).is_ok());
+    }
+
+    #[test]
+    fn idle_buckets_are_collected() {
+        let limiter = limiter(1, 100);
+        let start = Instant::now();
+        assert!(limiter.acquire("a", start).is_ok());
+        assert!(limiter.acquire("b", start).is_ok());
+        assert_eq!(limiter.len(), 2);
+        assert!(
+            limiter
+                .acquire("c", start + Duration::from_millis(200))
+                .is_ok()
+        );
+        assert_eq!(limiter.len(), 1);
+    }
+
+    #[test]
+    fn reconfigure_changes_the_budget() {
+        let limiter = limiter(1, 1_000);
+        let start = Instant::now();
+        assert!(limiter.acquire("a", start).is_ok());
+        assert!(limiter.acquire("a", start).is_err());
+        limiter.reconfigure(RateLimitConf {
+            enabled: true,
+            requests: 10,
+            per_ms: 1_000,
+            per_user: false,
+        });
+        assert!(
+            limiter
+                .acquire("a", start + Duration::from_millis(200))
+                .is_ok()
+        );
+    }
+}
@@ -104,6 +104,19 @@ impl Methods {
     pub const TRACE: Self = Self(MethodFilter::TRACE);
     /// Match `CONNECT` requests.
     pub const CONNECT: Self = Self(MethodFilter::CONNECT);
+    /// Match every method; routed with [`axum::routing::any`] so non-standard
+    /// methods reach the handler too.
+    pub const ANY: Self = Self(
+        MethodFilter::GET
+            .or(MethodFilter::POST)
+            .or(MethodFilter::PUT)
+            .or(MethodFilter::PATCH)
+            .or(MethodFilter::DELETE)
+            .or(MethodFilter::HEAD)
+            .or(MethodFilter::OPTIONS)
+            .or(MethodFilter::TRACE)
+            .or(MethodFilter::CONNECT),
+    );
 
     /// Iterate over individual methods in this filter.
     pub fn iter(&self) -> MethodIter {
@@ -141,8 +154,11 @@ impl Methods {
         other.iter().any(|(_, method)| self.contains(method))
     }
 
-    /// Parse a method string (case-insensitive).
+    /// Parse a method string (case-insensitive); `ANY` yields [`Methods::ANY`].
     pub fn from_str(s: &str) -> Option<Self> {
+        if s.eq_ignore_ascii_case("ANY") {
+            return Some(Self::ANY);
+        }
         KNOWN_METHODS
             .iter()
             .find(|(name, _)| name.eq_ignore_ascii_case(s))
@@ -227,6 +243,8 @@ mod tests {
         assert_eq!(Methods::from_str("Trace"), Some(Methods::TRACE));
         assert_eq!(Methods::from_str("connect"), Some(Methods::CONNECT));
         assert_eq!(Methods::from_str("BREW"), None);
+        assert_eq!(Methods::from_str("any"), Some(Methods::ANY));
+        assert_eq!(Methods::ANY.to_vec().len(), 9);
     }
 
     #[test]
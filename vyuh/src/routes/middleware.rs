@@ -1,6 +1,23 @@
-use axum::routing::Route;
+use std::{
+    convert::Infallible,
+    marker::PhantomData,
+    task::{Context, Poll},
+};
 
-use crate::callables::LayerSpec;
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, Request},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::Route,
+};
+use futures::future::BoxFuture;
+
+use crate::{
+    Site,
+    callables::{ArgPart, IntoArgPart, LayerSpec},
+    errors::{ErrorReport, ErrorSourceKind},
+};
 
 /// A documented tower middleware that can inject its spec into wrapped operations.
 ///
@@ -58,3 +75,123 @@ where
         self.0
     }
 }
+
+/// Runs the extractor `E` once before any route it wraps, rejecting early.
+///
+/// Apply it to a bundle with `Bundle::layer(Require::<AuthUser>::new())` or
+/// `bundle! { requires = [AuthUser], ... }`. The extracted value is stored in
+/// the request extensions; handlers that still declare `E` run it again, which
+/// is free for extractors that read their cached value (such as `AuthUser`).
+pub struct Require<E>(PhantomData<fn() -> E>);
+
+impl<E> Require<E> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E> Default for Require<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Clone for Require<E> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<E, S> tower::Layer<S> for Require<E> {
+    type Service = RequireService<E, S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireService {
+            inner,
+            extractor: PhantomData,
+        }
+    }
+}
+
+impl<E> Middleware for Require<E>
+where
+    E: FromRequestParts<Site> + IntoArgPart + Clone + Send + Sync + 'static,
+{
+    type Layer = Self;
+
+    fn layer_spec(&self) -> Option<LayerSpec> {
+        let part = E::into_arg_part();
+        if matches!(part, ArgPart::Ignore) {
+            return None;
+        }
+        Some(LayerSpec {
+            name: "requires".to_string(),
+            description: Some(format!("Requires `{}`", short_type_name::<E>())),
+            parts: vec![part],
+        })
+    }
+
+    fn into_layer(self) -> Self::Layer {
+        self
+    }
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Service produced by [`Require`].
+pub struct RequireService<E, S> {
+    inner: S,
+    extractor: PhantomData<fn() -> E>,
+}
+
+impl<E, S: Clone> Clone for RequireService<E, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            extractor: PhantomData,
+        }
+    }
+}
+
+impl<E, S> tower::Service<Request> for RequireService<E, S>
+where
+    E: FromRequestParts<Site> + Clone + Send + Sync + 'static,
+    S: tower::Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        // Take the service that was polled ready; leave a fresh clone behind.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(async move {
+            let (mut parts, body) = req.into_parts();
+            let Some(site) = parts.extensions.get::<Site>().cloned() else {
+                return Ok(ErrorReport::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ErrorSourceKind::Framework,
+                    "site_missing",
+                    "site is not attached to request",
+                )
+                .into_response());
+            };
+            match E::from_request_parts(&mut parts, &site).await {
+                Ok(value) => {
+                    parts.extensions.insert(value);
+                }
+                Err(rejection) => return Ok(rejection.into_response()),
+            }
+            inner.call(Request::<Body>::from_parts(parts, body)).await
+        })
+    }
+}
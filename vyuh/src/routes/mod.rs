@@ -1,10 +1,12 @@
 mod methods;
 pub mod middleware;
 pub mod multipart;
+mod pagination;
+mod sse;
+mod store;
 mod subscriber;
 mod types;
 
-#[cfg(feature = "cors")]
 pub mod builtin;
 
 // Response types
@@ -23,6 +25,9 @@ pub use axum::extract::{
 // Extra extractors
 pub use axum_extra::extract::TypedHeader;
 
+// WebSocket handlers
+pub use axum::extract::ws::{Message as WsMessage, WebSocket, WebSocketUpgrade};
+
 // HTTP primitives
 pub use axum::http::{HeaderMap, HeaderName, Method as HttpMethod, StatusCode, Uri};
 
@@ -31,10 +36,14 @@ pub use axum::body::Body;
 
 // Local types
 pub use crate::Data;
+pub use crate::middlewares::RequestId;
 pub use crate::validation::Valid;
 pub use methods::{MethodIter, Methods};
-pub use middleware::{Middleware, RawLayer, layer_from};
+pub use middleware::{Middleware, RawLayer, Require, layer_from};
 pub use multipart::{JsonPart, MultipartForm, MultipartMap, UploadedFile, UploadedText};
+pub use pagination::PageHeaders;
+pub use sse::{Sse, SseEvent};
+pub use store::RequestStore;
 pub use subscriber::{ChannelAttach, Subscriber};
 pub use types::{BodyBytes, Form, Json, JsonStr, Path, Query, RouteConf};
 
@@ -52,3 +61,7 @@ pub mod axum_extractors {
 
 #[cfg(feature = "cors")]
 pub use builtin::CorsMiddleware;
+pub use builtin::{
+    AllowConf, CsrfConf, CsrfToken, HealthConf, HealthReport, RateLimitConf, RouteInfo,
+    RouteInspectorConf, RouteParam,
+};
@@ -1003,3 +1003,12 @@ impl crate::callables::IntoArgPart for MultipartMap {
         )
     }
 }
+
+impl crate::callables::IntoArgPart for axum_extra::extract::Multipart {
+    fn into_arg_part() -> crate::callables::ArgPart {
+        crate::callables::ArgPart::Body(
+            crate::callables::TypeSchema::binary_body(),
+            Cow::Borrowed("multipart/form-data"),
+        )
+    }
+}
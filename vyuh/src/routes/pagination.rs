@@ -0,0 +1,80 @@
+use axum::http::{HeaderName, HeaderValue, header};
+use axum::response::{IntoResponseParts, ResponseParts};
+
+use crate::Site;
+use crate::callables::{IntoReturnPart, ReturnPart};
+use crate::db::Page;
+
+/// `X-Total-Count` and RFC 8288 `Link` headers for a [`Page`].
+///
+/// Links point at the named route with `page` and `per_page` query
+/// arguments added to `args`. Return it next to the body:
+/// `(PageHeaders::new(&page, &site, "orders", &[]), Json(page.items))`.
+#[derive(Debug, Clone)]
+pub struct PageHeaders {
+    total: i64,
+    link: Option<String>,
+}
+
+impl PageHeaders {
+    pub fn new<T>(page: &Page<T>, site: &Site, route: &str, args: &[(&str, &str)]) -> Self {
+        Self {
+            total: page.total,
+            link: page_links(page, site, route, args),
+        }
+    }
+}
+
+/// Builds the `Link` value; `None` for unpaginated results or unknown routes.
+fn page_links<T>(
+    page: &Page<T>,
+    site: &Site,
+    route: &str,
+    args: &[(&str, &str)],
+) -> Option<String> {
+    if page.per_page == usize::MAX {
+        return None;
+    }
+    let last = page.total_pages.max(1);
+    let mut rels = vec![("first", 1)];
+    if page.page > 1 {
+        rels.push(("prev", (page.page - 1).min(last)));
+    }
+    if page.page < last {
+        rels.push(("next", page.page + 1));
+    }
+    rels.push(("last", last));
+
+    let per_page = page.per_page.to_string();
+    let mut links = Vec::with_capacity(rels.len());
+    for (rel, number) in rels {
+        let number = number.to_string();
+        let mut query = args.to_vec();
+        query.extend([("page", number.as_str()), ("per_page", per_page.as_str())]);
+        let url = site.reverse(route, &query)?;
+        links.push(format!("<{url}>; rel=\"{rel}\""));
+    }
+    Some(links.join(", "))
+}
+
+impl IntoResponseParts for PageHeaders {
+    type Error = std::convert::Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        let headers = res.headers_mut();
+        headers.insert(
+            HeaderName::from_static("x-total-count"),
+            HeaderValue::from(self.total),
+        );
+        if let Some(value) = self.link.and_then(|link| HeaderValue::try_from(link).ok()) {
+            headers.insert(header::LINK, value);
+        }
+        Ok(res)
+    }
+}
+
+impl<T: IntoReturnPart> IntoReturnPart for (PageHeaders, T) {
+    fn into_return_part() -> ReturnPart {
+        T::into_return_part()
+    }
+}
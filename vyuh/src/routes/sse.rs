@@ -0,0 +1,129 @@
+//! Server-sent events responses over arbitrary event streams.
+
+use std::{convert::Infallible, time::Duration};
+
+use axum::response::{
+    IntoResponse, Response,
+    sse::{KeepAlive, Sse as AxumSse},
+};
+use futures::{Stream, StreamExt, stream::BoxStream};
+
+use crate::{
+    Site,
+    callables::{IntoReturnPart, ReturnPart},
+    db::DbError,
+};
+
+pub use axum::response::sse::Event as SseEvent;
+
+const DEFAULT_KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// `text/event-stream` response that forwards every event from `stream`.
+///
+/// A keep-alive comment is sent whenever the stream stays idle for the
+/// keep-alive interval (15 seconds by default).
+pub struct Sse<S> {
+    stream: S,
+    keepalive: Duration,
+}
+
+impl<S> Sse<S>
+where
+    S: Stream<Item = SseEvent> + Send + 'static,
+{
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            keepalive: DEFAULT_KEEPALIVE,
+        }
+    }
+
+    /// Overrides the idle keep-alive interval.
+    pub fn keep_alive(mut self, interval: Duration) -> Self {
+        self.keepalive = interval;
+        self
+    }
+}
+
+impl Sse<BoxStream<'static, SseEvent>> {
+    /// Streams Postgres `NOTIFY` messages on `channels` as events named after
+    /// the channel. Other backends return `DbError::Unsupported`.
+    pub async fn from_notify(site: &Site, channels: &[&str]) -> Result<Self, DbError> {
+        let topics = channels.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let receiver = site.consume_notify(&topics).await?;
+        let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+            let notify = receiver.recv().await?;
+            let event = SseEvent::default()
+                .event(notify.channel)
+                .data(notify.payload);
+            Some((event, receiver))
+        });
+        Ok(Self::new(stream.boxed()))
+    }
+}
+
+impl<S> IntoResponse for Sse<S>
+where
+    S: Stream<Item = SseEvent> + Send + 'static,
+{
+    fn into_response(self) -> Response {
+        AxumSse::new(self.stream.map(Ok::<_, Infallible>))
+            .keep_alive(KeepAlive::new().interval(self.keepalive))
+            .into_response()
+    }
+}
+
+impl<S: Send> IntoReturnPart for Sse<S> {
+    fn into_return_part() -> ReturnPart {
+        ReturnPart::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{StatusCode, header};
+
+    use super::*;
+    use crate::{
+        SiteConf, bundles,
+        routes::{Methods, RouteConf},
+        testing::TestClient,
+    };
+
+    async fn ticks() -> Sse<BoxStream<'static, SseEvent>> {
+        let events = (1..=2).map(|n| SseEvent::default().event("tick").data(n.to_string()));
+        Sse::new(futures::stream::iter(events).boxed())
+    }
+
+    #[tokio::test]
+    async fn sse_streams_events_as_event_stream() {
+        let bundle = bundles::bundle([bundles::route(
+            ticks,
+            RouteConf {
+                name: "ticks".into(),
+                methods: Methods::GET,
+                path: "/ticks".into(),
+                slash: None,
+            },
+        )]);
+        let site = Site::build(SiteConf::default().log_init(false), bundle)
+            .await
+            .unwrap();
+        let response = TestClient::new(site.clone())
+            .get("/ticks")
+            .send()
+            .await
+            .assert_status(StatusCode::OK);
+
+        assert_eq!(
+            response
+                .header(header::CONTENT_TYPE.as_str())
+                .and_then(|value| value.to_str().ok()),
+            Some("text/event-stream")
+        );
+        let body = response.text().await;
+        assert!(body.contains("event: tick\ndata: 1\n\n"));
+        assert!(body.contains("event: tick\ndata: 2\n\n"));
+        site.shutdown_and_wait().await;
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use axum::http::{Extensions, request::Parts};
+use parking_lot::Mutex;
+
+use crate::Site;
+use crate::callables::{ArgPart, IntoArgPart};
+
+/// Typed values shared by middleware, extractors, and the handler of one request.
+///
+/// The store lives in the request extensions behind an `Arc`, so a middleware
+/// that keeps its handle can also read values set further down the stack
+/// after `next.run(req)` returns.
+#[derive(Clone, Default)]
+pub struct RequestStore {
+    values: Arc<Mutex<Extensions>>,
+}
+
+impl RequestStore {
+    /// Returns the store attached to `extensions`, attaching a new one if absent.
+    pub fn of(extensions: &mut Extensions) -> Self {
+        extensions.get_or_insert_default::<RequestStore>().clone()
+    }
+
+    /// Returns a clone of the stored `T`, if any.
+    pub fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.values.lock().get::<T>().cloned()
+    }
+
+    /// Stores `value`, returning the previous `T`.
+    pub fn set<T: Clone + Send + Sync + 'static>(&self, value: T) -> Option<T> {
+        self.values.lock().insert(value)
+    }
+
+    pub fn remove<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.values.lock().remove::<T>()
+    }
+
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.values.lock().get::<T>().is_some()
+    }
+}
+
+impl std::fmt::Debug for RequestStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestStore")
+            .field("len", &self.values.lock().len())
+            .finish()
+    }
+}
+
+impl axum::extract::FromRequestParts<Site> for RequestStore {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _site: &Site) -> Result<Self, Self::Rejection> {
+        Ok(Self::of(&mut parts.extensions))
+    }
+}
+
+impl IntoArgPart for RequestStore {
+    fn into_arg_part() -> ArgPart {
+        ArgPart::Ignore
+    }
+}
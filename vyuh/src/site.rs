@@ -6,8 +6,10 @@ use crate::commands::CommandRegistry;
 use crate::conf::{self, SiteConf};
 use crate::db::{DbError, DbPool, Notify, Pool};
 use crate::emitters::EmitTarget;
+use crate::events::EventBus;
 use crate::logging::{self, LoggingGuard};
 use crate::notifiers::CancellationNotifier;
+use crate::routes::builtin::rate_limit::{RateLimiter, rate_limit_middleware};
 use crate::signals::SignalClient;
 #[cfg(any(feature = "postgres", feature = "mysql", feature = "sqlite"))]
 use crate::tasks::TaskError;
@@ -21,12 +23,12 @@ use axum::extract::{Request, State};
 use axum::middleware::Next;
 use axum::response::Response;
 use chrono_tz::Tz;
+use futures::StreamExt as _;
+use futures::stream::BoxStream;
 use std::net::{SocketAddr, ToSocketAddrs as _};
 use std::{path::PathBuf, sync::Arc};
 use tokio::sync::mpsc;
 use tower_http::catch_panic::CatchPanicLayer;
-use tower_http::compression::CompressionLayer;
-use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 
 use std::path::Path;
@@ -51,7 +53,47 @@ async fn error_report_middleware(State(site): State<Site>, req: Request, next: N
         path,
         headers,
     };
-    site.inner.conf.errors.render(ctx, report).await
+    let route_not_found = response
+        .extensions()
+        .get::<crate::errors::RouteNotFound>()
+        .is_some();
+    let mut rendered = site
+        .inner
+        .conf
+        .errors
+        .render(ctx, report, route_not_found)
+        .await;
+    // Renderers build a fresh response; keep headers that tell the client
+    // how to recover from the error.
+    for name in [
+        axum::http::header::RETRY_AFTER,
+        axum::http::header::ALLOW,
+        axum::http::header::WWW_AUTHENTICATE,
+    ] {
+        if let Some(value) = response.headers().get(&name)
+            && !rendered.headers().contains_key(&name)
+        {
+            rendered.headers_mut().insert(name, value.clone());
+        }
+    }
+    rendered
+}
+
+const ASSET_URL_PREFIX: &str = "/assets";
+
+/// Rejects routes that would be shadowed by, or collide with, the asset mount.
+fn check_asset_prefix(bundle: &Bundle) -> Result<(), String> {
+    let nested = format!("{ASSET_URL_PREFIX}/");
+    match bundle.iter_operations().find(|op| {
+        op.kind == callables::OperationKind::Route
+            && (op.path == ASSET_URL_PREFIX || op.path.starts_with(&nested))
+    }) {
+        Some(op) => Err(format!(
+            "route '{}' at '{}' overlaps the static asset prefix '{}'",
+            op.name, op.path, ASSET_URL_PREFIX
+        )),
+        None => Ok(()),
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -121,6 +163,9 @@ pub enum SiteError {
 
     #[error(transparent)]
     CommandError(#[from] crate::commands::CommandError),
+
+    #[error(transparent)]
+    StorageError(#[from] crate::file_storage::FileStorageError),
 }
 
 struct SiteBuilder {
@@ -179,7 +224,8 @@ impl SiteBuilder {
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
 
-        let make_svc = ServiceExt::<Request>::into_make_service(site.router());
+        let make_svc =
+            ServiceExt::<Request>::into_make_service_with_connect_info::<SocketAddr>(site.router());
 
         let touch_reload = site.inner.conf.touch_reload.clone();
 
@@ -189,6 +235,13 @@ impl SiteBuilder {
             std::time::Duration::from_millis(site.inner.conf.http.shutdown.grace_period_ms),
         );
         let forced = shutdown.force_notifier();
+        // SIGHUP reloads the config file, so without one the default action stands.
+        if site.inner.conf.config_file.is_some() {
+            site.inner
+                .joinset
+                .lock()
+                .spawn(watch::reload_on_hangup(site.clone()));
+        }
         let server =
             axum::serve(listener, make_svc).with_graceful_shutdown(shutdown.clone().graceful());
 
@@ -241,25 +294,48 @@ impl SiteBuilder {
             (bundle, None)
         };
 
+        let bundle = if self.conf.http.health.enabled {
+            bundle.merge(crate::routes::builtin::health::bundle(
+                &self.conf.http.health,
+            ))
+        } else {
+            bundle
+        };
+
+        let bundle = if self.conf.http.route_inspector.enabled {
+            bundle.merge(crate::routes::builtin::inspector::bundle(
+                &self.conf.http.route_inspector,
+            ))
+        } else {
+            bundle
+        };
+
         bundle.validate()?;
 
         let mut router = bundle.to_router();
+        let paths = crate::routes::builtin::allow::allowed_methods(bundle.iter_routes());
+        router =
+            crate::routes::builtin::allow::method_routes(router, &paths, &self.conf.http.allow);
 
         if !bundle.asset_dirs.is_empty() {
+            check_asset_prefix(&bundle).map_err(SiteError::AssetError)?;
             let assets = crate::assets::AssetServe::from_dirs(bundle.asset_dirs.clone(), "public")
-                .strip_url_prefix("assets")
+                .strip_url_prefix(ASSET_URL_PREFIX)
                 .precompressed(true)
                 .with_etag(true);
-            router = router.nest_service("/assets", assets);
+            router = router.nest_service(ASSET_URL_PREFIX, assets);
         }
 
         let mut template_engine = TemplateEngine::new();
+        template_engine.register_filters(&self.conf.templates, timezone, bundle.route_paths());
 
+        let events = EventBus::default();
         let pool = if let Some(pool) = pool {
             DbPool::from_pool(pool)
         } else {
             DbPool::from_conf(&self.conf.database).await?
-        };
+        }
+        .with_events(events.clone());
 
         template_engine.inject_templates(&bundle)?;
 
@@ -314,7 +390,7 @@ impl SiteBuilder {
         };
 
         let mut site = SiteInner {
-            _logging_guard: logging_guard,
+            logging_guard,
             project_dir,
             start_time: std::time::Instant::now(),
             conf: self.conf.clone(),
@@ -325,11 +401,21 @@ impl SiteBuilder {
             authenticator,
             template_engine,
             slash_router,
+            maintenance: Arc::new(crate::middlewares::MaintenanceSwitch::new(
+                self.conf.http.maintenance.clone(),
+            )),
+            rate_limiter: self
+                .conf
+                .http
+                .rate_limit
+                .enabled
+                .then(|| Arc::new(RateLimiter::new(self.conf.http.rate_limit.clone()))),
             joinset: Arc::new(parking_lot::Mutex::new(tokio::task::JoinSet::new())),
             channels: LocalChannelBackend::new(self.conf.channels.clone()),
             console_runtime,
             bundle,
             signal_engine,
+            events,
             emitter_engine,
             commands: command_registry,
             task_engine: task_dispatcher,
@@ -351,15 +437,18 @@ struct SiteInner {
     console_runtime: Option<crate::console::ConsoleRuntime>,
     template_engine: TemplateEngine,
     slash_router: Arc<crate::middlewares::SlashRouter>,
+    maintenance: Arc<crate::middlewares::MaintenanceSwitch>,
+    rate_limiter: Option<Arc<RateLimiter>>,
     timezone: Tz,
     bundle: Bundle,
     signal_engine: crate::signals::SignalEngine,
+    events: EventBus,
     emitter_engine: crate::emitters::EmitterEngine,
     commands: CommandRegistry,
     task_engine: TaskDispatcher<TaskStore>,
     service_engine: services::ServiceEngine,
     shutdown_notifier: CancellationNotifier,
-    _logging_guard: LoggingGuard,
+    logging_guard: LoggingGuard,
     joinset: Arc<parking_lot::Mutex<tokio::task::JoinSet<()>>>,
 }
 
@@ -427,7 +516,7 @@ impl std::fmt::Debug for Site {
 
 impl Site {
     pub async fn build(conf: SiteConf, bundle: impl IntoBundle) -> Result<Self, SiteError> {
-        let builder = SiteBuilder::new(conf);
+        let builder = SiteBuilder::new(conf.load_secret_files()?);
         let site = builder.build(None, bundle).await?;
         let site = Self {
             inner: Arc::new(site),
@@ -455,7 +544,7 @@ impl Site {
         bundle: impl IntoBundle,
         pool: Pool,
     ) -> Result<Self, SiteError> {
-        let builder = SiteBuilder::new(conf);
+        let builder = SiteBuilder::new(conf.load_secret_files()?);
         let site = builder.build(Some(pool), bundle).await?;
         let site = Self {
             inner: Arc::new(site),
@@ -507,6 +596,76 @@ impl Site {
         self.inner.bundle.iter_operations()
     }
 
+    /// See [`Bundle::iter_routes`].
+    pub fn iter_routes(&self) -> impl Iterator<Item = &callables::Operation> {
+        self.inner.bundle.iter_routes()
+    }
+
+    /// See [`Bundle::find_by_path`].
+    pub fn find_by_path(&self, path: &str) -> Vec<&callables::Operation> {
+        self.inner.bundle.find_by_path(path)
+    }
+
+    /// See [`Bundle::iter_namespace`].
+    pub fn iter_namespace<'a>(
+        &'a self,
+        namespace: &str,
+    ) -> impl Iterator<Item = &'a callables::Operation> + 'a {
+        self.inner.bundle.iter_namespace(namespace)
+    }
+
+    /// Turns maintenance mode on or off for subsequent requests.
+    pub fn set_maintenance(&self, enabled: bool) {
+        self.inner.maintenance.set(enabled);
+    }
+
+    pub fn in_maintenance(&self) -> bool {
+        self.inner.maintenance.is_enabled()
+    }
+
+    pub(crate) fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.inner.rate_limiter.as_deref()
+    }
+
+    /// Re-reads the file the site was configured from with
+    /// [`SiteConf::from_file`] and applies its hot-reloadable fields; see
+    /// [`Site::reload_conf`]. The server calls this on `SIGHUP`.
+    pub fn reload(&self) -> Result<(), conf::ConfError> {
+        let Some(path) = &self.inner.conf.config_file else {
+            return Err(conf::ConfError::Other(
+                "site was not configured from a file; nothing to reload".into(),
+            ));
+        };
+        self.reload_conf(&SiteConf::from_file(path)?)
+    }
+
+    /// Applies the hot-reloadable fields of `conf` to the running site: log
+    /// filters, `database.slow_query_ms`, and `http.rate_limit` budgets.
+    /// Everything else, including turning a disabled rate limit on, needs a
+    /// restart. Nothing is applied if `conf` is invalid.
+    pub fn reload_conf(&self, conf: &SiteConf) -> Result<(), conf::ConfError> {
+        let mut errors = Vec::new();
+        conf.http.rate_limit.validate(&mut errors);
+        if !errors.is_empty() {
+            return Err(conf::ConfError::Many(errors));
+        }
+        let log_error = |err: logging::LoggingError| conf::ConfError::InvalidValue {
+            field: "logging".into(),
+            reason: err.to_string(),
+            expected: None,
+        };
+        conf.logging.validate().map_err(log_error)?;
+        self.inner
+            .logging_guard
+            .reload_filters(&conf.logging)
+            .map_err(log_error)?;
+        self.inner.pool.set_slow_query(conf.database.slow_query_ms);
+        if let Some(limiter) = self.rate_limiter() {
+            limiter.reconfigure(conf.http.rate_limit.clone());
+        }
+        Ok(())
+    }
+
     pub fn shutdown_notifier(&self) -> CancellationNotifier {
         self.inner.shutdown_notifier.child()
     }
@@ -552,12 +711,21 @@ impl Site {
     pub(crate) async fn consume_notify(
         &self,
         topics: &[String],
+    ) -> Result<mpsc::Receiver<Notify>, DbError> {
+        let capacity = self.inner.conf.emitters.notify_channel_capacity();
+        self.consume_notify_with(topics, capacity).await
+    }
+
+    async fn consume_notify_with(
+        &self,
+        topics: &[String],
+        capacity: usize,
     ) -> Result<mpsc::Receiver<Notify>, DbError> {
         let conf = &self.inner.conf.emitters;
         self.db()
             .consume_notify(
                 topics,
-                conf.notify_channel_capacity(),
+                capacity.max(1),
                 conf.pgnotify_reconnect_initial_ms(),
                 conf.pgnotify_reconnect_max_ms(),
                 self.shutdown_notifier(),
@@ -565,6 +733,37 @@ impl Site {
             .await
     }
 
+    /// Streams Postgres `NOTIFY` messages on `topics` with their JSON payload
+    /// deserialized into `T`, buffering up to `capacity` messages. Malformed
+    /// payloads are logged and skipped. Other backends return
+    /// `DbError::Unsupported`.
+    pub async fn notify_stream_typed<T>(
+        &self,
+        topics: &[&str],
+        capacity: usize,
+    ) -> Result<BoxStream<'static, Notify<T>>, DbError>
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        let topics = topics.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+        let receiver = self.consume_notify_with(&topics, capacity).await?;
+        let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                let notify = receiver.recv().await?;
+                let channel = notify.channel.clone();
+                match notify.parse::<T>() {
+                    Ok(parsed) => return Some((parsed, receiver)),
+                    Err(err) => tracing::warn!(
+                        channel = channel.as_str(),
+                        "skipping malformed notification payload: {}",
+                        err
+                    ),
+                }
+            }
+        });
+        Ok(stream.boxed())
+    }
+
     pub(crate) fn spawn(&self, fut: impl std::future::Future<Output = ()> + Send + 'static) {
         self.inner.joinset.lock().spawn(fut);
     }
@@ -581,10 +780,28 @@ impl Site {
         self.inner.bundle.reverse(name, args)
     }
 
+    /// Strict [`reverse`](Site::reverse); see [`Bundle::reverse_checked`].
+    pub fn reverse_checked(
+        &self,
+        name: &str,
+        args: &[(&str, &str)],
+    ) -> Result<String, crate::bundles::ReverseError> {
+        self.inner.bundle.reverse_checked(name, args)
+    }
+
     pub fn templates(&self) -> Templates {
         Templates::new(self.clone())
     }
 
+    /// Renders an inline template source with the site template environment.
+    pub fn render_template_string<S: serde::Serialize>(
+        &self,
+        source: &str,
+        context: &S,
+    ) -> Result<String, TemplateError> {
+        self.inner.template_engine.render_str(source, context)
+    }
+
     pub(crate) fn template_engine(&self) -> &TemplateEngine {
         &self.inner.template_engine
     }
@@ -597,14 +814,29 @@ impl Site {
         self.inner.timezone
     }
 
+    /// The current time in the site timezone.
+    pub fn now_local(&self) -> chrono::DateTime<Tz> {
+        chrono::Utc::now().with_timezone(&self.inner.timezone)
+    }
+
     pub fn db(&self) -> DbPool {
         self.inner.pool.clone()
     }
 
+    /// Live connection counts of the site pool.
+    pub fn pool_stats(&self) -> crate::db::PoolStats {
+        self.inner.pool.stats()
+    }
+
     pub fn channels(&self) -> Channels {
         Channels::new(self.inner.channels.clone())
     }
 
+    /// In-process typed broadcast bus shared by handlers and tasks.
+    pub fn events(&self) -> EventBus {
+        self.inner.events.clone()
+    }
+
     pub(crate) fn console_runtime(&self) -> Option<crate::console::ConsoleRuntime> {
         self.inner.console_runtime.clone()
     }
@@ -617,6 +849,17 @@ impl Site {
         self.inner.service_engine.infos()
     }
 
+    pub(crate) fn console_schedules(&self) -> Vec<crate::emitters::ScheduleInfo> {
+        self.inner.emitter_engine.schedules()
+    }
+
+    pub(crate) async fn console_run_schedule(
+        &self,
+        name: &str,
+    ) -> Result<(), crate::emitters::EmitterError> {
+        self.inner.emitter_engine.run_once(self, name).await
+    }
+
     pub(crate) fn console_has_tasks(&self) -> bool {
         self.inner.task_engine.has_tasks()
     }
@@ -628,6 +871,17 @@ impl Site {
         )
     }
 
+    /// Streams an uploaded multipart field into `uploads.dir` and returns the
+    /// stored path. Files larger than `uploads.max_file_bytes` are rejected.
+    pub async fn save_upload(
+        &self,
+        field: axum_extra::extract::multipart::Field,
+    ) -> Result<PathBuf, SiteError> {
+        let max_bytes = self.inner.conf.uploads.max_file_bytes;
+        let saved = self.file_storage().save_field(field, max_bytes).await?;
+        Ok(saved.path)
+    }
+
     pub fn service<T: ?Sized + 'static>(&self) -> Result<Arc<T>, services::ServiceError> {
         self.inner
             .service_engine
@@ -639,13 +893,35 @@ impl Site {
     /// and before running the server.
     pub(crate) fn router(&self) -> axum::Router {
         let http = &self.inner.conf.http;
-        let mut router = self.inner.bundle.to_router();
+        let mut router = self
+            .inner
+            .bundle
+            .to_router()
+            .fallback(crate::errors::not_found_fallback)
+            // Route layers such as `RequireRoles` cannot reach router state.
+            .layer(axum::Extension(self.clone()));
+
+        router = router.layer(axum::middleware::from_fn(crate::flash::flash_middleware));
 
         router = router.layer(axum::middleware::from_fn_with_state(
             self.inner.slash_router.clone(),
             crate::middlewares::slash_middleware,
         ));
 
+        if http.retry.enabled {
+            router = router.layer(axum::middleware::from_fn_with_state(
+                http.retry.clone(),
+                crate::middlewares::retry_middleware,
+            ));
+        }
+
+        if http.csrf.enabled {
+            router = router.layer(axum::middleware::from_fn_with_state(
+                self.clone(),
+                crate::routes::builtin::csrf::csrf_middleware,
+            ));
+        }
+
         if http.security_headers.enabled {
             router = router.layer(axum::middleware::from_fn_with_state(
                 http.security_headers.clone(),
@@ -667,6 +943,13 @@ impl Site {
             ));
         }
 
+        if http.rate_limit.enabled {
+            router = router.layer(axum::middleware::from_fn_with_state(
+                self.clone(),
+                rate_limit_middleware,
+            ));
+        }
+
         if http.request_id.enabled {
             router = router.layer(axum::middleware::from_fn_with_state(
                 http.request_id.clone(),
@@ -674,12 +957,12 @@ impl Site {
             ));
         }
 
-        if http.cors.enabled && http.cors.permissive {
-            router = router.layer(CorsLayer::permissive());
+        if http.cors.enabled {
+            router = router.layer(http.cors.layer());
         }
 
         if http.compression.enabled {
-            router = router.layer(CompressionLayer::new());
+            router = router.layer(http.compression.layer());
         }
 
         if http.trace.enabled {
@@ -690,6 +973,11 @@ impl Site {
             router = router.layer(CatchPanicLayer::new());
         }
 
+        router = router.layer(axum::middleware::from_fn_with_state(
+            self.inner.maintenance.clone(),
+            crate::middlewares::maintenance_middleware,
+        ));
+
         router = router.layer(axum::middleware::from_fn_with_state(
             self.clone(),
             error_report_middleware,
@@ -737,7 +1025,12 @@ impl axum::extract::FromRequestParts<Site> for SiteConfig {
 
 #[cfg(test)]
 mod tests {
-    use super::Site;
+    use super::{Site, SiteError};
+    use crate::{
+        SiteConf, bundles,
+        console::ConsoleConf,
+        routes::{Json, Methods, RouteConf},
+    };
 
     fn strings(args: &[&str]) -> Vec<String> {
         args.iter().map(|arg| (*arg).to_string()).collect()
@@ -769,4 +1062,27 @@ mod tests {
         assert_eq!(command, "greet");
         assert_eq!(args, strings(&["--name", "Vyuh"]));
     }
+
+    async fn shadowed() -> Json<&'static str> {
+        Json("app")
+    }
+
+    #[tokio::test]
+    async fn routes_under_asset_prefix_are_rejected() {
+        let conf = SiteConf::default()
+            .log_init(false)
+            .console(ConsoleConf::default().enabled(true));
+        let bundle = bundles::bundle([bundles::route(
+            shadowed,
+            RouteConf {
+                name: "shadowed".into(),
+                methods: Methods::GET,
+                path: "/assets/app.css".into(),
+                slash: None,
+            },
+        )]);
+
+        let err = Site::build(conf, bundle).await.err().unwrap();
+        assert!(matches!(err, SiteError::AssetError(msg) if msg.contains("/assets/app.css")));
+    }
 }
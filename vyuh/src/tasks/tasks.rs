@@ -679,6 +679,18 @@ impl<S: crate::tasks::store::AbstractTaskStore + Send + Sync + 'static> TaskClie
         self.dispatcher.submit_with(input, conf).await
     }
 
+    /// Submits `input` to the task registered as `name`, checked against its
+    /// input schema. Useful when the input type is not known statically.
+    pub async fn submit_named<T: Serialize + 'static>(
+        &self,
+        name: &str,
+        input: T,
+    ) -> Result<uuid::Uuid, TaskError> {
+        self.dispatcher
+            .submit_registered(name, input, TaskOptions::default())
+            .await
+    }
+
     pub async fn resume<T: Serialize>(&self, id: uuid::Uuid, input: T) -> Result<u64, TaskError> {
         self.dispatcher.resume(id, input).await
     }
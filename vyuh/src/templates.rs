@@ -5,7 +5,19 @@ use crate::{
     embed,
 };
 use axum::response::IntoResponse;
+use indexmap::IndexMap;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+mod filters;
+
+/// Pages `cached_html` keeps; the oldest is evicted past this.
+const MAX_RENDERED_PAGES: usize = 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemplateConf {
@@ -15,6 +27,9 @@ pub struct TemplateConf {
     pub lstrip_blocks: bool,
     pub keep_trailing_newline: bool,
     pub date_formats: TemplateDateFormats,
+    /// Custom filters and globals; set in code, never loaded from config.
+    #[serde(skip)]
+    pub extensions: TemplateExtensions,
 }
 
 impl Default for TemplateConf {
@@ -26,10 +41,45 @@ impl Default for TemplateConf {
             lstrip_blocks: false,
             keep_trailing_newline: true,
             date_formats: TemplateDateFormats::default(),
+            extensions: TemplateExtensions::default(),
         }
     }
 }
 
+impl TemplateConf {
+    /// Registers a filter, e.g. `{{ price | cents }}`. Replaces a built-in
+    /// filter of the same name.
+    pub fn with_filter<F, Rv, Args>(mut self, name: impl Into<String>, f: F) -> Self
+    where
+        F: minijinja::functions::Function<Rv, Args>,
+        Rv: minijinja::value::FunctionResult,
+        Args: for<'a> minijinja::value::FunctionArgs<'a>,
+    {
+        let filter = minijinja::Value::from_function(f);
+        self.extensions.filters.push((name.into(), filter));
+        self
+    }
+
+    /// Registers a global value or function (`minijinja::Value::from_function`).
+    /// Replaces the built-in `url` global if named `url`.
+    pub fn with_global(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<minijinja::Value>,
+    ) -> Self {
+        self.extensions.globals.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Filters and globals added with [`TemplateConf::with_filter`] and
+/// [`TemplateConf::with_global`].
+#[derive(Debug, Clone, Default)]
+pub struct TemplateExtensions {
+    filters: Vec<(String, minijinja::Value)>,
+    globals: Vec<(String, minijinja::Value)>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TemplateAutoEscape {
@@ -94,12 +144,42 @@ pub enum TemplateError {
 
 pub struct TemplateEngine {
     env: minijinja::Environment<'static>,
+    rendered: Mutex<IndexMap<String, (Instant, Arc<str>)>>,
 }
 
 impl TemplateEngine {
     pub fn new() -> Self {
-        let env = minijinja::Environment::new();
-        TemplateEngine { env }
+        TemplateEngine {
+            env: minijinja::Environment::new(),
+            rendered: Mutex::new(IndexMap::new()),
+        }
+    }
+
+    /// Registers the `number`, `currency`, and `date` filters, formatting
+    /// dates in `tz` with `conf.date_formats`, the `url(name, **args)` global
+    /// over `routes`, the `timezone` name global, and then the custom
+    /// extensions from `conf`.
+    pub(crate) fn register_filters(
+        &mut self,
+        conf: &TemplateConf,
+        tz: chrono_tz::Tz,
+        routes: BTreeMap<String, String>,
+    ) {
+        filters::register(&mut self.env, &conf.date_formats, tz);
+        self.env.add_function("url", filters::url(routes));
+        self.env.add_global("timezone", tz.name());
+        for (name, filter) in &conf.extensions.filters {
+            let filter = filter.clone();
+            self.env.add_filter(
+                name.clone(),
+                move |state: &minijinja::State, args: minijinja::value::Rest<minijinja::Value>| {
+                    filter.call(state, &args)
+                },
+            );
+        }
+        for (name, value) in &conf.extensions.globals {
+            self.env.add_global(name.clone(), value.clone());
+        }
     }
 
     pub fn render<S: serde::Serialize>(
@@ -116,6 +196,17 @@ impl TemplateEngine {
             .map_err(|e| TemplateError::RenderError(e).into())
     }
 
+    /// Renders an ad-hoc template source with the same environment as named
+    /// templates. The source is compiled for this call only and never
+    /// registered, so distinct sources cannot grow the engine.
+    pub fn render_str<S: serde::Serialize>(
+        &self,
+        source: &str,
+        context: &S,
+    ) -> Result<String, TemplateError> {
+        Ok(self.env.render_str(source, context)?)
+    }
+
     pub fn html<S: serde::Serialize>(
         &self,
         template_name: &str,
@@ -124,6 +215,30 @@ impl TemplateEngine {
         Ok(axum::response::Html(self.render(template_name, context)?))
     }
 
+    /// Returns the page cached under `key` if it has not expired.
+    pub(crate) fn cached(&self, key: &str) -> Option<Arc<str>> {
+        let rendered = self.rendered.lock();
+        let (expires, html) = rendered.get(key)?;
+        (*expires > Instant::now()).then(|| html.clone())
+    }
+
+    /// Caches `html` under `key` for `ttl`, dropping expired entries and
+    /// then the oldest ones beyond `MAX_RENDERED_PAGES`.
+    pub(crate) fn store_cached(&self, key: &str, ttl: Duration, html: Arc<str>) {
+        let now = Instant::now();
+        let mut rendered = self.rendered.lock();
+        rendered.retain(|_, (expires, _)| *expires > now);
+        rendered.shift_remove(key);
+        while rendered.len() >= MAX_RENDERED_PAGES {
+            rendered.shift_remove_index(0);
+        }
+        rendered.insert(key.to_string(), (now + ttl, html));
+    }
+
+    pub(crate) fn invalidate_cached(&self, key: &str) {
+        self.rendered.lock().shift_remove(key);
+    }
+
     pub fn exists(&self, template_name: &str) -> bool {
         self.env.templates().any(|(name, _)| name == template_name)
     }
@@ -424,6 +539,15 @@ impl Templates {
         self.site.template_engine().render(template_name, context)
     }
 
+    /// Renders an ad-hoc template source; see [`TemplateEngine::render_str`].
+    pub fn render_str<S: serde::Serialize>(
+        &self,
+        source: &str,
+        context: &S,
+    ) -> Result<String, TemplateError> {
+        self.site.template_engine().render_str(source, context)
+    }
+
     pub fn html<S: serde::Serialize>(
         &self,
         template_name: &str,
@@ -432,6 +556,34 @@ impl Templates {
         Ok(axum::response::Html(self.render(template_name, context)?))
     }
 
+    /// Returns the HTML cached under `key`, or runs `render` and caches its
+    /// output for `ttl`. While the entry is fresh, `render` is not called, so
+    /// neither its data loading nor template rendering runs. Keys are shared
+    /// site-wide; include anything the page varies by (user, locale, query).
+    pub async fn cached_html<E, F, Fut>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        render: F,
+    ) -> Result<axum::response::Html<String>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<axum::response::Html<String>, E>>,
+    {
+        let engine = self.site.template_engine();
+        if let Some(html) = engine.cached(key) {
+            return Ok(axum::response::Html(html.to_string()));
+        }
+        let axum::response::Html(html) = render().await?;
+        engine.store_cached(key, ttl, Arc::from(html.as_str()));
+        Ok(axum::response::Html(html))
+    }
+
+    /// Drops the page cached under `key` by [`Templates::cached_html`].
+    pub fn invalidate(&self, key: &str) {
+        self.site.template_engine().invalidate_cached(key);
+    }
+
     pub fn exists(&self, template_name: &str) -> bool {
         self.site.template_engine().exists(template_name)
     }
@@ -575,6 +727,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inline_templates_render_and_stay_hidden() {
+        let engine = TemplateEngine::new();
+        let source = "Hi {{ name }}";
+
+        for name in ["Ada", "Bo"] {
+            assert_eq!(
+                engine
+                    .render_str(source, &serde_json::json!({ "name": name }))
+                    .unwrap(),
+                format!("Hi {name}")
+            );
+        }
+        assert!(engine.names().is_empty());
+        assert!(matches!(
+            engine.render_str("{% if %}", &()),
+            Err(TemplateError::RenderError(_))
+        ));
+    }
+
+    #[test]
+    fn cached_pages_are_bounded() {
+        let engine = TemplateEngine::new();
+        let ttl = Duration::from_secs(60);
+        for i in 0..=MAX_RENDERED_PAGES {
+            engine.store_cached(&format!("page-{i}"), ttl, Arc::from("html"));
+        }
+
+        assert_eq!(engine.rendered.lock().len(), MAX_RENDERED_PAGES);
+        assert!(engine.cached("page-0").is_none());
+        assert!(
+            engine
+                .cached(&format!("page-{MAX_RENDERED_PAGES}"))
+                .is_some()
+        );
+    }
+
     #[test]
     fn duplicate_template_names_are_rejected() {
         let first = tempfile::tempdir().unwrap();
@@ -0,0 +1,126 @@
+//! Formatting filters and the `url` global registered on every site
+//! template environment.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use minijinja::{Environment, Error, ErrorKind, Value, value::Kwargs};
+
+use super::TemplateDateFormats;
+
+pub(crate) fn register(env: &mut Environment<'static>, formats: &TemplateDateFormats, tz: Tz) {
+    env.add_filter("number", number);
+    env.add_filter("currency", currency);
+    let pattern = formats.date.clone();
+    env.add_filter("date", move |value: Value, format: Option<String>| {
+        date(&value, format.as_deref().unwrap_or(&pattern), tz)
+    });
+}
+
+/// `{{ url("order", id=7, tab="items") }}` -> `/orders/7?tab=items`, reversing
+/// a named route. Unknown routes and missing path parameters are errors.
+pub(crate) fn url(
+    routes: BTreeMap<String, String>,
+) -> impl Fn(String, Kwargs) -> Result<String, Error> + Send + Sync + 'static {
+    move |name: String, kwargs: Kwargs| {
+        let template = routes
+            .get(&name)
+            .ok_or_else(|| invalid(format!("url: unknown route '{name}'")))?;
+        let mut values = Vec::new();
+        for key in kwargs.args() {
+            let value: Value = kwargs.get(key)?;
+            let value = match value.as_str() {
+                Some(text) => text.to_string(),
+                None => value.to_string(),
+            };
+            values.push((key, value));
+        }
+        let args: Vec<(&str, &str)> = values.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        crate::bundles::reverse_path(template, &args)
+            .ok_or_else(|| invalid(format!("url: missing path parameters for '{name}'")))
+    }
+}
+
+/// `{{ 1234.5 | number }}` -> `1,234.50`; integers default to no decimals.
+fn number(value: Value, decimals: Option<usize>) -> Result<String, Error> {
+    let decimals = decimals.unwrap_or(if value.as_i64().is_some() { 0 } else { 2 });
+    Ok(group_thousands(to_f64(value)?, decimals))
+}
+
+/// `{{ amount | currency('USD') }}` -> `$1,234.50`; unknown codes are prefixed.
+fn currency(value: Value, code: Option<String>) -> Result<String, Error> {
+    let code = code.unwrap_or_else(|| "USD".into()).to_ascii_uppercase();
+    let (symbol, decimals) = match code.as_str() {
+        "USD" | "CAD" | "AUD" => ("$", 2),
+        "EUR" => ("€", 2),
+        "GBP" => ("£", 2),
+        "INR" => ("₹", 2),
+        "JPY" => ("¥", 0),
+        "KRW" => ("₩", 0),
+        _ => ("", 2),
+    };
+    let amount = to_f64(value)?;
+    let digits = group_thousands(amount.abs(), decimals);
+    let sign = if amount < 0.0 && digits.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        "-"
+    } else {
+        ""
+    };
+    Ok(match symbol {
+        "" => format!("{sign}{code} {digits}"),
+        symbol => format!("{sign}{symbol}{digits}"),
+    })
+}
+
+/// Formats an RFC 3339 string or Unix timestamp in the site timezone.
+/// Plain `YYYY-MM-DD` dates are formatted as-is.
+fn date(value: &Value, pattern: &str, tz: Tz) -> Result<String, Error> {
+    if let Some(secs) = value.as_i64() {
+        let at = DateTime::<Utc>::from_timestamp(secs, 0)
+            .ok_or_else(|| invalid(format!("timestamp {secs} is out of range")))?;
+        return Ok(at.with_timezone(&tz).format(pattern).to_string());
+    }
+    let text = value
+        .as_str()
+        .ok_or_else(|| invalid("date filter expects a string or timestamp"))?;
+    if let Ok(at) = DateTime::parse_from_rfc3339(text) {
+        return Ok(at.with_timezone(&tz).format(pattern).to_string());
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map(|day| day.format(pattern).to_string())
+        .map_err(|err| invalid(format!("invalid date '{text}': {err}")))
+}
+
+fn to_f64(value: Value) -> Result<f64, Error> {
+    f64::try_from(value).map_err(|_| invalid("expected a number"))
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::new(ErrorKind::InvalidOperation, message.into())
+}
+
+fn group_thousands(value: f64, decimals: usize) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (int, frac) = formatted
+        .split_once('.')
+        .map_or((formatted.as_str(), None), |(int, frac)| (int, Some(frac)));
+    let mut out = String::with_capacity(formatted.len() + int.len() / 3 + 1);
+    if value < 0.0 && formatted.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        out.push('-');
+    }
+    for (i, ch) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    if let Some(frac) = frac {
+        out.push('.');
+        out.push_str(frac);
+    }
+    out
+}
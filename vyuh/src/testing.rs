@@ -1,13 +1,17 @@
+use crate::bundles::IntoBundle;
 use crate::db::{DbConf, Pool};
 use crate::{Site, SiteConf};
 use axum::Router;
 use axum::body::{self, Body, Bytes};
-use axum::http::{Method, Request, Response};
+use axum::http::{HeaderMap, Method, Request, Response, header};
+use axum_extra::extract::cookie::Cookie;
+use parking_lot::Mutex;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_json::{self, Value, value::to_value};
 use std::collections::BTreeMap;
 use std::ops::Deref;
+use std::sync::Arc;
 use tower::ServiceExt;
 
 pub use sqlx::{test, test_block_on};
@@ -46,6 +50,111 @@ impl TestClient {
     pub fn patch(&self, path: &str) -> TestRequestBuilder {
         self.request(Method::PATCH, path)
     }
+
+    /// A [`TestSession`] on this client's app, starting with no cookies.
+    pub fn session(&self) -> TestSession {
+        TestSession {
+            app: self.app.clone(),
+            jar: CookieStore::default(),
+        }
+    }
+}
+
+/// Something that issues test requests: a [`TestClient`] or a [`TestSession`].
+pub trait TestRequester {
+    fn request(&self, method: Method, path: &str) -> TestRequestBuilder;
+}
+
+impl TestRequester for TestClient {
+    fn request(&self, method: Method, path: &str) -> TestRequestBuilder {
+        TestClient::request(self, method, path)
+    }
+}
+
+impl TestRequester for TestSession {
+    fn request(&self, method: Method, path: &str) -> TestRequestBuilder {
+        TestSession::request(self, method, path)
+    }
+}
+
+/// Cookies a [`TestSession`] has been given, by name.
+type CookieStore = Arc<Mutex<BTreeMap<String, String>>>;
+
+/// A browser-like client: `Set-Cookie` headers from each response are stored
+/// and sent back as `Cookie` on later requests, and cleared cookies are
+/// forgotten. Cookie attributes such as `Path` and `Domain` are ignored.
+#[derive(Clone)]
+pub struct TestSession {
+    app: Router,
+    jar: CookieStore,
+}
+
+impl TestSession {
+    pub fn request(&self, method: Method, path: &str) -> TestRequestBuilder {
+        let mut builder = TestRequestBuilder::new(self.app.clone(), method, path);
+        builder.jar = Some(self.jar.clone());
+        builder
+    }
+
+    pub fn get(&self, path: &str) -> TestRequestBuilder {
+        self.request(Method::GET, path)
+    }
+    pub fn post(&self, path: &str) -> TestRequestBuilder {
+        self.request(Method::POST, path)
+    }
+    pub fn put(&self, path: &str) -> TestRequestBuilder {
+        self.request(Method::PUT, path)
+    }
+    pub fn delete(&self, path: &str) -> TestRequestBuilder {
+        self.request(Method::DELETE, path)
+    }
+    pub fn patch(&self, path: &str) -> TestRequestBuilder {
+        self.request(Method::PATCH, path)
+    }
+
+    /// The stored value of cookie `name`.
+    pub fn cookie(&self, name: &str) -> Option<String> {
+        self.jar.lock().get(name).cloned()
+    }
+
+    pub fn set_cookie(&self, name: &str, value: &str) {
+        self.jar.lock().insert(name.to_string(), value.to_string());
+    }
+
+    pub fn clear_cookies(&self) {
+        self.jar.lock().clear();
+    }
+}
+
+/// `name=value; ...` for every stored cookie, or `None` when there are none.
+fn cookie_header(jar: &CookieStore) -> Option<String> {
+    let jar = jar.lock();
+    if jar.is_empty() {
+        return None;
+    }
+    let pairs: Vec<String> = jar.iter().map(|(k, v)| format!("{k}={v}")).collect();
+    Some(pairs.join("; "))
+}
+
+/// Applies a response's `Set-Cookie` headers to `jar`.
+fn store_cookies(jar: &CookieStore, headers: &HeaderMap) {
+    let mut jar = jar.lock();
+    let cookies = headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .filter_map(|value| Cookie::parse(value.to_string()).ok());
+    for cookie in cookies {
+        let expired = cookie.max_age().is_some_and(|age| age.is_zero())
+            || cookie
+                .expires_datetime()
+                .is_some_and(|at| at <= time::OffsetDateTime::now_utc());
+        if expired {
+            jar.remove(cookie.name());
+        } else {
+            jar.insert(cookie.name().to_string(), cookie.value().to_string());
+        }
+    }
 }
 
 impl Drop for TestClient {
@@ -60,6 +169,7 @@ pub struct TestRequestBuilder {
     path: String,
     headers: Vec<(String, String)>,
     body: Option<Body>,
+    jar: Option<CookieStore>,
 }
 
 impl TestRequestBuilder {
@@ -70,6 +180,7 @@ impl TestRequestBuilder {
             path: path.to_string(),
             headers: Vec::new(),
             body: None,
+            jar: None,
         }
     }
 
@@ -102,7 +213,16 @@ impl TestRequestBuilder {
     }
 
     pub async fn send(self) -> TestResponse {
+        let (method, path) = (self.method.clone(), self.path.clone());
         let mut req = Request::builder().method(self.method).uri(self.path);
+        let jar_cookies = self.jar.as_ref().and_then(cookie_header);
+        let has_cookie = self
+            .headers
+            .iter()
+            .any(|(k, _)| k.eq_ignore_ascii_case("cookie"));
+        if let (Some(cookies), false) = (jar_cookies, has_cookie) {
+            req = req.header(header::COOKIE, cookies);
+        }
         for (k, v) in self.headers {
             req = req.header(&k, &v);
         }
@@ -110,13 +230,18 @@ impl TestRequestBuilder {
             .body(self.body.unwrap_or_else(|| Body::empty()))
             .unwrap();
         let resp = self.app.clone().oneshot(req).await.unwrap();
-        TestResponse { resp }
+        if let Some(jar) = &self.jar {
+            store_cookies(jar, resp.headers());
+        }
+        TestResponse { resp, method, path }
     }
 }
 
 #[derive(Debug)]
 pub struct TestResponse {
     resp: Response<Body>,
+    method: Method,
+    path: String,
 }
 
 impl TestResponse {
@@ -126,6 +251,17 @@ impl TestResponse {
     pub fn header(&self, name: &str) -> Option<&axum::http::HeaderValue> {
         self.resp.headers().get(name)
     }
+    /// Returns the last `Set-Cookie` named `name`, if the response sets one.
+    pub fn cookie(&self, name: &str) -> Option<Cookie<'static>> {
+        self.resp
+            .headers()
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .rev()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| Cookie::parse(value.to_string()).ok())
+            .find(|cookie| cookie.name() == name)
+    }
     pub async fn text(self) -> String {
         let bytes = body::to_bytes(self.resp.into_body(), usize::MAX)
             .await
@@ -184,6 +320,79 @@ impl TestResponse {
     pub fn assert_bad_request(self) -> Self {
         self.assert_status(axum::http::StatusCode::BAD_REQUEST)
     }
+
+    /// Asserts the response is a `status` redirect to `location`.
+    pub fn assert_redirect(self, status: axum::http::StatusCode, location: &str) -> Self {
+        let response = self.assert_status(status);
+        let actual = response.header("location").and_then(|v| v.to_str().ok());
+        assert_eq!(actual, Some(location), "Unexpected redirect location");
+        response
+    }
+
+    /// Requests this redirect's `Location` through `client`, as a browser
+    /// would: 301, 302 and 303 become a `GET`, while 307 and 308 repeat the
+    /// method without a body. Pass a [`TestSession`] to keep its cookies,
+    /// including any set by this response.
+    ///
+    /// Panics when the response is not a redirect.
+    pub async fn follow_redirect(self, client: &impl TestRequester) -> TestResponse {
+        use axum::http::StatusCode;
+        let status = self.status();
+        assert!(status.is_redirection(), "Expected a redirect, got {status}");
+        let location = self
+            .header("location")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_else(|| panic!("Redirect {status} has no Location header"));
+        let target = resolve_location(&self.path, location);
+        let method = match status {
+            StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT => self.method.clone(),
+            _ => Method::GET,
+        };
+        client.request(method, &target).send().await
+    }
+
+    /// Asserts the response sets a non-empty cookie named `name`.
+    pub fn assert_cookie(self, name: &str) -> Self {
+        let cookie = self.cookie(name);
+        assert!(
+            cookie.as_ref().is_some_and(|c| !c.value().is_empty()),
+            "Expected cookie {name} to be set, got {cookie:?}"
+        );
+        self
+    }
+
+    /// Asserts the response clears the cookie `name` with `Max-Age=0` or a
+    /// past `Expires`.
+    pub fn assert_cookie_cleared(self, name: &str) -> Self {
+        let cookie = self.cookie(name);
+        let cleared = cookie.as_ref().is_some_and(|c| {
+            c.max_age().is_some_and(|age| age.is_zero())
+                || c.expires_datetime()
+                    .is_some_and(|at| at <= time::OffsetDateTime::now_utc())
+        });
+        assert!(
+            cleared,
+            "Expected cookie {name} to be cleared, got {cookie:?}"
+        );
+        self
+    }
+}
+
+/// Resolves a `Location` against the request path; absolute URLs keep only
+/// their path and query, since every test request goes to the same app.
+fn resolve_location(from: &str, location: &str) -> String {
+    if let Some((_, rest)) = location.split_once("://") {
+        return match rest.find('/') {
+            Some(i) => rest[i..].to_string(),
+            None => "/".to_string(),
+        };
+    }
+    if location.starts_with('/') {
+        return location.to_string();
+    }
+    let base = from.split(['?', '#']).next().unwrap_or_default();
+    let dir = base.rfind('/').map_or("/", |i| &base[..=i]);
+    format!("{dir}{location}")
 }
 
 impl TestClient {
@@ -320,100 +529,182 @@ impl Drop for MockDb {
 /// ```
 pub async fn mock_db() -> MockDb {
     #[cfg(feature = "postgres")]
-    {
-        let base_url = std::env::var("TEST_DATABASE_URL")
-            .unwrap_or_else(|_| "postgres://localhost".to_string());
+    let default_url = "postgres://localhost";
+    #[cfg(all(feature = "mysql", not(feature = "postgres")))]
+    let default_url = "mysql://localhost";
+    #[cfg(not(any(feature = "postgres", feature = "mysql")))]
+    let default_url = "sqlite::memory:";
+    let base_url = std::env::var("TEST_DATABASE_URL").unwrap_or_else(|_| default_url.to_string());
+    mock_db_at(base_url).await
+}
 
-        let db_name = format!("vyuh_test_{}", uuid::Uuid::now_v7().simple());
+/// [`mock_db`] on a fresh, uniquely named database on the server at `base_url`.
+#[cfg(feature = "postgres")]
+async fn mock_db_at(base_url: String) -> MockDb {
+    let db_name = format!("vyuh_test_{}", uuid::Uuid::now_v7().simple());
 
-        let root_pool = sqlx::PgPool::connect(&base_url)
-            .await
-            .expect("Failed to connect to postgres");
+    let root_pool = sqlx::PgPool::connect(&base_url)
+        .await
+        .expect("Failed to connect to postgres");
 
-        sqlx::query(&format!("CREATE DATABASE \"{}\"", db_name))
-            .execute(&root_pool)
-            .await
-            .expect("Failed to create test database");
+    sqlx::query(&format!("CREATE DATABASE \"{}\"", db_name))
+        .execute(&root_pool)
+        .await
+        .expect("Failed to create test database");
 
-        root_pool.close().await;
+    root_pool.close().await;
 
-        let test_url = if base_url.contains('/') {
-            let parts: Vec<&str> = base_url.rsplitn(2, '/').collect();
-            format!("{}/{}", parts[1], db_name)
-        } else {
-            format!("{}/{}", base_url, db_name)
-        };
+    let test_url = if base_url.contains('/') {
+        let parts: Vec<&str> = base_url.rsplitn(2, '/').collect();
+        format!("{}/{}", parts[1], db_name)
+    } else {
+        format!("{}/{}", base_url, db_name)
+    };
 
-        let pool = sqlx::PgPool::connect(&test_url)
-            .await
-            .expect("Failed to connect to test database");
+    let pool = sqlx::PgPool::connect(&test_url)
+        .await
+        .expect("Failed to connect to test database");
 
-        MockDb {
-            pool,
-            db_name,
-            base_url,
-        }
+    MockDb {
+        pool,
+        db_name,
+        base_url,
     }
+}
 
-    #[cfg(feature = "mysql")]
-    {
-        let base_url =
-            std::env::var("TEST_DATABASE_URL").unwrap_or_else(|_| "mysql://localhost".to_string());
+/// [`mock_db`] on a fresh, uniquely named database on the server at `base_url`.
+#[cfg(all(feature = "mysql", not(feature = "postgres")))]
+async fn mock_db_at(base_url: String) -> MockDb {
+    let db_name = format!("vyuh_test_{}", uuid::Uuid::now_v7().simple());
 
-        let db_name = format!("vyuh_test_{}", uuid::Uuid::now_v7().simple());
+    let root_pool = sqlx::MySqlPool::connect(&base_url)
+        .await
+        .expect("Failed to connect to mysql");
 
-        let root_pool = sqlx::MySqlPool::connect(&base_url)
-            .await
-            .expect("Failed to connect to mysql");
+    sqlx::query(&format!("CREATE DATABASE `{}`", db_name))
+        .execute(&root_pool)
+        .await
+        .expect("Failed to create test database");
 
-        sqlx::query(&format!("CREATE DATABASE `{}`", db_name))
-            .execute(&root_pool)
-            .await
-            .expect("Failed to create test database");
+    root_pool.close().await;
 
-        root_pool.close().await;
+    let test_url = if base_url.contains('/') {
+        let parts: Vec<&str> = base_url.rsplitn(2, '/').collect();
+        format!("{}/{}", parts[1], db_name)
+    } else {
+        format!("{}/{}", base_url, db_name)
+    };
 
-        let test_url = if base_url.contains('/') {
-            let parts: Vec<&str> = base_url.rsplitn(2, '/').collect();
-            format!("{}/{}", parts[1], db_name)
-        } else {
-            format!("{}/{}", base_url, db_name)
-        };
+    let pool = sqlx::MySqlPool::connect(&test_url)
+        .await
+        .expect("Failed to connect to test database");
 
-        let pool = sqlx::MySqlPool::connect(&test_url)
-            .await
-            .expect("Failed to connect to test database");
+    MockDb {
+        pool,
+        db_name,
+        base_url,
+    }
+}
 
-        MockDb {
-            pool,
-            db_name,
-            base_url,
-        }
+/// [`mock_db`] on SQLite, which ignores `base_url` and uses a
+/// single-connection in-memory database.
+#[cfg(all(feature = "sqlite", not(any(feature = "postgres", feature = "mysql"))))]
+async fn mock_db_at(_base_url: String) -> MockDb {
+    // Every in-memory connection is its own database, so keep just one.
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(":memory:")
+        .await
+        .expect("Failed to create in-memory sqlite database");
+
+    MockDb {
+        pool,
+        db_name: String::new(),
+        base_url: String::new(),
     }
+}
 
-    #[cfg(feature = "sqlite")]
-    {
-        let pool = sqlx::SqlitePool::connect(":memory:")
-            .await
-            .expect("Failed to create in-memory sqlite database");
+/// [`mock_db`] without a backend feature: an in-memory SQLite database that
+/// ignores `base_url`.
+#[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
+async fn mock_db_at(_base_url: String) -> MockDb {
+    let pool = sqlx::SqlitePool::connect(":memory:")
+        .await
+        .expect("Failed to create in-memory sqlite database");
 
-        MockDb {
-            pool,
-            db_name: String::new(),
-            base_url: String::new(),
-        }
+    MockDb {
+        pool,
+        db_name: String::new(),
+        base_url: String::new(),
     }
+}
 
-    #[cfg(not(any(feature = "postgres", feature = "mysql", feature = "sqlite")))]
-    {
-        let pool = sqlx::SqlitePool::connect(":memory:")
-            .await
-            .expect("Failed to create in-memory sqlite database");
+/// A [`Site`] on its own test database, from [`test_site_with_db`].
+///
+/// Derefs to the site; dropping it shuts the site down and drops the database.
+pub struct TestSite {
+    site: Site,
+    db: MockDb,
+}
 
-        MockDb {
-            pool,
-            db_name: String::new(),
-            base_url: String::new(),
-        }
+impl TestSite {
+    pub fn site(&self) -> &Site {
+        &self.site
+    }
+
+    /// Name of the test database; empty on SQLite.
+    pub fn db_name(&self) -> &str {
+        &self.db.db_name
+    }
+
+    /// A [`TestClient`] for this site.
+    pub fn client(&self) -> TestClient {
+        TestClient::new(self.site.clone())
+    }
+}
+
+impl Deref for TestSite {
+    type Target = Site;
+
+    fn deref(&self) -> &Self::Target {
+        &self.site
+    }
+}
+
+impl Drop for TestSite {
+    fn drop(&mut self) {
+        self.site.shutdown();
     }
 }
+
+/// Builds a site on a fresh, uniquely named database created on the server
+/// in `TEST_DATABASE_URL`, after running `schema` (one or more SQL
+/// statements) against it. Returns `None` when the variable is not set, so
+/// the test can skip:
+///
+/// ```ignore
+/// let Some(site) = test_site_with_db(app_bundle(), SCHEMA).await else {
+///     return;
+/// };
+/// ```
+pub async fn test_site_with_db(bundle: impl IntoBundle, schema: &str) -> Option<TestSite> {
+    let Ok(base_url) = std::env::var("TEST_DATABASE_URL") else {
+        tracing::info!("skipping database test: TEST_DATABASE_URL is not set");
+        return None;
+    };
+    let db = mock_db_at(base_url).await;
+    if !schema.trim().is_empty() {
+        sqlx::raw_sql(schema)
+            .execute(db.pool())
+            .await
+            .expect("Failed to apply test schema");
+    }
+    let conf = SiteConf {
+        log_init: false,
+        ..SiteConf::default()
+    };
+    let site = Site::test(conf, bundle, db.pool().clone())
+        .await
+        .expect("Failed to build test site");
+    Some(TestSite { site, db })
+}
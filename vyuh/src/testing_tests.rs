@@ -1,5 +1,25 @@
+use crate::bundles::Bundle;
+use crate::db::{self, FilteredBuilder};
 #[cfg(feature = "postgres")]
 use crate::testing::mock_db;
+use crate::testing::test_site_with_db;
+
+#[tokio::test]
+async fn test_site_with_db_runs_schema_on_a_fresh_database() {
+    let schema = "CREATE TABLE notes (body TEXT NOT NULL); \
+                  INSERT INTO notes (body) VALUES ('seeded');";
+    let Some(site) = test_site_with_db(Bundle::new(), schema).await else {
+        return;
+    };
+    let mut pool = site.db().clone();
+    let count = db::select("notes")
+        .filter("body = 'seeded'")
+        .with_deleted()
+        .count(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
 
 #[tokio::test]
 #[cfg(feature = "postgres")]
@@ -5,6 +5,7 @@ use axum::{
     http::request::Parts,
 };
 
+use crate::db::{DbError, IntegrityKind};
 use crate::errors::ErrorReport;
 pub use vyuh_macros::Validate;
 
@@ -163,6 +164,14 @@ impl ValidationReport {
         self.push(Path::root(), invalid);
     }
 
+    /// Pushes an issue keyed to a top-level field, as the derive does.
+    ///
+    /// Prefer this over `push_root` when the failure belongs to one field;
+    /// root issues render under `non_field_errors`.
+    pub fn push_field(&mut self, name: impl Into<Cow<'static, str>>, invalid: ValidationError) {
+        self.push(Path::root().at_field(name), invalid);
+    }
+
     pub fn merge(&mut self, other: ValidationReport, prefix: Option<PathSeg>) {
         for mut issue in other.issues {
             if let Some(p) = &prefix {
@@ -172,6 +181,21 @@ impl ValidationReport {
         }
     }
 
+    /// Drops issues repeating an earlier `(path, code)` pair, keeping the first.
+    ///
+    /// Useful when a derived and a hand-written validator both flag a field.
+    pub fn dedup_codes(&mut self) {
+        let mut seen = std::collections::HashSet::new();
+        self.issues
+            .retain(|issue| seen.insert((issue.path.clone(), issue.invalid.code.clone())));
+    }
+
+    /// Consuming variant of `dedup_codes`.
+    pub fn deduped(mut self) -> Self {
+        self.dedup_codes();
+        self
+    }
+
     pub fn has_error(&self, field: &str) -> bool {
         self.issues.iter().any(|i| i.path.to_string() == field)
     }
@@ -251,10 +275,18 @@ impl ValidationReport {
     /// - Objects for `Field`/`Key` segments
     /// - Arrays for `Index` segments
     /// Leaves are arrays of messages (strings).
+    ///
+    /// Object keys are sorted at every level so output is stable for
+    /// snapshots; messages keep the order their issues were pushed.
     pub fn to_nested_map(&self) -> serde_json::Value {
         self.to_nested_messages()
     }
 
+    /// `to_nested_map` as indented JSON, for snapshot tests and logs.
+    pub fn to_nested_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.to_nested_map()).unwrap_or_default()
+    }
+
     pub fn to_nested_errors(&self) -> serde_json::Value {
         self.to_nested_with(|err| err.to_json())
     }
@@ -290,7 +322,7 @@ impl ValidationReport {
                             let entry = map.entry(key).or_insert_with(|| {
                                 serde_json::Value::Object(serde_json::Map::new())
                             });
-                            if !entry.is_object() {
+                            if !fits_next(entry, &segs[1]) {
                                 *entry = serde_json::Value::Object(serde_json::Map::new());
                             }
                             insert_at(entry, &segs[1..], msg);
@@ -313,7 +345,7 @@ impl ValidationReport {
                             let entry = map.entry(key).or_insert_with(|| {
                                 serde_json::Value::Object(serde_json::Map::new())
                             });
-                            if !entry.is_object() {
+                            if !fits_next(entry, &segs[1]) {
                                 *entry = serde_json::Value::Object(serde_json::Map::new());
                             }
                             insert_at(entry, &segs[1..], msg);
@@ -365,8 +397,89 @@ impl ValidationReport {
             insert_at(&mut root, iss.path.segments(), &msg);
         }
 
+        sort_keys(&mut root);
         root
     }
+
+    /// Rebuilds a report from `to_nested_map` or `to_nested_errors` output.
+    ///
+    /// Paths are best effort: object keys become `Field` segments (map keys
+    /// are indistinguishable from fields) and array positions become `Index`
+    /// segments. Plain string leaves get the `custom` code.
+    pub fn from_nested_map(value: &serde_json::Value) -> Self {
+        let mut report = Self::empty();
+        if let serde_json::Value::Object(map) = value {
+            for (key, child) in map {
+                let path = if key == "non_field_errors" {
+                    Path::root()
+                } else {
+                    Path::root().at_field(key.clone())
+                };
+                collect_nested(&mut report, path, child);
+            }
+        }
+        report
+    }
+}
+
+/// Whether an existing nested value can hold the next segment; index arrays
+/// under a field must survive later issues at other indexes.
+fn fits_next(entry: &serde_json::Value, next: &PathSeg) -> bool {
+    match next {
+        PathSeg::Index(_) => entry.is_array(),
+        _ => entry.is_object(),
+    }
+}
+
+/// Sorts object keys at every level; arrays keep their order.
+fn sort_keys(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.sort_keys();
+            map.values_mut().for_each(sort_keys);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(sort_keys),
+        _ => {}
+    }
+}
+
+fn collect_nested(report: &mut ValidationReport, path: Path, value: &serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                collect_nested(report, path.clone().at_field(key.clone()), child);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                match leaf_error(item) {
+                    Some(invalid) => report.push(path.clone(), invalid),
+                    None => collect_nested(report, path.clone().at_index(idx), item),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Parses a message string or a `{code, message, params}` object.
+fn leaf_error(value: &serde_json::Value) -> Option<ValidationError> {
+    if let Some(message) = value.as_str() {
+        return Some(ValidationError::custom(message.to_string()));
+    }
+    let map = value.as_object()?;
+    let code = map.get("code")?.as_str()?;
+    let message = map.get("message")?.as_str()?;
+    let mut invalid = ValidationError::new(code.to_string(), message.to_string());
+    if let Some(serde_json::Value::Object(params)) = map.get("params") {
+        for (key, param) in params {
+            let param = param
+                .as_str()
+                .map_or_else(|| param.to_string(), str::to_string);
+            invalid = invalid.with_param(key.clone(), param);
+        }
+    }
+    Some(invalid)
 }
 
 impl fmt::Display for ValidationReport {
@@ -380,14 +493,134 @@ impl fmt::Display for ValidationReport {
 
 impl std::error::Error for ValidationReport {}
 
+/// Runs validator closures against one value and collects their failures.
+///
+/// Each `check` accepts any `Fn(&U) -> Result<(), ValidationError>` where the
+/// value borrows as `U`, so `min_len(3)` works on a `String` and `min_items(1)`
+/// on a `Vec<T>` without boxing. Failures are pushed at the builder's path,
+/// which is the root unless set with [`ValidationBuilder::at`].
+///
+/// # Example
+/// ```ignore
+/// let report = ValidationBuilder::new(&self.name)
+///     .at(Path::root().at_field("name"))
+///     .check(non_empty)
+///     .check(max_len(80))
+///     .into_report();
+/// ```
+pub struct ValidationBuilder<'a, T: ?Sized> {
+    value: &'a T,
+    path: Path,
+    report: ValidationReport,
+}
+
+impl<'a, T: ?Sized> ValidationBuilder<'a, T> {
+    pub fn new(value: &'a T) -> Self {
+        Self {
+            value,
+            path: Path::root(),
+            report: ValidationReport::empty(),
+        }
+    }
+
+    /// Sets the path used for failures from later checks.
+    pub fn at(mut self, path: Path) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Runs `validator` against the value, recording any failure.
+    pub fn check<U, F>(mut self, validator: F) -> Self
+    where
+        U: ?Sized,
+        T: std::borrow::Borrow<U>,
+        F: Fn(&U) -> Result<(), ValidationError>,
+    {
+        if let Err(invalid) = validator(self.value.borrow()) {
+            self.report.push(self.path.clone(), invalid);
+        }
+        self
+    }
+
+    /// Returns the collected failures, possibly empty.
+    pub fn into_report(self) -> ValidationReport {
+        self.report
+    }
+
+    /// `Ok(())` when every check passed, otherwise the collected report.
+    pub fn finish(self) -> Result<(), ValidationReport> {
+        if self.report.is_empty() {
+            Ok(())
+        } else {
+            Err(self.report)
+        }
+    }
+}
+
+/// Folds a database integrity violation into `report` as a field-level issue.
+///
+/// `field_map` maps constraint names to dotted field paths; unmapped
+/// constraints land under `non_field_errors`. Non-integrity errors are
+/// returned unchanged so callers can propagate them.
+pub fn merge_db_error(
+    mut report: ValidationReport,
+    error: DbError,
+    field_map: &[(&str, &str)],
+) -> Result<ValidationReport, DbError> {
+    let DbError::Integrity {
+        kind, constraint, ..
+    } = &error
+    else {
+        return Err(error);
+    };
+    let mut invalid = integrity_error(kind);
+    if let Some(name) = constraint {
+        invalid = invalid.with_param("constraint", name);
+    }
+    let field = constraint.as_deref().and_then(|name| {
+        field_map
+            .iter()
+            .find(|(constraint, _)| *constraint == name)
+            .map(|(_, field)| *field)
+    });
+    match field {
+        Some(field) => {
+            let path = field
+                .split('.')
+                .fold(Path::root(), |path, seg| path.at_field(seg.to_string()));
+            report.push(path, invalid);
+        }
+        None => report.push_root(invalid),
+    }
+    Ok(report)
+}
+
+fn integrity_error(kind: &IntegrityKind) -> ValidationError {
+    match kind {
+        IntegrityKind::Unique => ValidationError::new("unique", "This value is already in use."),
+        IntegrityKind::ForeignKey => {
+            ValidationError::new("foreign_key", "The referenced record does not exist.")
+        }
+        IntegrityKind::Check => ValidationError::new("check", "This value is not allowed."),
+        IntegrityKind::NotNull => ValidationError::new("required", "This field is required."),
+        IntegrityKind::Exclusion => {
+            ValidationError::new("exclusion", "This value conflicts with an existing record.")
+        }
+        IntegrityKind::Other(_) => {
+            ValidationError::new("integrity", "This value violates a database constraint.")
+        }
+    }
+}
+
 /// Structural validation trait for types that can validate themselves.
 ///
 /// # Example
 /// ```ignore
 /// impl Validate for MyStruct {
 ///     fn validate(&self) -> Result<(), ValidationReport> {
-///         let mut report = ValidationReport::empty();\n///         if self.name.is_empty() {
-///             report.push_root(ValidationError::new(\"required\", \"Name is required\"));
+///         let mut report = ValidationReport::empty();
+///         if self.name.is_empty() {
+///             report.push_field("name", ValidationError::new("required", "Name is required"));
 ///         }
 ///         if report.is_empty() {
 ///             Ok(())
@@ -625,6 +858,69 @@ impl_as_validation_target!(
     i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool
 );
 
+impl_as_validation_target!(rust_decimal::Decimal);
+
+/// Presence test behind `#[validate(required_if = ...)]`.
+///
+/// Used both for the condition's sibling field and for the required field:
+/// `Option` is truthy when `Some`, strings when not blank, collections when
+/// not empty, numbers when non-zero.
+pub trait Truthy {
+    fn is_truthy(&self) -> bool;
+}
+
+impl<T> Truthy for Option<T> {
+    fn is_truthy(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl Truthy for bool {
+    fn is_truthy(&self) -> bool {
+        *self
+    }
+}
+
+impl Truthy for String {
+    fn is_truthy(&self) -> bool {
+        !self.trim().is_empty()
+    }
+}
+
+impl Truthy for &str {
+    fn is_truthy(&self) -> bool {
+        !self.trim().is_empty()
+    }
+}
+
+impl<T> Truthy for Vec<T> {
+    fn is_truthy(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+impl<T: Truthy> Truthy for Box<T> {
+    fn is_truthy(&self) -> bool {
+        self.as_ref().is_truthy()
+    }
+}
+
+macro_rules! impl_truthy_numeric {
+    ($($t:ty),*) => {
+        $(
+            impl Truthy for $t {
+                fn is_truthy(&self) -> bool {
+                    *self != <$t>::default()
+                }
+            }
+        )*
+    };
+}
+
+impl_truthy_numeric!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
 impl<T: Validate> Validate for Option<T> {
     fn validate(&self) -> Result<(), ValidationReport> {
         match self {
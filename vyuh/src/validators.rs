@@ -1,9 +1,12 @@
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 use regex::Regex;
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::net::Ipv6Addr;
+use std::net::{IpAddr, Ipv6Addr};
 use std::str::FromStr;
+use std::sync::Arc;
 
 use super::validation::ValidationError;
 
@@ -215,6 +218,39 @@ pub fn regex_with_msg(
     }
 }
 
+/// Compiled `regex_str` patterns, keyed by pattern text.
+static REGEX_CACHE: Lazy<RwLock<HashMap<String, Arc<Regex>>>> = Lazy::new(Default::default);
+
+fn cached_regex(pattern: &str) -> Result<Arc<Regex>, regex::Error> {
+    if let Some(re) = REGEX_CACHE.read().get(pattern) {
+        return Ok(re.clone());
+    }
+    let re = Arc::new(Regex::new(pattern)?);
+    Ok(REGEX_CACHE
+        .write()
+        .entry(pattern.to_string())
+        .or_insert(re)
+        .clone())
+}
+
+/// Validates against a pattern compiled on first use.
+///
+/// Compiled patterns are cached process-wide, so calling this repeatedly with
+/// the same pattern does not recompile it. Invalid patterns are returned as
+/// the `regex::Error` rather than surfacing as validation failures.
+pub fn regex_str(
+    pattern: &str,
+) -> Result<impl Fn(&str) -> Result<(), ValidationError> + use<>, regex::Error> {
+    let re = cached_regex(pattern)?;
+    Ok(move |s: &str| {
+        if re.is_match(s) {
+            Ok(())
+        } else {
+            Err(err("pattern", "Invalid format."))
+        }
+    })
+}
+
 /// Validates URL format (http/https).
 pub fn url(s: &str) -> Result<(), ValidationError> {
     static URL_RE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
@@ -297,6 +333,15 @@ pub fn ipv6(s: &str) -> Result<(), ValidationError> {
     }
 }
 
+/// Validates an IPv4 or IPv6 address, including compressed and mapped forms.
+pub fn ip(s: &str) -> Result<(), ValidationError> {
+    if IpAddr::from_str(s).is_ok() {
+        Ok(())
+    } else {
+        Err(err("ip", "Enter a valid IP address."))
+    }
+}
+
 /// Validates E.164 phone number format.
 pub fn phone_e164(s: &str) -> Result<(), ValidationError> {
     static PHONE_RE: once_cell::sync::Lazy<Regex> =
@@ -309,6 +354,13 @@ pub fn phone_e164(s: &str) -> Result<(), ValidationError> {
     }
 }
 
+/// Validates a phone number in E.164 form: `+` followed by up to 15 digits.
+///
+/// Same rule as [`phone_e164`], reported under the shorter `phone` code.
+pub fn phone(s: &str) -> Result<(), ValidationError> {
+    phone_e164(s).map_err(|_| err("phone", "Enter a valid phone number."))
+}
+
 /// Validates ISO calendar date format: YYYY-MM-DD.
 pub fn date(s: &str) -> Result<(), ValidationError> {
     if chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
@@ -437,6 +489,28 @@ where
     }
 }
 
+/// Validates that a decimal has at most `places` digits after the point.
+///
+/// Pairs with `min`/`max`/`range`, which accept `Decimal` bounds, for money
+/// fields that must not carry sub-cent precision. Trailing zeros count, so
+/// normalize first if `1.500` should pass at two places.
+pub fn max_decimal_places(
+    places: u32,
+) -> impl Fn(&rust_decimal::Decimal) -> Result<(), ValidationError> {
+    move |v| {
+        if v.scale() > places {
+            Err(err_param(
+                "max_decimal_places",
+                format!("Ensure there are no more than {places} decimal places."),
+                "max_decimal_places",
+                places,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// ---------- collections ----------
 
 /// Checks that a collection is not empty.
@@ -581,6 +655,19 @@ mod tests {
         assert!(digit_validator("12a45").is_err());
     }
 
+    #[test]
+    fn test_regex_str_caches_and_rejects_bad_patterns() {
+        let digits = regex_str(r"^\d+$").expect("valid pattern");
+        assert!(digits("12345").is_ok());
+        assert_eq!(digits("12a45").unwrap_err().code, "pattern");
+
+        let first = cached_regex(r"^\d+$").expect("cached");
+        let second = cached_regex(r"^\d+$").expect("cached");
+        assert!(Arc::ptr_eq(&first, &second));
+
+        assert!(regex_str("(unclosed").is_err());
+    }
+
     #[test]
     fn test_new_string_validators() {
         assert!(url("https://example.com").is_ok());
@@ -1,4 +1,4 @@
-use crate::{SiteError, notifiers::CancellationNotifier};
+use crate::{Site, SiteError, notifiers::CancellationNotifier};
 use notify::{RecursiveMode, Watcher};
 use std::{future::Future, path::PathBuf, pin::Pin, time::Duration};
 use tokio::signal;
@@ -27,6 +27,29 @@ async fn reload_signal(touch_reload: Option<String>) -> Result<(), SiteError> {
     return futures::future::pending().await;
 }
 
+/// Calls [`Site::reload`] on every `SIGHUP` until the task is aborted.
+#[cfg(unix)]
+pub(crate) async fn reload_on_hangup(site: Site) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(err) => {
+            tracing::warn!("Failed to install SIGHUP handler: {}", err);
+            return;
+        }
+    };
+    while hangup.recv().await.is_some() {
+        match site.reload() {
+            Ok(()) => tracing::info!("Configuration reloaded on SIGHUP"),
+            Err(err) => tracing::error!("Configuration reload failed: {}", err),
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) async fn reload_on_hangup(_site: Site) {}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ShutdownSource {
     Programmatic,
@@ -0,0 +1,108 @@
+use axum::http::StatusCode;
+use rust_silos::Silo;
+use vyuh::{SiteConf, bundles, embed, middlewares::SlashPolicy, routes::Json, testing::TestClient};
+
+fn test_conf() -> SiteConf {
+    let mut conf = SiteConf {
+        log_init: false,
+        logging: vyuh::logging::LoggingConf {
+            env_prefix: None,
+            rules: vec![],
+        },
+        ..SiteConf::default()
+    };
+    conf.http.slash.policy = SlashPolicy::RedirectAppend;
+    conf
+}
+
+#[bundles::route(path = "/docs/")]
+async fn docs() -> Json<&'static str> {
+    Json("docs")
+}
+
+async fn asset_client(cache_control: Option<&str>) -> (TestClient, tempfile::TempDir) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("public")).unwrap();
+    std::fs::write(dir.path().join("public/app.css"), "body { color: red }").unwrap();
+    let mut assets = embed::Dir::new(Silo::new(dir.path().to_str().unwrap()));
+    if let Some(value) = cache_control {
+        assets = assets.with_cache_control(value);
+    }
+    let bundle = bundles::bundle([bundles::asset_dir(assets)]).merge(bundles::bundle! { docs });
+    let site = vyuh::Site::build(test_conf(), bundle).await.unwrap();
+    (TestClient::new(site), dir)
+}
+
+fn header(response: &vyuh::testing::TestResponse, name: &str) -> String {
+    response
+        .header(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[tokio::test]
+async fn static_assets_answer_conditional_gets_with_304() {
+    let (client, _dir) = asset_client(None).await;
+    let response = client.get("/assets/app.css").send().await.assert_ok();
+    let etag = header(&response, "etag");
+    let last_modified = header(&response, "last-modified");
+    assert!(etag.starts_with('"'));
+    assert!(last_modified.ends_with("GMT"));
+    assert_eq!(
+        header(&response, "cache-control"),
+        "public, max-age=31536000, immutable"
+    );
+
+    // Weak validators and lists match, including with a trailing slash that
+    // passes through the slash middleware.
+    for (path, name, value) in [
+        ("/assets/app.css", "if-none-match", etag.clone()),
+        (
+            "/assets/app.css/",
+            "if-none-match",
+            format!("\"x\", W/{etag}"),
+        ),
+        (
+            "/assets/app.css",
+            "if-modified-since",
+            last_modified.clone(),
+        ),
+    ] {
+        let response = client.get(path).header(name, &value).send().await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "{path} {name}");
+        assert_eq!(header(&response, "etag"), etag);
+        assert!(response.bytes().await.is_empty());
+    }
+
+    // A stale ETag wins over a fresh If-Modified-Since.
+    client
+        .get("/assets/app.css")
+        .header("if-none-match", "\"stale\"")
+        .header("if-modified-since", &last_modified)
+        .send()
+        .await
+        .assert_ok();
+    client
+        .get("/assets/app.css")
+        .header("if-modified-since", "Thu, 01 Jan 1970 00:00:00 GMT")
+        .send()
+        .await
+        .assert_ok();
+}
+
+#[tokio::test]
+async fn asset_dir_overrides_cache_control() {
+    let (client, _dir) = asset_client(Some("public, max-age=60")).await;
+    let response = client.get("/assets/app.css").send().await.assert_ok();
+    assert_eq!(header(&response, "cache-control"), "public, max-age=60");
+
+    let etag = header(&response, "etag");
+    let response = client
+        .get("/assets/app.css")
+        .header("if-none-match", &etag)
+        .send()
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(header(&response, "cache-control"), "public, max-age=60");
+}
@@ -4,8 +4,8 @@ use vyuh::{
     SiteConf,
     auth::{
         ApiKey, ApiKeyConf, ApiKeyPrincipal, ApiKeyVerifier, AuthAudiencePolicy, AuthConf,
-        AuthError, AuthUser, BitRole, JWTClaim, JwtAlgorithm, JwtConf, JwtKeySource, TokenKind,
-        permit,
+        AuthError, AuthUser, BitRole, JWTClaim, JwtAlgorithm, JwtConf, JwtKeySource, MaybeUser,
+        TokenKind, TokenLocation, permit,
     },
     bundles, routes,
     routes::{Json, StatusCode},
@@ -80,7 +80,7 @@ enum TestRole {
     Viewer,
 }
 
-#[derive(Debug, Serialize, JsonSchema)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 struct WhoAmI {
     key: String,
     roles: u64,
@@ -129,6 +129,16 @@ async fn me(user: AuthUser) -> Json<WhoAmI> {
     })
 }
 
+#[bundles::route(path = "/managers", roles("manager"))]
+async fn managers_only(user: AuthUser) -> Json<String> {
+    Json(user.key.to_string())
+}
+
+#[bundles::route(path = "/maybe")]
+async fn maybe_me(user: MaybeUser) -> Json<Option<String>> {
+    Json(user.0.map(|user| user.key.to_string()))
+}
+
 #[bundles::route(path = "/api-key")]
 async fn api_key_route(key: ApiKey) -> Json<KeyInfo> {
     Json(KeyInfo {
@@ -138,6 +148,31 @@ async fn api_key_route(key: ApiKey) -> Json<KeyInfo> {
     })
 }
 
+#[bundles::route(path = "/login")]
+async fn cookie_login(site: vyuh::Site) -> routes::Response {
+    let mut response = routes::Response::new(routes::Body::empty());
+    if let Err(err) = site
+        .auth()
+        .login_user(AuthUser::new("user-1", 0), &[], &mut response)
+    {
+        *response.body_mut() = routes::Body::from(err.to_string());
+    }
+    response
+}
+
+#[bundles::route(path = "/session", method = "POST")]
+async fn session_login(site: vyuh::Site) -> routes::Response {
+    cookie_login(site).await
+}
+
+#[bundles::route(path = "/logout")]
+async fn cookie_logout(site: vyuh::Site) -> routes::Response {
+    let mut response = routes::Response::new(routes::Body::empty());
+    site.auth().logout(false, &mut response);
+    site.auth().logout(true, &mut response);
+    response
+}
+
 #[bundles::route(path = "/secure")]
 async fn secure(_permit: permit!(TestRole, Manager)) -> Json<WhoAmI> {
     Json(WhoAmI {
@@ -272,6 +307,41 @@ async fn rsa_jwt_keys_can_be_loaded_from_project_relative_files() {
     site.shutdown_and_wait().await;
 }
 
+#[tokio::test]
+async fn secret_key_can_be_loaded_from_project_relative_file() {
+    let temp = tempfile::tempdir().unwrap();
+    std::fs::write(temp.path().join("secret_key"), format!("{HMAC_SECRET}\n")).unwrap();
+    let file_site = vyuh::Site::build(
+        test_conf()
+            .project_dir(temp.path().to_string_lossy())
+            .secret_key_file("secret_key"),
+        bundles::Bundle::new(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(file_site.conf().secret_key, HMAC_SECRET);
+    let token = file_site
+        .auth()
+        .create_token_pair(AuthUser::new("user-1", 0), &[])
+        .unwrap()
+        .access_token;
+
+    let inline_site =
+        vyuh::Site::build(test_conf().secret_key(HMAC_SECRET), bundles::Bundle::new())
+            .await
+            .unwrap();
+    inline_site.auth().decode(&token).unwrap();
+    let other_site = vyuh::Site::build(test_conf(), bundles::Bundle::new())
+        .await
+        .unwrap();
+    let err = other_site.auth().decode(&token).unwrap_err();
+    assert!(matches!(err, AuthError::InvalidSignature));
+
+    file_site.shutdown_and_wait().await;
+    inline_site.shutdown_and_wait().await;
+    other_site.shutdown_and_wait().await;
+}
+
 #[tokio::test]
 async fn jwt_rejects_tokens_signed_with_wrong_algorithm() {
     let hs384_site = vyuh::Site::build(
@@ -428,6 +498,92 @@ async fn auth_accepts_bearer_authorization_header() {
     site.shutdown_and_wait().await;
 }
 
+#[tokio::test]
+async fn route_roles_guard_rejects_users_without_the_role() {
+    let conf = test_conf().auth(AuthConf::default().bit_roles::<TestRole>());
+    let site = vyuh::Site::build(conf, bundles::bundle! { managers_only })
+        .await
+        .unwrap();
+    let token = |role: TestRole| {
+        site.auth()
+            .create_token_pair(AuthUser::new("user-1", role.to_role_type()), &[])
+            .unwrap()
+            .access_token
+    };
+    let client = TestClient::new(site.clone());
+
+    client
+        .get("/managers")
+        .header(
+            "authorization",
+            &format!("Bearer {}", token(TestRole::Viewer)),
+        )
+        .send()
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+    client
+        .get("/managers")
+        .header(
+            "authorization",
+            &format!("Bearer {}", token(TestRole::Manager)),
+        )
+        .send()
+        .await
+        .assert_status(StatusCode::OK);
+    client
+        .get("/managers")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    let op = site
+        .iter_operations()
+        .find(|op| op.path == "/managers")
+        .unwrap();
+    assert_eq!(op.layers[0].name, "roles");
+
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn bundle_requires_rejects_before_handler_runs() {
+    let site = vyuh::Site::build(
+        test_conf(),
+        bundles::bundle! { requires = [AuthUser], maybe_me },
+    )
+    .await
+    .unwrap();
+    let token = site
+        .auth()
+        .create_token_pair(AuthUser::new("user-1", 0), &[])
+        .unwrap()
+        .access_token;
+    let client = TestClient::new(site.clone());
+
+    client
+        .get("/maybe")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    let user: Option<String> = client
+        .get("/maybe")
+        .header("authorization", &format!("Bearer {token}"))
+        .send()
+        .await
+        .assert_ok()
+        .json()
+        .await;
+    assert_eq!(user.as_deref(), Some("user-1"));
+
+    let op = site
+        .iter_operations()
+        .find(|op| op.path == "/maybe")
+        .unwrap();
+    assert_eq!(op.layers[0].name, "requires");
+
+    site.shutdown_and_wait().await;
+}
+
 #[tokio::test]
 async fn public_route_does_not_require_auth() {
     let site = vyuh::Site::build(
@@ -500,6 +656,96 @@ async fn auth_missing_token_returns_unauthorized() {
     site.shutdown_and_wait().await;
 }
 
+#[tokio::test]
+async fn malformed_tokens_return_unauthorized() {
+    let site = vyuh::Site::build(
+        test_conf(),
+        bundles::bundle! {
+            me,
+        },
+    )
+    .await
+    .unwrap();
+    let client = TestClient::new(site.clone());
+
+    for token in [
+        "garbage",
+        "a.b.c",
+        "eyJhbGciOiJIUzI1NiJ9.!!!.c2ln",
+        "bm90LWpzb24.e30.c2ln",
+        "eyJhbGciOiJIUzI1NiJ9.bm90LWpzb24.c2ln",
+    ] {
+        let err = site.auth().decode(token).unwrap_err();
+        assert!(
+            matches!(err, AuthError::InvalidToken | AuthError::InvalidSignature),
+            "{token}: {err:?}"
+        );
+        client
+            .get("/me")
+            .header("authorization", &format!("Bearer {token}"))
+            .send()
+            .await
+            .assert_status(StatusCode::UNAUTHORIZED);
+    }
+
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn maybe_user_is_none_without_a_usable_token() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { maybe_me })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+    let user = AuthUser::new("user-1", 0);
+    let valid = site
+        .auth()
+        .create_token_pair(user.clone(), &[])
+        .unwrap()
+        .access_token;
+    let expired = JWTClaim::new(&user, "", None, vec![], -3600, TokenKind::Access);
+    let expired = site.auth().encode(&expired).unwrap();
+
+    let fetch = async |token: Option<&str>| {
+        let mut request = client.get("/maybe");
+        if let Some(token) = token {
+            request = request.header("authorization", &format!("Bearer {token}"));
+        }
+        request
+            .send()
+            .await
+            .assert_ok()
+            .json::<Option<String>>()
+            .await
+    };
+    assert_eq!(fetch(Some(&valid)).await.as_deref(), Some("user-1"));
+    assert_eq!(fetch(None).await, None);
+    assert_eq!(fetch(Some(&expired)).await, None);
+    assert_eq!(fetch(Some("garbage")).await, None);
+
+    site.shutdown_and_wait().await;
+}
+
+#[test]
+fn only_token_errors_count_as_anonymous() {
+    for err in [
+        AuthError::MissingToken,
+        AuthError::InvalidToken,
+        AuthError::ExpiredToken,
+        AuthError::InvalidSignature,
+        AuthError::WrongTokenKind,
+    ] {
+        assert!(err.is_token_error(), "{err}");
+    }
+    for err in [
+        AuthError::InternalError("rng error".to_string()),
+        AuthError::JwtConfigError("missing key".to_string()),
+        AuthError::Forbidden,
+    ] {
+        assert!(!err.is_token_error(), "{err}");
+    }
+}
+
 #[tokio::test]
 async fn auth_permit_rejects_missing_role() {
     let site = vyuh::Site::build(
@@ -630,10 +876,7 @@ async fn issuer_must_match_when_configured() {
     let token = site.auth().encode(&claims).unwrap();
 
     let err = site.auth().decode(&token).unwrap_err();
-    assert!(matches!(
-        err,
-        AuthError::InvalidToken | AuthError::InternalError(_)
-    ));
+    assert!(matches!(err, AuthError::InvalidToken));
 
     site.shutdown_and_wait().await;
 }
@@ -674,6 +917,19 @@ async fn configured_minimum_secret_length_is_validated() {
     assert!(err.to_string().contains("secret_key"));
 }
 
+#[tokio::test]
+async fn ten_character_secret_fails_to_build() {
+    let err = vyuh::Site::build(
+        SiteConf::default().secret_key("0123456789").log_init(false),
+        bundles::Bundle::new(),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, vyuh::SiteError::ConfError(_)));
+    assert!(err.to_string().contains("at least 32 bytes"));
+}
+
 #[tokio::test]
 async fn default_cookies_are_disabled() {
     let site = vyuh::Site::build(test_conf(), bundles::Bundle::new())
@@ -712,6 +968,142 @@ async fn opt_in_cookies_are_written() {
     site.shutdown_and_wait().await;
 }
 
+#[tokio::test]
+async fn login_sets_cookies_and_logout_clears_them() {
+    let conf = test_conf().auth(AuthConf::cookie_pair("access_token", "refresh_token"));
+    let site = vyuh::Site::build(
+        conf,
+        bundles::bundle! {
+            cookie_login,
+            cookie_logout,
+        },
+    )
+    .await
+    .unwrap();
+    let client = TestClient::new(site.clone());
+
+    let login = client
+        .get("/login")
+        .send()
+        .await
+        .assert_ok()
+        .assert_cookie("access_token")
+        .assert_cookie("refresh_token");
+    let access = login.cookie("access_token").unwrap();
+    assert_eq!(access.http_only(), Some(true));
+    assert!(site.auth().decode(access.value()).is_ok());
+
+    client
+        .get("/logout")
+        .send()
+        .await
+        .assert_ok()
+        .assert_cookie_cleared("access_token")
+        .assert_cookie_cleared("refresh_token");
+
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn test_session_replays_login_cookies() {
+    let conf = test_conf().auth(AuthConf::cookie_pair("access_token", "refresh_token"));
+    let site = vyuh::Site::build(
+        conf,
+        bundles::bundle! {
+            session_login,
+            cookie_logout,
+            me,
+        },
+    )
+    .await
+    .unwrap();
+    let client = TestClient::new(site.clone());
+    let session = client.session();
+
+    session
+        .get("/me")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    session.post("/session").send().await.assert_ok();
+    assert!(session.cookie("access_token").is_some());
+
+    let who: WhoAmI = session.get("/me").send().await.assert_ok().json().await;
+    assert_eq!(who.key, "user-1");
+
+    // Plain client requests stay anonymous.
+    client
+        .get("/me")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    session.get("/logout").send().await.assert_ok();
+    assert!(session.cookie("access_token").is_none());
+    session
+        .get("/me")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    site.shutdown_and_wait().await;
+}
+
+async fn who_is(location: TokenLocation, header: bool, cookie: bool) -> (StatusCode, String) {
+    let auth = AuthConf::cookie_pair("access_token", "refresh_token").token_location(location);
+    let site = vyuh::Site::build(test_conf().auth(auth), bundles::bundle! { me })
+        .await
+        .unwrap();
+    let token = |key: &str| {
+        site.auth()
+            .create_token_pair(AuthUser::new(key, 0), &[])
+            .unwrap()
+            .access_token
+    };
+    let (header_token, cookie_token) = (token("header-user"), token("cookie-user"));
+    let mut request = TestClient::new(site.clone()).get("/me");
+    if header {
+        request = request.header("authorization", &format!("Bearer {header_token}"));
+    }
+    if cookie {
+        request = request.header("cookie", &format!("access_token={cookie_token}"));
+    }
+    let response = request.send().await;
+    let status = response.status();
+    let key = if status.is_success() {
+        response.json::<WhoAmI>().await.key
+    } else {
+        String::new()
+    };
+    site.shutdown_and_wait().await;
+    (status, key)
+}
+
+#[tokio::test]
+async fn token_location_selects_header_or_cookie() {
+    let found = |key: &str| (StatusCode::OK, key.to_string());
+    assert_eq!(
+        who_is(TokenLocation::HeaderFirst, true, true).await,
+        found("header-user")
+    );
+    assert_eq!(
+        who_is(TokenLocation::CookieFirst, true, true).await,
+        found("cookie-user")
+    );
+    assert_eq!(
+        who_is(TokenLocation::CookieFirst, true, false).await,
+        found("header-user")
+    );
+    assert_eq!(
+        who_is(TokenLocation::HeaderOnly, true, true).await,
+        found("header-user")
+    );
+    assert_eq!(
+        who_is(TokenLocation::HeaderOnly, false, true).await,
+        (StatusCode::UNAUTHORIZED, String::new())
+    );
+}
+
 #[tokio::test]
 async fn api_key_extracts_from_configured_header() {
     let conf = test_conf()
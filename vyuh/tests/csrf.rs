@@ -0,0 +1,203 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use vyuh::{
+    SiteConf, bundles,
+    routes::{Body, CsrfToken, Form, Json, StatusCode, axum_extractors::Multipart},
+    testing::TestClient,
+};
+
+fn test_conf() -> SiteConf {
+    SiteConf {
+        log_init: false,
+        logging: vyuh::logging::LoggingConf {
+            env_prefix: None,
+            rules: vec![],
+        },
+        ..SiteConf::default()
+    }
+    .with_csrf()
+}
+
+#[derive(Deserialize, JsonSchema)]
+struct Note {
+    text: String,
+}
+
+#[bundles::route(path = "/form")]
+async fn form(csrf: CsrfToken) -> Json<String> {
+    Json(csrf.input())
+}
+
+#[bundles::route(path = "/notes", method = "POST")]
+async fn create_note(Form(note): Form<Note>) -> Json<String> {
+    Json(note.text)
+}
+
+#[bundles::route(path = "/uploads", method = "POST")]
+async fn upload(mut form: Multipart) -> Json<String> {
+    while let Some(field) = form.next_field().await.unwrap() {
+        if field.name() == Some("text") {
+            return Json(field.text().await.unwrap());
+        }
+    }
+    Json(String::new())
+}
+
+/// Fetches `/form` and returns the cookie value and the embedded token.
+async fn issue_token(client: &TestClient) -> (String, String) {
+    let response = client.get("/form").send().await.assert_ok();
+    let cookie = response.cookie("vyuh_csrf").unwrap().value().to_string();
+    let input: String = response.json().await;
+    let token = input
+        .split("value=\"")
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap()
+        .to_string();
+    (cookie, token)
+}
+
+fn multipart_body(boundary: &str, fields: &[(&str, &str)]) -> String {
+    let mut body = String::new();
+    for (name, value) in fields {
+        body.push_str(&format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+        ));
+    }
+    body.push_str(&format!("--{boundary}--\r\n"));
+    body
+}
+
+#[tokio::test]
+async fn unsafe_requests_must_echo_the_cookie_token() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { form, create_note })
+        .await
+        .unwrap();
+    let client = TestClient::new(site);
+
+    let response = client.get("/form").send().await.assert_ok();
+    let cookie = response.cookie("vyuh_csrf").unwrap().value().to_string();
+    let input: String = response.json().await;
+    let token = input
+        .split("value=\"")
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap()
+        .to_string();
+    assert!(input.contains("name=\"csrf_token\""));
+    assert!(!token.is_empty());
+
+    // The issued cookie is reused, not rotated, while it is valid.
+    let response = client
+        .get("/form")
+        .header("cookie", &format!("vyuh_csrf={cookie}"))
+        .send()
+        .await
+        .assert_ok();
+    assert!(response.cookie("vyuh_csrf").is_none());
+    let again: String = response.json().await;
+    assert_eq!(again, input);
+
+    let post = |body: String| {
+        client
+            .post("/notes")
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+    };
+
+    let response = post("text=hi".into())
+        .header("cookie", &format!("vyuh_csrf={cookie}"))
+        .send()
+        .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = post(format!("text=hi&csrf_token={token}")).send().await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = post(format!("text=hi&csrf_token={token}"))
+        .header("cookie", &format!("vyuh_csrf={cookie}"))
+        .send()
+        .await
+        .assert_ok();
+    let text: String = response.json().await;
+    assert_eq!(text, "hi");
+
+    let response = post("text=header".into())
+        .header("cookie", &format!("vyuh_csrf={cookie}"))
+        .header("x-csrf-token", &token)
+        .send()
+        .await
+        .assert_ok();
+    let text: String = response.json().await;
+    assert_eq!(text, "header");
+
+    let response = post("text=wrong&csrf_token=nope".into())
+        .header("cookie", &format!("vyuh_csrf={cookie}"))
+        .send()
+        .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Bearer-authenticated API calls skip the check.
+    post("text=api".into())
+        .header("authorization", "Bearer some-token")
+        .send()
+        .await
+        .assert_ok();
+}
+
+#[tokio::test]
+async fn multipart_forms_carry_the_token_as_a_field() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { form, upload })
+        .await
+        .unwrap();
+    let client = TestClient::new(site);
+    let (cookie, token) = issue_token(&client).await;
+
+    let post = |fields: &[(&str, &str)]| {
+        client
+            .post("/uploads")
+            .header(
+                "content-type",
+                "multipart/form-data; boundary=vyuh-boundary",
+            )
+            .header("cookie", &format!("vyuh_csrf={cookie}"))
+            .body(Body::from(multipart_body("vyuh-boundary", fields)))
+    };
+
+    let response = post(&[("csrf_token", &token), ("text", "upload")])
+        .send()
+        .await
+        .assert_ok();
+    let text: String = response.json().await;
+    assert_eq!(text, "upload");
+
+    let response = post(&[("text", "upload")]).send().await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = post(&[("csrf_token", "nope"), ("text", "upload")])
+        .send()
+        .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn scanned_form_bodies_follow_the_configured_body_limit() {
+    let mut conf = test_conf();
+    conf.http.body_limit.max_bytes = 64;
+    let site = vyuh::Site::build(conf, bundles::bundle! { form, create_note })
+        .await
+        .unwrap();
+    let client = TestClient::new(site);
+    let (cookie, token) = issue_token(&client).await;
+
+    let response = client
+        .post("/notes")
+        .header("content-type", "application/x-www-form-urlencoded")
+        .header("cookie", &format!("vyuh_csrf={cookie}"))
+        .body(Body::from(format!(
+            "csrf_token={token}&text={}",
+            "x".repeat(64)
+        )))
+        .send()
+        .await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -0,0 +1,118 @@
+//! `DbPool::copy_in` against a real Postgres server, ignored by default:
+//! `TEST_DATABASE_URL=postgres://... cargo test --features postgres --test db_copy -- --ignored`.
+#![cfg(feature = "postgres")]
+
+use vyuh::db::{self, Bindable, ColumnEnum, DbPool, Scannable};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, Bindable, Scannable)]
+#[serde(rename_all = "camelCase")]
+struct Upload {
+    #[column(name = "title")]
+    name: String,
+    #[column(json)]
+    labels: Vec<String>,
+    #[column(array)]
+    tags: Vec<String>,
+    data: Vec<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    digest: Option<Vec<u8>>,
+    meta: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ColumnEnum)]
+enum Level {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq, Bindable, Scannable)]
+struct Ranked {
+    #[column(store_as = "text")]
+    label: Level,
+    #[column(store_as = "int")]
+    level: Level,
+}
+
+async fn pool() -> (vyuh::testing::MockDb, DbPool) {
+    let guard = vyuh::testing::mock_db().await;
+    let pool = DbPool::from_pool(guard.pool().clone());
+    (guard, pool)
+}
+
+#[tokio::test]
+#[ignore] // Needs a Postgres server; see the module docs.
+async fn copy_in_writes_json_bytea_and_renamed_columns() {
+    let (_guard, mut pool) = pool().await;
+    sqlx::query(
+        "CREATE TABLE uploads (title TEXT NOT NULL, labels JSONB NOT NULL, tags TEXT[] NOT NULL,
+         data BYTEA NOT NULL, digest BYTEA, meta JSONB NOT NULL)",
+    )
+    .execute(pool.as_sqlx())
+    .await
+    .unwrap();
+
+    let rows = vec![
+        Upload {
+            name: "a\tb".to_string(),
+            labels: vec!["x".to_string(), "y".to_string()],
+            tags: vec!["t \"1\"".to_string()],
+            data: vec![0, 1, 0xff],
+            digest: None,
+            meta: serde_json::json!({"k": [1, 2]}),
+        },
+        Upload {
+            name: "c".to_string(),
+            labels: Vec::new(),
+            tags: Vec::new(),
+            data: Vec::new(),
+            digest: Some(vec![0xab]),
+            meta: serde_json::json!([1, "two"]),
+        },
+    ];
+    assert_eq!(pool.copy_in("uploads", &rows).await.unwrap(), 2);
+
+    let copied: Vec<Upload> = db::select("uploads")
+        .order_by("title", true)
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(copied, rows);
+}
+
+#[tokio::test]
+#[ignore] // Needs a Postgres server; see the module docs.
+async fn copy_in_writes_enums_as_inserts_do() {
+    let (_guard, mut pool) = pool().await;
+    sqlx::query("CREATE TABLE ranked (label TEXT NOT NULL, level BIGINT NOT NULL)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+
+    let rows = vec![
+        Ranked {
+            label: Level::High,
+            level: Level::Low,
+        },
+        Ranked {
+            label: Level::Low,
+            level: Level::High,
+        },
+    ];
+    assert_eq!(pool.copy_in("ranked", &rows).await.unwrap(), 2);
+
+    let labels: Vec<(String, i64)> =
+        sqlx::query_as("SELECT label, level FROM ranked ORDER BY level")
+            .fetch_all(pool.as_sqlx())
+            .await
+            .unwrap();
+    assert_eq!(
+        labels,
+        vec![("high".to_string(), 0), ("low".to_string(), 1)]
+    );
+    let copied: Vec<Ranked> = db::select("ranked")
+        .order_by("level", true)
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(copied, rows);
+}
@@ -0,0 +1,36 @@
+use vyuh::db::{PgType, rust_to_pg_type};
+
+#[test]
+fn common_rust_types_map_to_postgres_columns() {
+    assert_eq!(rust_to_pg_type::<i64>(), "bigint");
+    assert_eq!(rust_to_pg_type::<String>(), "text");
+    assert_eq!(
+        rust_to_pg_type::<chrono::DateTime<chrono::Utc>>(),
+        "timestamptz"
+    );
+    assert_eq!(rust_to_pg_type::<uuid::Uuid>(), "uuid");
+    assert_eq!(rust_to_pg_type::<serde_json::Value>(), "jsonb");
+    assert_eq!(rust_to_pg_type::<Vec<u8>>(), "bytea");
+    assert_eq!(rust_to_pg_type::<Vec<i32>>(), "integer[]");
+    assert_eq!(rust_to_pg_type::<Vec<Vec<String>>>(), "text[][]");
+    assert_eq!(rust_to_pg_type::<Option<Vec<u8>>>(), "bytea");
+    assert_eq!(rust_to_pg_type::<vyuh::rust_decimal::Decimal>(), "numeric");
+
+    assert!(<Option<i32> as PgType>::nullable());
+    assert!(!<Vec<Option<i32>> as PgType>::nullable());
+    assert!(!<i32 as PgType>::nullable());
+}
+
+struct Money;
+
+impl PgType for Money {
+    fn pg_type() -> std::borrow::Cow<'static, str> {
+        "numeric(12, 2)".into()
+    }
+}
+
+#[test]
+fn custom_types_choose_their_column_type() {
+    assert_eq!(rust_to_pg_type::<Money>(), "numeric(12, 2)");
+    assert_eq!(rust_to_pg_type::<Vec<Money>>(), "numeric(12, 2)[]");
+}
@@ -0,0 +1,42 @@
+//! `with_role_filter` against a real Postgres server, ignored by default:
+//! `TEST_DATABASE_URL=postgres://... cargo test --features postgres --test db_owned -- --ignored`.
+#![cfg(feature = "postgres")]
+
+use vyuh::auth::AuthUser;
+use vyuh::db::{self, ArgValue, Bindable, DbPool, FilteredBuilder, Owned, QueryError, Scannable};
+
+#[derive(Debug, Clone, PartialEq, Bindable, Scannable)]
+struct Invoice {
+    total: i64,
+}
+
+impl Owned for Invoice {
+    fn owner_key(user: &AuthUser) -> Result<ArgValue, QueryError> {
+        let id: i64 = user
+            .key
+            .parse()
+            .map_err(|_| QueryError::BindError(format!("owner key '{}'", user.key)))?;
+        Ok(ArgValue::new(id))
+    }
+}
+
+#[tokio::test]
+#[ignore] // Needs a Postgres server; see the module docs.
+async fn role_filter_matches_a_bigint_owner_column() {
+    let guard = vyuh::testing::mock_db().await;
+    let mut pool = DbPool::from_pool(guard.pool().clone());
+    sqlx::raw_sql(
+        "CREATE TABLE invoices (total BIGINT NOT NULL, owner_id BIGINT NOT NULL);
+         INSERT INTO invoices VALUES (10, 7), (20, 8);",
+    )
+    .execute(pool.as_sqlx())
+    .await
+    .unwrap();
+
+    let own: Vec<Invoice> = db::select("invoices")
+        .with_role_filter::<Invoice>(&AuthUser::new("7", 0))
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(own, vec![Invoice { total: 10 }]);
+}
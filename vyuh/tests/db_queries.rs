@@ -0,0 +1,1092 @@
+#![cfg(not(any(feature = "postgres", feature = "mysql")))]
+
+use vyuh::auth::AuthUser;
+use vyuh::db::mock::{DbCallKind, MockDBSession, PlannedCall, PlannedResponse};
+use vyuh::db::{
+    self, ArgValue, Bindable, ColumnEnum, DbConf, DbPool, Filterable, FilteredBuilder, Owned,
+    PoolPressure, QueryError, Scannable, Statement, col,
+};
+use vyuh::events::EventBus;
+
+#[derive(Debug, Clone, PartialEq, Bindable, Scannable, serde::Serialize)]
+struct Person {
+    name: String,
+    age: i64,
+}
+
+async fn test_pool() -> DbPool {
+    let conf = DbConf {
+        url: "sqlite::memory:".to_string(),
+        min_connections: 1,
+        max_connections: 1,
+        lazy: false,
+        ..Default::default()
+    };
+    DbPool::from_conf(&conf).await.unwrap()
+}
+
+async fn create_table(pool: &DbPool, table: &str) {
+    sqlx::query(&format!(
+        "CREATE TABLE {} (name TEXT NOT NULL, age INTEGER NOT NULL)",
+        table
+    ))
+    .execute(pool.as_sqlx())
+    .await
+    .unwrap();
+}
+
+fn person(name: &str, age: i64) -> Person {
+    Person {
+        name: name.to_string(),
+        age,
+    }
+}
+
+#[tokio::test]
+async fn cached_select_is_invalidated_by_writes() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "cached_people").await;
+    db::insert("cached_people")
+        .row(&person("Ada", 36))
+        .execute(&mut pool)
+        .await
+        .unwrap();
+
+    let query = db::select("cached_people")
+        .cache_tag("cached_people")
+        .order_by("name", true);
+    let first: Vec<Person> = query.clone().all_cached(&mut pool).await.unwrap();
+    assert_eq!(first, vec![person("Ada", 36)]);
+
+    sqlx::query("INSERT INTO cached_people (name, age) VALUES ('Raw', 1)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    let memoized: Vec<Person> = query.clone().all_cached(&mut pool).await.unwrap();
+    assert_eq!(memoized, first);
+
+    db::insert("cached_people")
+        .row(&person("Bo", 20))
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    let fresh: Vec<Person> = query.all_cached(&mut pool).await.unwrap();
+    assert_eq!(
+        fresh,
+        vec![person("Ada", 36), person("Bo", 20), person("Raw", 1)]
+    );
+}
+
+#[tokio::test]
+async fn manual_invalidation_drops_cached_rows() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "tagged_people").await;
+
+    let query = db::select("tagged_people").cache_tag("directory");
+    let empty: Vec<Person> = query.clone().all_cached(&mut pool).await.unwrap();
+    assert!(empty.is_empty());
+
+    sqlx::query("INSERT INTO tagged_people (name, age) VALUES ('Grace', 45)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    pool.cache().invalidate("directory");
+
+    let rows: Vec<Person> = query.all_cached(&mut pool).await.unwrap();
+    assert_eq!(rows, vec![person("Grace", 45)]);
+}
+
+#[tokio::test]
+async fn query_cache_is_per_pool_and_bounded() {
+    let conf = DbConf {
+        url: "sqlite::memory:".to_string(),
+        min_connections: 1,
+        max_connections: 1,
+        lazy: false,
+        query_cache_entries: 1,
+        ..Default::default()
+    };
+    let mut pool = DbPool::from_conf(&conf).await.unwrap();
+    let mut other = test_pool().await;
+    for db in [&pool, &other] {
+        create_table(db, "scoped_people").await;
+    }
+    db::insert("scoped_people")
+        .row(&person("Ada", 36))
+        .execute(&mut pool)
+        .await
+        .unwrap();
+
+    let query = db::select("scoped_people");
+    let rows: Vec<Person> = query.clone().all_cached(&mut pool).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    let rows: Vec<Person> = query.clone().all_cached(&mut other).await.unwrap();
+    assert!(rows.is_empty(), "a pool must not see another pool's rows");
+
+    sqlx::query("INSERT INTO scoped_people (name, age) VALUES ('Raw', 1)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    let _: Vec<Person> = db::select("scoped_people")
+        .filter("age > 0")
+        .all_cached(&mut pool)
+        .await
+        .unwrap();
+    let rows: Vec<Person> = query.all_cached(&mut pool).await.unwrap();
+    assert_eq!(rows.len(), 2, "the oldest entry is evicted past capacity");
+}
+
+#[derive(Debug, Clone, PartialEq, Scannable)]
+struct Note {
+    title: String,
+}
+
+const NOTE_ADMIN: u64 = 1 << 3;
+
+impl Owned for Note {
+    fn owner_column() -> &'static str {
+        "author"
+    }
+
+    fn bypass_roles() -> u64 {
+        NOTE_ADMIN
+    }
+}
+
+#[tokio::test]
+async fn role_filter_limits_rows_to_owner() {
+    let mut pool = test_pool().await;
+    sqlx::query(
+        "CREATE TABLE owned_notes (title TEXT NOT NULL, author TEXT NOT NULL);
+         INSERT INTO owned_notes VALUES ('mine', 'u-1'), ('theirs', 'u-2');",
+    )
+    .execute(pool.as_sqlx())
+    .await
+    .unwrap();
+
+    let owner = AuthUser::new("u-1", 0);
+    let own: Vec<Note> = db::select("owned_notes")
+        .with_role_filter::<Note>(&owner)
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        own,
+        vec![Note {
+            title: "mine".to_string()
+        }]
+    );
+
+    let admin = AuthUser::new("u-3", NOTE_ADMIN);
+    let all = db::select("owned_notes")
+        .with_role_filter::<Note>(&admin)
+        .for_model::<Note>()
+        .count(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(all, 2);
+
+    let leaked = db::select("owned_notes")
+        .filter("title = 'theirs' OR 1 = 1")
+        .with_role_filter::<Note>(&owner)
+        .or_filter("title = 'theirs'")
+        .for_model::<Note>()
+        .count(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(leaked, 1);
+}
+
+#[derive(Debug, Clone, PartialEq, Bindable, Scannable)]
+struct Invoice {
+    total: i64,
+}
+
+impl Owned for Invoice {
+    fn owner_key(user: &AuthUser) -> Result<ArgValue, QueryError> {
+        let id: i64 = user
+            .key
+            .parse()
+            .map_err(|_| QueryError::BindError(format!("owner key '{}'", user.key)))?;
+        Ok(ArgValue::new(id))
+    }
+}
+
+#[tokio::test]
+async fn role_filter_binds_the_typed_owner_key() {
+    let mut pool = test_pool().await;
+    sqlx::query(
+        "CREATE TABLE invoices (total INTEGER NOT NULL, owner_id INTEGER NOT NULL);
+         INSERT INTO invoices VALUES (10, 7), (20, 8);",
+    )
+    .execute(pool.as_sqlx())
+    .await
+    .unwrap();
+
+    let own: Vec<Invoice> = db::select("invoices")
+        .with_role_filter::<Invoice>(&AuthUser::new("7", 0))
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(own, vec![Invoice { total: 10 }]);
+
+    let err = db::select("invoices")
+        .with_role_filter::<Invoice>(&AuthUser::new("not-a-number", 0))
+        .all::<Invoice, _>(&mut pool)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, db::DbError::QuerySet(QueryError::BindError(_))),
+        "{err}"
+    );
+}
+
+#[derive(Filterable)]
+#[allow(non_snake_case)]
+struct PersonFilter {
+    age__gte: i64,
+    #[filter(op = "lt", column = "age")]
+    younger_than: i64,
+    name__icontains: String,
+}
+
+#[tokio::test]
+async fn filter_struct_applies_suffix_operators() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "filtered_people").await;
+    for row in [
+        person("Ada", 36),
+        person("Adam", 17),
+        person("Bo_b", 40),
+        person("Bob", 41),
+        person("Madison", 70),
+    ] {
+        db::insert("filtered_people")
+            .row(&row)
+            .execute(&mut pool)
+            .await
+            .unwrap();
+    }
+
+    let filters = PersonFilter {
+        age__gte: 18,
+        younger_than: 65,
+        name__icontains: "AD".to_string(),
+    };
+    let rows: Vec<Person> = db::select("filtered_people")
+        .filter_with(&filters)
+        .order_by("name", true)
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![person("Ada", 36)]);
+
+    let filters = PersonFilter {
+        age__gte: 0,
+        younger_than: 100,
+        name__icontains: "o_".to_string(),
+    };
+    let rows: Vec<Person> = db::select("filtered_people")
+        .filter_with(&filters)
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![person("Bo_b", 40)]);
+}
+
+#[derive(Default, Filterable)]
+#[allow(non_snake_case)]
+struct OptionalPersonFilter {
+    name: Option<String>,
+    age__gte: Option<i64>,
+    age__lt: Option<i64>,
+}
+
+#[tokio::test]
+async fn filter_struct_skips_none_fields() {
+    let mut session = MockDBSession::new();
+    session.plan_fetch_scalar_ok("COUNT", 1i64);
+    let filters = OptionalPersonFilter {
+        age__gte: Some(18),
+        ..OptionalPersonFilter::default()
+    };
+
+    db::select("people")
+        .filter_with(&filters)
+        .with_deleted()
+        .count(&mut session)
+        .await
+        .unwrap();
+
+    let sql = &session.recorded[0].stmt.sql;
+    assert!(sql.contains("age >= "), "{sql}");
+    assert!(!sql.contains("name"), "{sql}");
+    assert!(!sql.contains("age < "), "{sql}");
+}
+
+#[tokio::test]
+async fn filter_optional_only_adds_predicate_for_some() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "optional_people").await;
+    db::insert("optional_people")
+        .rows(&[person("Ada", 36), person("Bob", 17)])
+        .execute(&mut pool)
+        .await
+        .unwrap();
+
+    let unfiltered = db::select("optional_people")
+        .for_model::<Person>()
+        .filter_optional("age >= :min_age", None::<i64>);
+    assert_eq!(unfiltered.count(&mut pool).await.unwrap(), 2);
+
+    let named = db::select("optional_people")
+        .for_model::<Person>()
+        .filter_optional("age >= :min_age", Some(18i64));
+    assert_eq!(named.count(&mut pool).await.unwrap(), 1);
+
+    let positional = db::select("optional_people")
+        .for_model::<Person>()
+        .filter_optional("name = ?", Some("Bob".to_string()));
+    assert_eq!(positional.count(&mut pool).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn or_groups_combine_with_and_filters() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "grouped_people").await;
+    db::insert("grouped_people")
+        .rows(&[person("Ada", 36), person("Bob", 17), person("Cy", 52)])
+        .execute(&mut pool)
+        .await
+        .unwrap();
+
+    let query = db::select("grouped_people")
+        .or_group(|g| {
+            g.filter("name = :first")
+                .filter("name = ?")
+                .bind_as("first", "Ada".to_string())
+                .bind("Bob".to_string())
+        })
+        .filter("age > :min")
+        .bind_as("min", 18i64);
+    let rows: Vec<Person> = query.all(&mut pool).await.unwrap();
+    assert_eq!(rows, vec![person("Ada", 36)]);
+
+    let rows: Vec<Person> = db::select("grouped_people")
+        .filter("age < 18")
+        .or_filter("name = 'Cy'")
+        .order_by("name", true)
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![person("Bob", 17), person("Cy", 52)]);
+
+    let nested = db::select("grouped_people")
+        .for_model::<Person>()
+        .or_group(|g| {
+            g.and_group(|g| g.filter("age > 30").filter("age < 40"))
+                .filter_optional("name = :other", Some("Bob".to_string()))
+                .filter_optional("name = :missing", None::<String>)
+        });
+    assert_eq!(nested.count(&mut pool).await.unwrap(), 2);
+
+    let empty = db::select("grouped_people")
+        .for_model::<Person>()
+        .or_group(|g| g);
+    assert_eq!(empty.count(&mut pool).await.unwrap(), 3);
+
+    let single = db::select("grouped_people")
+        .for_model::<Person>()
+        .and_group(|g| g.filter("name = 'Ada' OR name = 'Bob'"))
+        .filter("age > 18");
+    assert_eq!(single.count(&mut pool).await.unwrap(), 1);
+
+    let mixed = db::select("grouped_people")
+        .for_model::<Person>()
+        .or_group(|g| {
+            g.and_group(|g| g.filter("name = 'Cy' OR name = 'Bob'").filter("age > 18"))
+                .filter("name = 'Ada'")
+        });
+    assert_eq!(mixed.count(&mut pool).await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn column_conditions_bind_values_and_reject_bad_names() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "cond_people").await;
+    db::insert("cond_people")
+        .rows(&[person("Ada", 36), person("Bob", 17), person("Cy", 52)])
+        .execute(&mut pool)
+        .await
+        .unwrap();
+
+    let hostile = "Ada' OR '1' = '1".to_string();
+    let query = db::select("cond_people")
+        .for_model::<Person>()
+        .filter_cond(col("name").eq(hostile));
+    assert_eq!(query.count(&mut pool).await.unwrap(), 0);
+
+    let rows: Vec<Person> = db::select("cond_people")
+        .filter_cond(col("age").gt(18i64).and(col("age").lt(50i64)))
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![person("Ada", 36)]);
+
+    let query = db::select("cond_people")
+        .for_model::<Person>()
+        .filter_cond(
+            col("name")
+                .ilike("b%")
+                .or(col("name").in_(["Cy".to_string()])),
+        )
+        .filter_cond(col("age").is_not_null());
+    assert_eq!(query.count(&mut pool).await.unwrap(), 2);
+
+    let query = db::select("cond_people")
+        .for_model::<Person>()
+        .filter_cond(!col("name").like("%y"));
+    assert_eq!(query.count(&mut pool).await.unwrap(), 2);
+
+    let none = db::select("cond_people")
+        .for_model::<Person>()
+        .filter_cond(col("name").in_(Vec::<String>::new()));
+    assert_eq!(none.count(&mut pool).await.unwrap(), 0);
+
+    let err = db::select("cond_people")
+        .filter_cond(col("age = age OR 1").eq(1i64))
+        .for_model::<Person>()
+        .count(&mut pool)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, db::DbError::QuerySet(QueryError::InvalidColumn(_))),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn distinct_on_columns_lead_order_by() {
+    let mut session = MockDBSession::new();
+    session.plan(PlannedCall {
+        kind: DbCallKind::FetchAll,
+        sql_contains: Some("SELECT DISTINCT ON (name) "),
+        response: PlannedResponse::OkAnyVec(Box::new(Vec::<Person>::new())),
+    });
+
+    db::select("people")
+        .distinct_on(&["name"])
+        .order_by("age", false)
+        .all::<Person, _>(&mut session)
+        .await
+        .unwrap();
+
+    let sql = &session.recorded[0].stmt.sql;
+    assert!(sql.ends_with("ORDER BY name ASC, age DESC"), "{sql}");
+}
+
+#[tokio::test]
+async fn distinct_on_rejects_conflicting_order_by() {
+    let mut session = MockDBSession::new();
+
+    let err = db::select("people")
+        .distinct_on(&["name"])
+        .order_by("age", false)
+        .order_by("name", true)
+        .all::<Person, _>(&mut session)
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("DISTINCT ON"), "{err}");
+    assert!(session.recorded.is_empty());
+}
+
+#[tokio::test]
+async fn distinct_count_matches_the_projected_rows() {
+    let mut pool = test_pool().await;
+    sqlx::raw_sql(
+        "CREATE TABLE dup_people (id INTEGER PRIMARY KEY, name TEXT NOT NULL, age INTEGER NOT NULL);
+         INSERT INTO dup_people (name, age) VALUES ('Ada', 36), ('Ada', 36), ('Bob', 17);",
+    )
+    .execute(pool.as_sqlx())
+    .await
+    .unwrap();
+
+    let rows: Vec<Person> = db::select("dup_people")
+        .distinct()
+        .all(&mut pool)
+        .await
+        .unwrap();
+    let counted = db::select("dup_people")
+        .distinct()
+        .for_model::<Person>()
+        .count(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!((rows.len(), counted), (2, 2));
+    let page = db::select("dup_people")
+        .distinct()
+        .page::<Person, _>(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!((page.items.len(), page.total), (2, 2));
+}
+
+#[tokio::test]
+async fn insert_chunked_splits_large_batches() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "bulk_people").await;
+    let people: Vec<Person> = (0..20_000).map(|i| person("p", i)).collect();
+
+    let inserted = pool
+        .insert_chunked("bulk_people", &people, 0)
+        .await
+        .unwrap();
+    assert_eq!(inserted, 20_000);
+    let inserted = pool
+        .insert_chunked("bulk_people", &people[..5], 2)
+        .await
+        .unwrap();
+    assert_eq!(inserted, 5);
+
+    let total = db::select("bulk_people")
+        .for_model::<Person>()
+        .count(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(total, 20_005);
+}
+
+#[tokio::test]
+async fn copy_in_loads_rows() {
+    let pool = test_pool().await;
+    create_table(&pool, "copied_people").await;
+    let rows: Vec<Person> = (0..250).map(|i| person(&format!("p{i}"), i)).collect();
+    assert_eq!(pool.copy_in("copied_people", &rows).await.unwrap(), 250);
+    assert_eq!(
+        pool.copy_in::<Person>("copied_people", &[]).await.unwrap(),
+        0
+    );
+
+    let mut session = pool.clone();
+    let count = db::select("copied_people")
+        .for_model::<Person>()
+        .count(&mut session)
+        .await
+        .unwrap();
+    assert_eq!(count, 250);
+}
+
+#[tokio::test]
+async fn approx_count_estimates_known_table() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "counted_people").await;
+    let people: Vec<Person> = (0..3).map(|i| person("p", i)).collect();
+    pool.insert_chunked("counted_people", &people, 0)
+        .await
+        .unwrap();
+
+    let estimate = db::approx_count(&mut pool, "counted_people").await.unwrap();
+    assert!(estimate >= 0);
+    // SQLite keeps no row statistics, so the fallback is exact.
+    assert_eq!(estimate, 3);
+    assert!(db::approx_count(&mut pool, "people; --").await.is_err());
+}
+
+#[tokio::test]
+async fn empty_collections_are_safe_no_ops() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "listed_people").await;
+    let people = [person("Ada", 36), person("Bo", 7), person("Cy", 50)];
+    db::insert("listed_people")
+        .rows(&people)
+        .execute(&mut pool)
+        .await
+        .unwrap();
+
+    let picked: Vec<Person> = db::select("listed_people")
+        .filter_in("name", "names", ["Ada".to_string(), "Cy".to_string()])
+        .order_by("name", true)
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(picked, vec![person("Ada", 36), person("Cy", 50)]);
+
+    let none: Vec<String> = Vec::new();
+    let picked: Vec<Person> = db::select("listed_people")
+        .filter_in("name", "names", none.clone())
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert!(picked.is_empty());
+    let updated = db::update("listed_people")
+        .set(&person("X", 0))
+        .filter_in("name", "names", none.clone())
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    let deleted = db::delete("listed_people")
+        .for_model::<Person>()
+        .filter_in("name", "names", none)
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!((updated, deleted), (0, 0));
+
+    let empty: [Person; 0] = [];
+    let inserted = db::insert("listed_people")
+        .rows(&empty)
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(inserted, 0);
+    assert_eq!(
+        pool.insert_chunked("listed_people", &empty, 0)
+            .await
+            .unwrap(),
+        0
+    );
+    let total = db::select("listed_people")
+        .for_model::<Person>()
+        .count(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(total, 3);
+}
+
+#[derive(Debug, Clone, PartialEq, Bindable, Scannable)]
+#[schema(soft_delete = "deleted_at")]
+struct Memo {
+    name: String,
+}
+
+#[tokio::test]
+async fn soft_delete_models_hide_deleted_rows() {
+    let mut pool = test_pool().await;
+    sqlx::query("CREATE TABLE memos (name TEXT NOT NULL, deleted_at TEXT NULL)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    let memos = ["a", "b", "c"].map(|name| Memo {
+        name: name.to_string(),
+    });
+    db::insert("memos")
+        .rows(&memos)
+        .execute(&mut pool)
+        .await
+        .unwrap();
+
+    let removed = db::delete("memos")
+        .for_model::<Memo>()
+        .filter("name = :name")
+        .bind_as("name", "b".to_string())
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(removed, 1);
+    // Already-deleted rows are not stamped again.
+    let again = db::delete("memos")
+        .for_model::<Memo>()
+        .filter("name = :name")
+        .bind_as("name", "b".to_string())
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(again, 0);
+
+    let live: Vec<Memo> = db::select("memos")
+        .order_by("name", true)
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(live, vec![memos[0].clone(), memos[2].clone()]);
+    let page = db::select("memos")
+        .page::<Memo, _>(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 2);
+    let all: Vec<Memo> = db::select("memos")
+        .with_deleted()
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(all.len(), 3);
+
+    let counted = db::select("memos")
+        .for_model::<Memo>()
+        .count(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(counted, 2);
+    let stamped: Option<String> =
+        sqlx::query_scalar("SELECT deleted_at FROM memos WHERE name = 'b'")
+            .fetch_one(pool.as_sqlx())
+            .await
+            .unwrap();
+    assert!(stamped.is_some());
+}
+
+#[tokio::test]
+async fn model_less_terminals_refuse_to_bypass_soft_delete() {
+    let mut pool = test_pool().await;
+    sqlx::query("CREATE TABLE memos (name TEXT NOT NULL, deleted_at TEXT NULL)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO memos (name, deleted_at) VALUES ('a', NULL), ('b', '2024-01-01')")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+
+    let counted = db::select("memos").count(&mut pool).await.unwrap_err();
+    let exists = db::select("memos").exists(&mut pool).await.unwrap_err();
+    let deleted = db::delete("memos").execute(&mut pool).await.unwrap_err();
+    for err in [counted, exists, deleted] {
+        assert!(
+            matches!(&err, db::DbError::QuerySet(QueryError::ModelNotSet(_))),
+            "{err}"
+        );
+    }
+
+    let live = db::select("memos").for_model::<Memo>();
+    assert_eq!(live.count(&mut pool).await.unwrap(), 1);
+    let every = db::select("memos").with_deleted();
+    assert_eq!(every.count(&mut pool).await.unwrap(), 2);
+    let removed = db::delete("memos")
+        .with_deleted()
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(removed, 2);
+}
+
+#[tokio::test]
+async fn soft_delete_guard_survives_or_filters_and_updates() {
+    let mut pool = test_pool().await;
+    sqlx::query("CREATE TABLE memos (name TEXT NOT NULL, deleted_at TEXT NULL)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    let memos = ["a", "b"].map(|name| Memo {
+        name: name.to_string(),
+    });
+    db::insert("memos")
+        .rows(&memos)
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    db::delete("memos")
+        .for_model::<Memo>()
+        .filter("name = 'a'")
+        .execute(&mut pool)
+        .await
+        .unwrap();
+
+    let live: Vec<Memo> = db::select("memos")
+        .filter("name = 'a' OR name = 'b'")
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(live, vec![memos[1].clone()]);
+    let counted = db::select("memos")
+        .filter("name = 'a' OR name = 'b'")
+        .for_model::<Memo>()
+        .count(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(counted, 1);
+    let widened = db::select("memos")
+        .for_model::<Memo>()
+        .or_filter("name = 'a'")
+        .count(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(widened, 0);
+
+    let renamed = Memo {
+        name: "z".to_string(),
+    };
+    let touched = db::update("memos")
+        .set(&renamed)
+        .filter("name = 'a' OR name = 'b'")
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(touched, 1);
+    let touched = db::update("memos")
+        .set(&renamed)
+        .filter("name = 'a'")
+        .with_deleted()
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(touched, 1);
+}
+
+#[derive(Debug, Clone, PartialEq, Bindable, Scannable)]
+struct Stamped {
+    name: String,
+    #[column(auto_now_add)]
+    created_at: Option<String>,
+    #[column(auto_now)]
+    updated_at: Option<String>,
+}
+
+#[tokio::test]
+async fn auto_timestamps_are_set_in_sql_not_bound() {
+    assert_eq!(<Stamped as Bindable>::bind_column_names(), vec!["name"]);
+    let mut pool = test_pool().await;
+    sqlx::query("CREATE TABLE stamped (name TEXT NOT NULL, created_at TEXT, updated_at TEXT)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+
+    // Client-supplied timestamps are ignored.
+    let spoofed = Stamped {
+        name: "a".to_string(),
+        created_at: Some("1999-01-01".to_string()),
+        updated_at: None,
+    };
+    db::insert("stamped")
+        .row(&spoofed)
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    let row: Stamped = db::select("stamped").one(&mut pool).await.unwrap();
+    let created = row.created_at.clone().unwrap();
+    assert_ne!(created, "1999-01-01");
+    assert!(row.updated_at.is_some());
+
+    sqlx::query("UPDATE stamped SET updated_at = NULL")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    db::update("stamped")
+        .set(&Stamped {
+            name: "b".to_string(),
+            created_at: None,
+            updated_at: None,
+        })
+        .execute(&mut pool)
+        .await
+        .unwrap();
+    let row: Stamped = db::select("stamped").one(&mut pool).await.unwrap();
+    assert_eq!(row.name, "b");
+    assert_eq!(row.created_at, Some(created));
+    assert!(row.updated_at.is_some());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ColumnEnum)]
+enum Status {
+    Draft,
+    InReview,
+    Published,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ColumnEnum)]
+enum Kind {
+    Staff = 1,
+    Customer = 7,
+}
+
+#[derive(Debug, Clone, PartialEq, Bindable, Scannable)]
+struct Ticket {
+    #[column(store_as = "text")]
+    status: Status,
+    #[column(store_as = "int", name = "kind_code")]
+    kind: Kind,
+}
+
+#[tokio::test]
+async fn enum_columns_round_trip_as_text_and_int() {
+    assert_eq!(Status::InReview.to_text(), "in_review");
+    assert_eq!(Status::from_text("published"), Some(Status::Published));
+    assert_eq!(Kind::Customer.to_int(), 7);
+    assert_eq!(Kind::from_int(2), None);
+
+    let mut pool = test_pool().await;
+    sqlx::query("CREATE TABLE tickets (status TEXT NOT NULL, kind_code SMALLINT NOT NULL)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    let tickets = [
+        Ticket {
+            status: Status::InReview,
+            kind: Kind::Customer,
+        },
+        Ticket {
+            status: Status::Draft,
+            kind: Kind::Staff,
+        },
+    ];
+    db::insert("tickets")
+        .rows(&tickets)
+        .execute(&mut pool)
+        .await
+        .unwrap();
+
+    let stored: Vec<(String, i64)> =
+        sqlx::query_as("SELECT status, kind_code FROM tickets ORDER BY kind_code")
+            .fetch_all(pool.as_sqlx())
+            .await
+            .unwrap();
+    assert_eq!(
+        stored,
+        vec![("draft".to_string(), 1), ("in_review".to_string(), 7)]
+    );
+
+    let loaded: Vec<Ticket> = db::select("tickets")
+        .filter("status = :status")
+        .bind_as("status", Status::InReview.to_text().to_string())
+        .all(&mut pool)
+        .await
+        .unwrap();
+    assert_eq!(loaded, vec![tickets[0].clone()]);
+
+    sqlx::query("UPDATE tickets SET status = 'archived'")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    assert!(
+        db::select("tickets")
+            .all::<Ticket, _>(&mut pool)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn with_retries_reruns_transaction_on_temporary_errors() {
+    let mut pool = test_pool().await;
+    create_table(&pool, "retried_people").await;
+
+    let mut calls = 0;
+    let inserted = pool
+        .with_retries(3, async |tx| {
+            calls += 1;
+            db::insert("retried_people")
+                .row(&person("Ada", calls))
+                .execute(tx)
+                .await?;
+            if calls == 1 {
+                return Err(db::DbError::Temporary);
+            }
+            Ok(calls)
+        })
+        .await
+        .unwrap();
+    assert_eq!(inserted, 2);
+    let rows: Vec<Person> = db::select("retried_people").all(&mut pool).await.unwrap();
+    assert_eq!(rows, vec![person("Ada", 2)]);
+
+    let mut calls = 0;
+    let result: Result<(), _> = pool
+        .with_retries(3, async |_tx| {
+            calls += 1;
+            Err(db::DbError::Temporary)
+        })
+        .await;
+    assert!(matches!(result, Err(db::DbError::Temporary)));
+    assert_eq!(calls, 3);
+}
+
+#[tokio::test]
+async fn saturated_pool_publishes_pressure_event() {
+    let conf = DbConf {
+        url: "sqlite::memory:".to_string(),
+        min_connections: 1,
+        max_connections: 1,
+        lazy: false,
+        slow_acquire_ms: Some(10),
+        ..Default::default()
+    };
+    let bus = EventBus::default();
+    let mut pressure = bus.subscribe::<PoolPressure>();
+    let pool = DbPool::from_conf(&conf).await.unwrap().with_events(bus);
+
+    let held = pool.begin().await.unwrap();
+    let waiting = tokio::spawn({
+        let mut pool = pool.clone();
+        async move {
+            db::select("sqlite_master")
+                .with_deleted()
+                .count(&mut pool)
+                .await
+        }
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    held.rollback().await.unwrap();
+    waiting.await.unwrap().unwrap();
+
+    let event = pressure.recv().await.unwrap();
+    assert!(event.waited >= std::time::Duration::from_millis(10));
+    assert_eq!(event.size, 1);
+}
+
+#[tokio::test]
+async fn opening_a_connection_below_max_is_not_pressure() {
+    let conf = DbConf {
+        url: "sqlite::memory:".to_string(),
+        min_connections: 1,
+        max_connections: 2,
+        lazy: false,
+        slow_acquire_ms: Some(0),
+        ..Default::default()
+    };
+    let bus = EventBus::default();
+    let mut pressure = bus.subscribe::<PoolPressure>();
+    let pool = DbPool::from_conf(&conf).await.unwrap().with_events(bus);
+
+    // The only connection is busy, so this acquire opens a second one.
+    let held = pool.begin().await.unwrap();
+    db::select("sqlite_master")
+        .with_deleted()
+        .count(&mut pool.clone())
+        .await
+        .unwrap();
+    held.rollback().await.unwrap();
+
+    let event = tokio::time::timeout(std::time::Duration::from_millis(50), pressure.recv()).await;
+    assert!(event.is_err(), "unexpected {event:?}");
+}
+
+#[tokio::test]
+async fn statement_debug_query_shows_bound_values() {
+    let stmt = Statement::from_str("SELECT * FROM people WHERE name = ? AND nick = ? AND age > ?")
+        .bind("it's alice".to_string())
+        .bind(None::<String>)
+        .bind(30_i64);
+    let shown = stmt.debug_query_with_args();
+    let sigil = if cfg!(feature = "postgres") { '$' } else { '?' };
+    if cfg!(debug_assertions) {
+        assert!(shown.ends_with(&format!(
+            " -- {sigil}1 = 'it''s alice', {sigil}2 = NULL, {sigil}3 = 30"
+        )));
+    } else {
+        assert!(shown.ends_with(&format!(" -- {sigil}1 = ?, {sigil}2 = ?, {sigil}3 = ?")));
+    }
+    assert_eq!(
+        Statement::from_str("SELECT 1").debug_query_with_args(),
+        "SELECT 1"
+    );
+
+    let mut session = MockDBSession::new();
+    session.plan_fetch_scalar_ok("COUNT", 1i64);
+    db::select("people")
+        .filter("name = :name")
+        .bind_as("name", "o'hara".to_string())
+        .filter_in("age", "age", [30_i64, 40])
+        .for_model::<Person>()
+        .count(&mut session)
+        .await
+        .unwrap();
+    let shown = session.recorded[0].stmt.debug_query_with_args();
+    if cfg!(debug_assertions) {
+        assert!(
+            shown.ends_with(&format!(
+                " -- {sigil}1 = 'o''hara', {sigil}2 = 30, {sigil}3 = 40"
+            )),
+            "{shown}"
+        );
+    }
+}
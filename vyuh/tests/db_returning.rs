@@ -0,0 +1,81 @@
+//! Postgres-only `INSERT ... RETURNING` helpers. The mock tests run without a
+//! server; the round trip is ignored by default:
+//! `TEST_DATABASE_URL=postgres://... cargo test --features postgres --test db_returning -- --ignored`.
+#![cfg(feature = "postgres")]
+
+use vyuh::db::mock::MockDBSession;
+use vyuh::db::{self, Bindable, DbPool, QueryError};
+
+#[derive(Debug, Clone, PartialEq, Bindable)]
+struct Account {
+    name: String,
+    email: String,
+}
+
+fn account() -> Account {
+    Account {
+        name: "Ada".to_string(),
+        email: "ada@example.com".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn returning_columns_limits_returning_and_scalar_decodes_it() {
+    let mut session = MockDBSession::new();
+    session.plan_fetch_scalar_ok("RETURNING", 7i64);
+
+    let id: i64 = db::insert("accounts")
+        .row(&account())
+        .returning_columns(&["id"])
+        .scalar(&mut session)
+        .await
+        .unwrap();
+
+    assert_eq!(id, 7);
+    let sql = &session.recorded[0].stmt.sql;
+    assert!(
+        sql.starts_with("INSERT INTO accounts (name, email) VALUES ($1, $2)"),
+        "{sql}"
+    );
+    assert!(sql.ends_with(" RETURNING id"), "{sql}");
+}
+
+#[tokio::test]
+async fn returning_columns_rejects_invalid_names() {
+    let mut session = MockDBSession::new();
+    let err = db::insert("accounts")
+        .row(&account())
+        .returning_columns(&["id; DROP TABLE accounts"])
+        .scalar::<i64, _>(&mut session)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            db::DbError::QuerySet(QueryError::InvalidIdentifier(_))
+        ),
+        "{err}"
+    );
+    assert!(session.recorded.is_empty());
+}
+
+#[tokio::test]
+#[ignore] // Needs a Postgres server; see the module docs.
+async fn scalar_decodes_the_generated_id() {
+    let guard = vyuh::testing::mock_db().await;
+    let mut pool = DbPool::from_pool(guard.pool().clone());
+    sqlx::query("CREATE TABLE accounts (id BIGSERIAL PRIMARY KEY, name TEXT, email TEXT)")
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+
+    for expected in 1..=2i64 {
+        let id: i64 = db::insert("accounts")
+            .row(&account())
+            .returning_columns(&["id"])
+            .scalar(&mut pool)
+            .await
+            .unwrap();
+        assert_eq!(id, expected);
+    }
+}
@@ -0,0 +1,151 @@
+//! Property test: random models survive a `Bindable` insert and `Scannable`
+//! select unchanged.
+//!
+//! SQLite runs in-process. Postgres needs a server and is ignored by default:
+//! `TEST_DATABASE_URL=postgres://... cargo test --features postgres --test db_roundtrip -- --ignored`.
+//! Set `VYUH_PROPTEST_SEED` to replay a failure and `VYUH_PROPTEST_CASES` to
+//! change the number of generated rows.
+#![cfg(not(feature = "mysql"))]
+
+use chrono::{DateTime, Utc};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use vyuh::db::{self, Bindable, DbPool, FilteredBuilder, Scannable};
+
+#[derive(Debug, Clone, PartialEq, Bindable, Scannable)]
+struct Sample {
+    id: i64,
+    small: i32,
+    ratio: f64,
+    flag: bool,
+    name: String,
+    note: Option<String>,
+    score: Option<i64>,
+    at: DateTime<Utc>,
+    seen_at: Option<DateTime<Utc>>,
+    token: uuid::Uuid,
+    #[column(array)]
+    tags: Vec<String>,
+    #[column(array)]
+    ranks: Option<Vec<i64>>,
+}
+
+#[cfg(feature = "postgres")]
+const CREATE_TABLE: &str = "CREATE TABLE samples (
+    id BIGINT PRIMARY KEY, small INTEGER NOT NULL, ratio DOUBLE PRECISION NOT NULL,
+    flag BOOLEAN NOT NULL, name TEXT NOT NULL, note TEXT, score BIGINT,
+    at TIMESTAMPTZ NOT NULL, seen_at TIMESTAMPTZ, token UUID NOT NULL, tags TEXT[] NOT NULL,
+    ranks BIGINT[]
+)";
+
+#[cfg(not(feature = "postgres"))]
+const CREATE_TABLE: &str = "CREATE TABLE samples (
+    id INTEGER PRIMARY KEY, small INTEGER NOT NULL, ratio REAL NOT NULL,
+    flag BOOLEAN NOT NULL, name TEXT NOT NULL, note TEXT, score INTEGER,
+    at TEXT NOT NULL, seen_at TEXT, token BLOB NOT NULL, tags TEXT NOT NULL, ranks TEXT
+)";
+
+/// Characters that have tripped up escaping, encoding, or LIKE handling.
+const TRICKY_CHARS: &[char] = &[
+    'a', 'Z', '0', ' ', '\'', '"', '\\', '%', '_', '!', ';', '\n', '\t', 'é', 'ß', '日', '🦀',
+];
+
+const EDGE_FLOATS: &[f64] = &[
+    0.0,
+    -0.0,
+    1.0,
+    -1.5,
+    f64::MIN,
+    f64::MAX,
+    f64::EPSILON,
+    1e-300,
+];
+
+fn text(rng: &mut StdRng) -> String {
+    let len = rng.random_range(0..24);
+    (0..len)
+        .map(|_| TRICKY_CHARS[rng.random_range(0..TRICKY_CHARS.len())])
+        .collect()
+}
+
+fn float(rng: &mut StdRng) -> f64 {
+    if rng.random_bool(0.3) {
+        return EDGE_FLOATS[rng.random_range(0..EDGE_FLOATS.len())];
+    }
+    rng.random_range(-1e12..1e12)
+}
+
+/// Timestamps are truncated to microseconds, the finest Postgres stores.
+fn timestamp(rng: &mut StdRng) -> DateTime<Utc> {
+    let micros = rng.random_range(-62_135_596_800_000_000i64..253_402_300_799_000_000);
+    DateTime::from_timestamp_micros(micros).unwrap_or_default()
+}
+
+fn sample(rng: &mut StdRng, id: i64) -> Sample {
+    Sample {
+        id,
+        small: rng.random(),
+        ratio: float(rng),
+        flag: rng.random(),
+        name: text(rng),
+        note: rng.random_bool(0.5).then(|| text(rng)),
+        score: rng.random_bool(0.5).then(|| rng.random()),
+        at: timestamp(rng),
+        seen_at: rng.random_bool(0.5).then(|| timestamp(rng)),
+        token: uuid::Uuid::from_u128(rng.random()),
+        tags: (0..rng.random_range(0..4)).map(|_| text(rng)).collect(),
+        ranks: rng
+            .random_bool(0.5)
+            .then(|| (0..rng.random_range(0..4)).map(|_| rng.random()).collect()),
+    }
+}
+
+fn env_u64(name: &str) -> Option<u64> {
+    std::env::var(name).ok().and_then(|v| v.parse().ok())
+}
+
+async fn assert_roundtrip(mut pool: DbPool) {
+    sqlx::query(CREATE_TABLE)
+        .execute(pool.as_sqlx())
+        .await
+        .unwrap();
+    let seed = env_u64("VYUH_PROPTEST_SEED").unwrap_or_else(rand::random);
+    let cases = env_u64("VYUH_PROPTEST_CASES").unwrap_or(256) as i64;
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for id in 0..cases {
+        let expected = sample(&mut rng, id);
+        db::insert("samples")
+            .row(&expected)
+            .execute(&mut pool)
+            .await
+            .unwrap_or_else(|err| panic!("insert failed (seed {seed}): {err} for {expected:?}"));
+        let actual: Sample = db::select("samples")
+            .filter("id = :id")
+            .bind_as("id", id)
+            .one(&mut pool)
+            .await
+            .unwrap_or_else(|err| panic!("select failed (seed {seed}): {err} for {expected:?}"));
+        assert_eq!(actual, expected, "round trip changed the row (seed {seed})");
+    }
+}
+
+#[cfg(not(feature = "postgres"))]
+#[tokio::test]
+async fn random_models_round_trip_through_sqlite() {
+    let conf = db::DbConf {
+        url: "sqlite::memory:".to_string(),
+        min_connections: 1,
+        max_connections: 1,
+        lazy: false,
+        ..Default::default()
+    };
+    assert_roundtrip(DbPool::from_conf(&conf).await.unwrap()).await;
+}
+
+#[cfg(feature = "postgres")]
+#[tokio::test]
+#[ignore] // Needs a Postgres server; see the module docs.
+async fn random_models_round_trip_through_postgres() {
+    let guard = vyuh::testing::mock_db().await;
+    assert_roundtrip(DbPool::from_pool(guard.pool().clone())).await;
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use vyuh::{
+    SiteConf, bundles,
+    email::{self, Email, MemoryMailer},
+    services::ServiceInstance,
+    tasks::TaskStatus,
+};
+
+fn test_conf() -> SiteConf {
+    SiteConf {
+        log_init: false,
+        logging: vyuh::logging::LoggingConf {
+            env_prefix: None,
+            rules: vec![],
+        },
+        ..SiteConf::default()
+    }
+}
+
+async fn memory_mailer() -> ServiceInstance<MemoryMailer> {
+    MemoryMailer::new().into()
+}
+
+#[tokio::test]
+async fn submitted_email_task_is_delivered_by_the_mailer() {
+    let bundle = email::bundle().merge(bundles::bundle([bundles::service(memory_mailer)]));
+    let site = vyuh::Site::build(test_conf(), bundle).await.unwrap();
+
+    let body = site
+        .render_template_string(
+            "Welcome, {{ name }}!",
+            &serde_json::json!({ "name": "Ada" }),
+        )
+        .unwrap();
+    let message = Email::new("ada@example.com", "Welcome").text(body);
+    let id = site.tasks().submit(message.clone()).await.unwrap();
+
+    let mut status = None;
+    for _ in 0..100 {
+        status = site.tasks().get(id).await.unwrap().map(|task| task.status);
+        if status == Some(TaskStatus::Succeeded) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    assert_eq!(status, Some(TaskStatus::Succeeded));
+    let mailer = site.service::<MemoryMailer>().unwrap();
+    assert_eq!(mailer.sent(), vec![message]);
+    assert_eq!(mailer.sent()[0].text, "Welcome, Ada!");
+    site.shutdown_and_wait().await;
+}
+
+#[test]
+fn cc_recipients_go_to_the_cc_header() {
+    let message = Email::new("ada@example.com", "Invoice")
+        .cc("billing@example.com")
+        .cc("audit@example.com")
+        .from("shop@example.com");
+
+    assert_eq!(message.to, vec!["ada@example.com"]);
+    assert_eq!(
+        message.headers(),
+        vec![
+            ("To", "ada@example.com".to_string()),
+            ("Cc", "billing@example.com, audit@example.com".to_string()),
+            ("From", "shop@example.com".to_string()),
+            ("Subject", "Invoice".to_string()),
+        ]
+    );
+
+    // Messages queued before `cc` existed still deserialize.
+    let queued: Email = serde_json::from_value(serde_json::json!({
+        "to": ["ada@example.com"],
+        "from": null,
+        "subject": "Welcome",
+        "text": "",
+        "html": null,
+    }))
+    .unwrap();
+    assert!(queued.cc.is_empty());
+    assert_eq!(queued.headers().len(), 2);
+}
@@ -0,0 +1,115 @@
+use axum::response::{IntoResponse, Redirect, Response};
+use vyuh::{
+    SiteConf, bundles,
+    flash::{FLASH_COOKIE, Flash},
+    routes::{Json, StatusCode},
+    testing::TestClient,
+};
+
+fn test_conf() -> SiteConf {
+    SiteConf {
+        log_init: false,
+        logging: vyuh::logging::LoggingConf {
+            env_prefix: None,
+            rules: vec![],
+        },
+        ..SiteConf::default()
+    }
+}
+
+#[bundles::route(path = "/save")]
+async fn save(flash: Flash) -> (Flash, Response) {
+    (
+        flash.success("Saved").warning("Check totals"),
+        Redirect::to("/show").into_response(),
+    )
+}
+
+#[bundles::route(path = "/save", method = "POST")]
+async fn save_form(flash: Flash) -> (Flash, Response) {
+    (
+        flash.success("Posted"),
+        Redirect::to("show").into_response(),
+    )
+}
+
+#[bundles::route(path = "/show")]
+async fn show(flash: Flash) -> Json<Vec<String>> {
+    Json(
+        flash
+            .messages()
+            .iter()
+            .map(|message| format!("{:?}:{}", message.level, message.text))
+            .collect(),
+    )
+}
+
+#[tokio::test]
+async fn flash_survives_one_redirect_and_is_cleared_once_read() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { save, show })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+
+    let response = client.get("/save").send().await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    let token = response.cookie(FLASH_COOKIE).unwrap().value().to_string();
+    assert!(site.auth().decode(&token).is_err());
+
+    let response = client
+        .get("/show")
+        .header("cookie", &format!("{FLASH_COOKIE}={token}"))
+        .send()
+        .await
+        .assert_ok();
+    let cleared = response.cookie(FLASH_COOKIE).unwrap();
+    assert_eq!(cleared.value(), "");
+    assert_eq!(cleared.max_age(), Some(time::Duration::ZERO));
+    let messages: Vec<String> = response.json().await;
+    assert_eq!(messages, vec!["Success:Saved", "Warning:Check totals"]);
+
+    let tampered = format!("{}x", token);
+    let response = client
+        .get("/show")
+        .header("cookie", &format!("{FLASH_COOKIE}={tampered}"))
+        .send()
+        .await
+        .assert_ok();
+    let messages: Vec<String> = response.json().await;
+    assert!(messages.is_empty());
+
+    let response = client.get("/show").send().await.assert_ok();
+    assert!(response.cookie(FLASH_COOKIE).is_none());
+}
+
+#[tokio::test]
+async fn session_follows_post_redirect_get_with_flash_cookie() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { save_form, show })
+        .await
+        .unwrap();
+    let client = TestClient::new(site);
+    let session = client.session();
+
+    let response = session
+        .post("/save")
+        .send()
+        .await
+        .assert_redirect(StatusCode::SEE_OTHER, "show")
+        .follow_redirect(&session)
+        .await
+        .assert_ok();
+    let messages: Vec<String> = response.json().await;
+    assert_eq!(messages, vec!["Success:Posted"]);
+    assert!(session.cookie(FLASH_COOKIE).is_none());
+
+    // Without the session the flash cookie is not carried over.
+    let response = client
+        .post("/save")
+        .send()
+        .await
+        .follow_redirect(&client)
+        .await
+        .assert_ok();
+    let messages: Vec<String> = response.json().await;
+    assert!(messages.is_empty());
+}
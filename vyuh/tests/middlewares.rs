@@ -0,0 +1,798 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use vyuh::{
+    SiteConf, bundles,
+    db::DbError,
+    middlewares::{CorsConf, HttpConf, RetryConf},
+    routes::{
+        HttpMethod, IntoResponse, Json, Methods, Path, RequestId, RequestStore, RouteConf,
+        RouteInfo, StatusCode, layer_from,
+    },
+    testing::TestClient,
+};
+
+fn test_conf() -> SiteConf {
+    SiteConf {
+        log_init: false,
+        logging: vyuh::logging::LoggingConf {
+            env_prefix: None,
+            rules: vec![],
+        },
+        ..SiteConf::default()
+    }
+}
+
+fn retry_conf() -> SiteConf {
+    test_conf().http(HttpConf {
+        retry: RetryConf {
+            enabled: true,
+            ..RetryConf::default()
+        },
+        ..HttpConf::default()
+    })
+}
+
+static FLAKY_CALLS: AtomicUsize = AtomicUsize::new(0);
+static BROKEN_CALLS: AtomicUsize = AtomicUsize::new(0);
+static WRITE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[bundles::route(path = "/flaky")]
+async fn flaky() -> Result<Json<String>, DbError> {
+    if FLAKY_CALLS.fetch_add(1, Ordering::SeqCst) == 0 {
+        return Err(DbError::Temporary);
+    }
+    Ok(Json("ok".to_string()))
+}
+
+#[bundles::route(path = "/broken")]
+async fn broken() -> Result<Json<String>, DbError> {
+    BROKEN_CALLS.fetch_add(1, Ordering::SeqCst);
+    Err(DbError::Temporary)
+}
+
+#[bundles::route(path = "/write", method = "POST")]
+async fn write() -> Result<Json<String>, DbError> {
+    WRITE_CALLS.fetch_add(1, Ordering::SeqCst);
+    Err(DbError::Temporary)
+}
+
+async fn retry_site() -> vyuh::Site {
+    let bundle = bundles::bundle! { flaky, broken, write };
+    vyuh::Site::build(retry_conf(), bundle).await.unwrap()
+}
+
+#[tokio::test]
+async fn retry_recovers_from_transient_db_error() {
+    let site = retry_site().await;
+    let client = TestClient::new(site.clone());
+
+    let body: String = client
+        .get("/flaky")
+        .send()
+        .await
+        .assert_status(StatusCode::OK)
+        .json()
+        .await;
+
+    assert_eq!(body, "ok");
+    assert_eq!(FLAKY_CALLS.load(Ordering::SeqCst), 2);
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn retry_is_bounded_and_skips_unsafe_methods() {
+    let site = retry_site().await;
+    let client = TestClient::new(site.clone());
+
+    client
+        .get("/broken")
+        .send()
+        .await
+        .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    client
+        .post("/write")
+        .send()
+        .await
+        .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+    assert_eq!(BROKEN_CALLS.load(Ordering::SeqCst), 2);
+    assert_eq!(WRITE_CALLS.load(Ordering::SeqCst), 1);
+    site.shutdown_and_wait().await;
+}
+
+async fn login() -> Json<String> {
+    Json("login".to_string())
+}
+
+async fn profile() -> Json<String> {
+    Json("profile".to_string())
+}
+
+async fn mark_limited(
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let mut response = next.run(req).await;
+    response
+        .headers_mut()
+        .insert("x-limited", axum::http::HeaderValue::from_static("1"));
+    response
+}
+
+fn route_conf(name: &'static str, path: &'static str) -> RouteConf {
+    RouteConf {
+        name: name.into(),
+        methods: Methods::GET,
+        path: path.into(),
+        slash: None,
+    }
+}
+
+#[tokio::test]
+async fn route_layer_applies_only_to_its_route() {
+    let bundle = bundles::bundle([
+        bundles::route(login, route_conf("login", "/login"))
+            .layer(layer_from(axum::middleware::from_fn(mark_limited))),
+        bundles::route(profile, route_conf("profile", "/profile")),
+    ]);
+    let site = vyuh::Site::build(test_conf(), bundle).await.unwrap();
+    let client = TestClient::new(site.clone());
+
+    let login = client
+        .get("/login")
+        .send()
+        .await
+        .assert_status(StatusCode::OK);
+    assert_eq!(
+        login.header("x-limited").and_then(|v| v.to_str().ok()),
+        Some("1")
+    );
+    let profile = client
+        .get("/profile")
+        .send()
+        .await
+        .assert_status(StatusCode::OK);
+    assert!(profile.header("x-limited").is_none());
+
+    site.shutdown_and_wait().await;
+}
+
+#[derive(Clone)]
+struct Tenant(String);
+
+#[derive(Clone)]
+struct Audited(&'static str);
+
+async fn resolve_tenant(
+    mut req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let store = RequestStore::of(req.extensions_mut());
+    store.set(Tenant("acme".to_string()));
+    let mut response = next.run(req).await;
+    if let Some(Audited(action)) = store.get::<Audited>() {
+        response
+            .headers_mut()
+            .insert("x-audited", axum::http::HeaderValue::from_static(action));
+    }
+    response
+}
+
+async fn tenant_name(store: RequestStore) -> Json<String> {
+    store.set(Audited("read"));
+    Json(store.get::<Tenant>().map(|t| t.0).unwrap_or_default())
+}
+
+#[tokio::test]
+async fn request_store_shares_values_between_layer_and_handler() {
+    let bundle = bundles::bundle(
+        [bundles::route(tenant_name, route_conf("tenant", "/tenant"))
+            .layer(layer_from(axum::middleware::from_fn(resolve_tenant)))],
+    );
+    let site = vyuh::Site::build(test_conf(), bundle).await.unwrap();
+    let client = TestClient::new(site.clone());
+
+    let response = client
+        .get("/tenant")
+        .send()
+        .await
+        .assert_status(StatusCode::OK);
+    assert_eq!(
+        response.header("x-audited").and_then(|v| v.to_str().ok()),
+        Some("read")
+    );
+    assert_eq!(response.json::<String>().await, "acme");
+
+    site.shutdown_and_wait().await;
+}
+
+#[bundles::route(path = "/healthz")]
+async fn healthz() -> Json<String> {
+    Json("ok".to_string())
+}
+
+#[bundles::route(path = "/orders")]
+async fn orders() -> Json<String> {
+    Json("orders".to_string())
+}
+
+#[bundles::route(path = "/orders/invalid")]
+async fn invalid_order() -> Result<Json<String>, vyuh::Error> {
+    Err(vyuh::Error::bad_request("order is invalid"))
+}
+
+#[bundles::route(path = "/report")]
+async fn large_report() -> Json<Vec<String>> {
+    Json((0..500).map(|i| format!("row-{i}")).collect())
+}
+
+#[bundles::route(path = "/archive")]
+async fn archive() -> vyuh::routes::Response {
+    ([("content-type", "application/zip")], "PK".repeat(2_000)).into_response()
+}
+
+#[bundles::route(path = "/events")]
+async fn event_stream() -> vyuh::routes::Response {
+    (
+        [("content-type", "text/event-stream")],
+        "data: tick\n\n".repeat(200),
+    )
+        .into_response()
+}
+
+async fn encoding(client: &TestClient, path: &str, accept: &str) -> (Option<String>, usize) {
+    let response = client
+        .get(path)
+        .header("accept-encoding", accept)
+        .send()
+        .await
+        .assert_ok();
+    let encoding = response
+        .header("content-encoding")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned);
+    (encoding, response.bytes().await.len())
+}
+
+#[tokio::test]
+async fn compression_negotiates_gzip_and_skips_streams_and_archives() {
+    let bundle = bundles::bundle! { large_report, archive, event_stream };
+    let site = vyuh::Site::build(test_conf().with_compression(), bundle)
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+    let (gzip, compressed_len) = encoding(&client, "/report", "gzip").await;
+    assert_eq!(gzip.as_deref(), Some("gzip"));
+    let (identity, plain_len) = encoding(&client, "/report", "identity").await;
+    assert_eq!(identity, None);
+    assert!(compressed_len < plain_len);
+    assert_eq!(
+        encoding(&client, "/report", "br").await.0.as_deref(),
+        Some("br")
+    );
+    assert_eq!(encoding(&client, "/archive", "gzip").await.0, None);
+    assert_eq!(encoding(&client, "/events", "gzip").await.0, None);
+
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn rate_limit_rejects_bursts_with_retry_after() {
+    let mut conf = test_conf().with_rate_limit(2, std::time::Duration::from_secs(60));
+    conf.http.rate_limit.per_user = true;
+    conf.secret_key = "rate-limit-test-secret-minimum-32-chars".into();
+    let site = vyuh::Site::build(conf, bundles::bundle! { orders })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+    let token = site
+        .auth()
+        .create_token_pair(vyuh::auth::AuthUser::new("user-1", 0), &[])
+        .unwrap()
+        .access_token;
+
+    for _ in 0..2 {
+        client.get("/orders").send().await.assert_ok();
+    }
+    let limited = client
+        .get("/orders")
+        .send()
+        .await
+        .assert_status(StatusCode::TOO_MANY_REQUESTS);
+    let retry_after = limited.header("retry-after").and_then(|v| v.to_str().ok());
+    assert_eq!(retry_after, Some("30"));
+
+    // Logged-in users are budgeted separately from their IP.
+    client
+        .get("/orders")
+        .header("authorization", &format!("Bearer {token}"))
+        .send()
+        .await
+        .assert_ok();
+
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn reload_conf_applies_new_rate_limits() {
+    let conf = test_conf().with_rate_limit(1, std::time::Duration::from_secs(60));
+    let site = vyuh::Site::build(conf.clone(), bundles::bundle! { orders })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+
+    client.get("/orders").send().await.assert_ok();
+    client
+        .get("/orders")
+        .send()
+        .await
+        .assert_status(StatusCode::TOO_MANY_REQUESTS);
+
+    let mut reloaded = conf.clone();
+    reloaded.http.rate_limit.requests = 0;
+    assert!(site.reload_conf(&reloaded).is_err());
+    reloaded.http.rate_limit.enabled = false;
+    site.reload_conf(&reloaded).unwrap();
+    client.get("/orders").send().await.assert_ok();
+
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn maintenance_mode_blocks_routes_except_health_checks() {
+    let bundle = bundles::bundle! { healthz, orders };
+    let site = vyuh::Site::build(test_conf(), bundle).await.unwrap();
+    let client = TestClient::new(site.clone());
+
+    site.set_maintenance(true);
+    client
+        .get("/orders")
+        .send()
+        .await
+        .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    client
+        .get("/healthz")
+        .send()
+        .await
+        .assert_status(StatusCode::OK);
+
+    site.set_maintenance(false);
+    client
+        .get("/orders")
+        .send()
+        .await
+        .assert_status(StatusCode::OK);
+
+    site.shutdown_and_wait().await;
+}
+
+#[bundles::route(path = "/items/{id}")]
+async fn item(Path(id): Path<i64>) -> Json<i64> {
+    Json(id)
+}
+
+#[bundles::route(path = "/report", head = true)]
+async fn report() -> Json<&'static str> {
+    Json("report")
+}
+
+#[tokio::test]
+async fn head_option_lists_head_and_strips_the_body() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { report })
+        .await
+        .unwrap();
+    let op = site.iter_routes().find(|op| op.name == "report").unwrap();
+    assert_eq!(op.methods, Methods::GET | Methods::HEAD);
+
+    let response = TestClient::new(site.clone())
+        .request(HttpMethod::HEAD, "/report")
+        .send()
+        .await
+        .assert_ok();
+    assert!(response.text().await.is_empty());
+    site.shutdown_and_wait().await;
+}
+
+#[bundles::route(path = "/report", method = "POST", name = "update_report")]
+async fn update_report() -> Json<&'static str> {
+    Json("updated")
+}
+
+#[bundles::route(path = "/report", method = "OPTIONS", name = "report_options")]
+async fn report_options() -> Json<&'static str> {
+    Json("custom")
+}
+
+#[tokio::test]
+async fn options_lists_allowed_methods_for_the_path() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { report, update_report })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+    let response = client
+        .request(HttpMethod::OPTIONS, "/report")
+        .send()
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    assert_eq!(
+        response.header("allow").unwrap(),
+        "GET, POST, HEAD, OPTIONS"
+    );
+    client
+        .request(HttpMethod::OPTIONS, "/missing")
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    site.shutdown_and_wait().await;
+
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { report, report_options })
+        .await
+        .unwrap();
+    let body: String = TestClient::new(site.clone())
+        .request(HttpMethod::OPTIONS, "/report")
+        .send()
+        .await
+        .assert_ok()
+        .json()
+        .await;
+    assert_eq!(body, "custom");
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn unregistered_method_gets_405_with_allow() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { report })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+    let response = client
+        .post("/report")
+        .send()
+        .await
+        .assert_status(StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.header("allow").unwrap(), "GET, HEAD, OPTIONS");
+    site.shutdown_and_wait().await;
+
+    let mut conf = test_conf();
+    conf.http.allow.auto_options = false;
+    let site = vyuh::Site::build(conf, bundles::bundle! { report })
+        .await
+        .unwrap();
+    let response = TestClient::new(site.clone())
+        .delete("/report")
+        .send()
+        .await
+        .assert_status(StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.header("allow").unwrap(), "GET, HEAD");
+    site.shutdown_and_wait().await;
+}
+
+#[bundles::route(path = "/slow")]
+async fn slow() -> Json<&'static str> {
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+    Json("slow")
+}
+
+#[bundles::route(path = "/report-export", timeout_ms = 2000)]
+async fn report_export() -> Json<&'static str> {
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+    Json("exported")
+}
+
+#[bundles::route(path = "/quick", timeout_ms = 20)]
+async fn quick() -> Json<&'static str> {
+    tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+    Json("late")
+}
+
+#[tokio::test]
+async fn route_timeout_replaces_site_timeout() {
+    let mut conf = test_conf();
+    conf.http.timeout.enabled = true;
+    conf.http.timeout.timeout_ms = 50;
+    let site = vyuh::Site::build(conf, bundles::bundle! { slow, report_export, quick })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+    client
+        .get("/slow")
+        .send()
+        .await
+        .assert_status(StatusCode::GATEWAY_TIMEOUT);
+    client.get("/report-export").send().await.assert_ok();
+    client
+        .get("/quick")
+        .send()
+        .await
+        .assert_status(StatusCode::GATEWAY_TIMEOUT);
+    site.shutdown_and_wait().await;
+
+    // Without a site-wide timeout the route enforces its own.
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { slow, quick })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+    client.get("/slow").send().await.assert_ok();
+    client
+        .get("/quick")
+        .send()
+        .await
+        .assert_status(StatusCode::GATEWAY_TIMEOUT);
+    site.shutdown_and_wait().await;
+}
+
+#[bundles::route(path = "/proxy/{*rest}", method = "ANY")]
+async fn proxy() -> Json<&'static str> {
+    Json("proxied")
+}
+
+#[tokio::test]
+async fn any_method_route_accepts_every_method() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { proxy })
+        .await
+        .unwrap();
+    let op = site.iter_routes().find(|op| op.name == "proxy").unwrap();
+    assert_eq!(op.methods, Methods::ANY);
+
+    let client = TestClient::new(site.clone());
+    for method in ["GET", "POST", "DELETE", "PURGE"] {
+        let method = HttpMethod::from_bytes(method.as_bytes()).unwrap();
+        let body: String = client
+            .request(method, "/proxy/a/b")
+            .send()
+            .await
+            .assert_ok()
+            .json()
+            .await;
+        assert_eq!(body, "proxied");
+    }
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn route_inspector_lists_named_routes() {
+    let mut conf = test_conf().with_route_inspector("/_routes");
+    conf.http.route_inspector.require_auth = false;
+    let app = bundles::bundle! { item }
+        .with_prefix("/shop")
+        .with_namespace("shop");
+    let site = vyuh::Site::build(conf.clone(), app).await.unwrap();
+    let client = TestClient::new(site.clone());
+
+    let routes: Vec<RouteInfo> = client.get("/_routes").send().await.assert_ok().json().await;
+    let item = routes.iter().find(|r| r.name == "shop:item").unwrap();
+    assert_eq!(item.path, "/shop/items/{id}");
+    assert_eq!(item.methods, ["GET"]);
+    assert!(item.params.iter().any(|p| p.source == "path"));
+    assert!(routes.iter().any(|r| r.name == "vyuh_route_inspector"));
+    site.shutdown_and_wait().await;
+
+    conf.http.route_inspector.require_auth = true;
+    let site = vyuh::Site::build(conf, bundles::Bundle::new())
+        .await
+        .unwrap();
+    TestClient::new(site.clone())
+        .get("/_routes")
+        .send()
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+    site.shutdown_and_wait().await;
+}
+
+#[cfg(not(any(feature = "postgres", feature = "mysql")))]
+fn health_conf(url: &str) -> SiteConf {
+    test_conf()
+        .database(vyuh::db::DbConf {
+            url: url.to_string(),
+            lazy: true,
+            acquire_timeout_ms: 500,
+            ..Default::default()
+        })
+        .with_healthcheck("/healthz")
+}
+
+#[cfg(not(any(feature = "postgres", feature = "mysql")))]
+#[tokio::test]
+async fn readiness_waits_for_lazy_pool_and_liveness_pings_db() {
+    use vyuh::routes::HealthReport;
+
+    let site = vyuh::Site::build(health_conf("sqlite::memory:"), bundles::Bundle::new())
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+
+    client
+        .get("/healthz/ready")
+        .send()
+        .await
+        .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+    let live: HealthReport = client.get("/healthz").send().await.assert_ok().json().await;
+    assert_eq!(live.status, "ok");
+    assert_eq!(live.database, "ok");
+    assert!(live.pool.size >= 1);
+    assert_eq!(live.pool.in_use + live.pool.idle, live.pool.size);
+    assert_eq!(live.pool.max, site.pool_stats().max);
+    client.get("/healthz/ready").send().await.assert_ok();
+
+    site.shutdown_and_wait().await;
+}
+
+#[cfg(not(any(feature = "postgres", feature = "mysql")))]
+#[tokio::test]
+async fn liveness_fails_when_db_is_unreachable() {
+    use vyuh::routes::HealthReport;
+
+    let conf = health_conf("sqlite:///vyuh-missing-dir/health.db?mode=rw");
+    let site = vyuh::Site::build(conf, bundles::Bundle::new())
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+
+    let live: HealthReport = client
+        .get("/healthz")
+        .send()
+        .await
+        .assert_status(StatusCode::SERVICE_UNAVAILABLE)
+        .json()
+        .await;
+    assert_eq!(live.status, "unavailable");
+    assert_eq!(
+        live.database, "unavailable",
+        "the database error is not exposed"
+    );
+
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn unmatched_path_returns_negotiated_not_found() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { orders, invalid_order })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+
+    let body: serde_json::Value = client
+        .get("/missing")
+        .send()
+        .await
+        .assert_not_found()
+        .json()
+        .await;
+    assert_eq!(body["detail"], "Not found");
+    assert_eq!(body["code"], "NOT_FOUND");
+
+    let page = client
+        .get("/missing")
+        .header("accept", "text/html,application/xhtml+xml")
+        .send()
+        .await
+        .assert_not_found();
+    assert!(
+        page.header("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/html"))
+    );
+    let html = page.text().await;
+    assert!(html.contains("<h1>404 Not Found</h1>"), "{html}");
+
+    // Handler errors keep the JSON fallback when no HTML renderer is set.
+    let body: serde_json::Value = client
+        .get("/orders/invalid")
+        .header("accept", "text/html,application/xhtml+xml")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST)
+        .json()
+        .await;
+    assert_eq!(body["detail"], "Bad request: order is invalid");
+
+    site.shutdown_and_wait().await;
+}
+
+#[bundles::route(path = "/whoami")]
+async fn request_id_echo(id: RequestId) -> Json<String> {
+    Json(id.0)
+}
+
+#[tokio::test]
+async fn request_id_uses_configured_header() {
+    let conf = test_conf().request_id_header("x-correlation-id");
+    let site = vyuh::Site::build(conf, bundles::bundle! { request_id_echo })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+
+    let response = client
+        .get("/whoami")
+        .header("x-correlation-id", "abc-123")
+        .send()
+        .await
+        .assert_ok();
+    assert_eq!(
+        response
+            .header("x-correlation-id")
+            .and_then(|v| v.to_str().ok()),
+        Some("abc-123")
+    );
+    assert_eq!(response.json::<String>().await, "abc-123");
+
+    let response = client.get("/whoami").send().await.assert_ok();
+    let generated = response
+        .header("x-correlation-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned)
+        .unwrap();
+    assert!(uuid::Uuid::parse_str(&generated).is_ok());
+    assert_eq!(response.json::<String>().await, generated);
+
+    site.shutdown_and_wait().await;
+}
+
+fn cors_conf(cors: CorsConf) -> SiteConf {
+    test_conf().http(HttpConf {
+        cors,
+        ..HttpConf::default()
+    })
+}
+
+#[tokio::test]
+async fn cors_preflight_allows_listed_origin_only() {
+    let conf = cors_conf(CorsConf {
+        enabled: true,
+        permissive: false,
+        allow_origins: vec!["https://app.example.com".into()],
+        allow_credentials: true,
+        max_age_secs: Some(600),
+        ..CorsConf::default()
+    });
+    let site = vyuh::Site::build(conf, bundles::bundle! { orders })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+    let preflight = |origin: &'static str| {
+        client
+            .request(vyuh::routes::HttpMethod::OPTIONS, "/orders")
+            .header("origin", origin)
+            .header("access-control-request-method", "POST")
+            .header("access-control-request-headers", "content-type")
+    };
+
+    let allowed = preflight("https://app.example.com").send().await;
+    let header = |name| {
+        allowed
+            .header(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned)
+    };
+    assert_eq!(
+        header("access-control-allow-origin").as_deref(),
+        Some("https://app.example.com")
+    );
+    assert_eq!(
+        header("access-control-allow-credentials").as_deref(),
+        Some("true")
+    );
+    assert_eq!(header("access-control-max-age").as_deref(), Some("600"));
+    assert!(header("access-control-allow-methods").is_some_and(|m| m.contains("POST")));
+
+    let denied = preflight("https://evil.example.com").send().await;
+    assert!(denied.header("access-control-allow-origin").is_none());
+
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn cors_rejects_credentials_with_wildcard_origin() {
+    let conf = cors_conf(CorsConf {
+        enabled: true,
+        permissive: false,
+        allow_origins: vec!["*".into()],
+        allow_credentials: true,
+        ..CorsConf::default()
+    });
+    let err = vyuh::Site::build(conf, bundles::Bundle::new())
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("http.cors.allow_origins"), "{err}");
+}
@@ -0,0 +1,150 @@
+#![cfg(not(any(feature = "postgres", feature = "mysql")))]
+
+use std::{sync::Mutex, time::Duration};
+
+use futures::future::BoxFuture;
+use vyuh::{
+    Site, SiteConf, bundles,
+    db::{self, FilteredBuilder},
+    outbox::{self, Outbox, OutboxError, OutboxHandler, OutboxMessage},
+    services::ServiceInstance,
+};
+
+fn test_conf() -> SiteConf {
+    SiteConf {
+        log_init: false,
+        logging: vyuh::logging::LoggingConf {
+            env_prefix: None,
+            rules: vec![],
+        },
+        ..SiteConf::default()
+    }
+}
+
+static RECEIVED: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+struct RecordingHandler;
+
+impl OutboxHandler for RecordingHandler {
+    fn dispatch<'a>(
+        &'a self,
+        _site: &'a Site,
+        message: &'a OutboxMessage,
+    ) -> BoxFuture<'a, Result<(), OutboxError>> {
+        Box::pin(async move {
+            let order: String = message.payload()?;
+            if let Ok(mut received) = RECEIVED.lock() {
+                received.push((message.topic.clone(), order));
+            }
+            Ok(())
+        })
+    }
+}
+
+struct FailingHandler;
+
+impl OutboxHandler for FailingHandler {
+    fn dispatch<'a>(
+        &'a self,
+        _site: &'a Site,
+        _message: &'a OutboxMessage,
+    ) -> BoxFuture<'a, Result<(), OutboxError>> {
+        Box::pin(async { Err(OutboxError::Dispatch("broker down".to_string())) })
+    }
+}
+
+async fn recording_outbox() -> ServiceInstance<Outbox> {
+    Outbox::new(RecordingHandler)
+        .poll_interval(Duration::from_millis(20))
+        .into()
+}
+
+#[tokio::test]
+async fn committed_outbox_rows_are_dispatched_once() {
+    let bundle = bundles::bundle([bundles::service(recording_outbox)]);
+    let site = vyuh::Site::build(test_conf(), bundle).await.unwrap();
+    let pool = site.db();
+    outbox::run_migrations(&mut pool.clone()).await.unwrap();
+
+    let mut tx = pool.begin().await.unwrap();
+    let id = outbox::enqueue(&mut tx, "order_placed", &"order-1".to_string())
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+    let mut tx = pool.begin().await.unwrap();
+    outbox::enqueue(&mut tx, "order_placed", &"order-2".to_string())
+        .await
+        .unwrap();
+    tx.rollback().await.unwrap();
+
+    let mut dispatched = None;
+    for _ in 0..100 {
+        dispatched = db::select("vyuh_outbox")
+            .filter("id = :id")
+            .bind_as("id", id.clone())
+            .first::<OutboxMessage, _>(&mut pool.clone())
+            .await
+            .unwrap()
+            .and_then(|message| message.dispatched_at);
+        if dispatched.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    assert!(dispatched.is_some());
+    assert_eq!(
+        RECEIVED.lock().unwrap().clone(),
+        vec![("order_placed".to_string(), "order-1".to_string())]
+    );
+    site.shutdown_and_wait().await;
+}
+
+async fn pending(pool: &db::DbPool, id: &str) -> OutboxMessage {
+    db::select("vyuh_outbox")
+        .filter("id = :id")
+        .bind_as("id", id.to_string())
+        .one::<OutboxMessage, _>(&mut pool.clone())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn failing_messages_back_off_between_attempts() {
+    let failing_outbox = || async {
+        ServiceInstance::from(
+            Outbox::new(FailingHandler)
+                .batch_size(1)
+                .poll_interval(Duration::from_millis(20))
+                .retry_backoff(Duration::from_secs(60), Duration::from_secs(600)),
+        )
+    };
+    let bundle = bundles::bundle([bundles::service(failing_outbox)]);
+    let site = vyuh::Site::build(test_conf(), bundle).await.unwrap();
+    let pool = site.db();
+    outbox::run_migrations(&mut pool.clone()).await.unwrap();
+
+    let mut tx = pool.begin().await.unwrap();
+    let id = outbox::enqueue(&mut tx, "order_placed", &"order-3".to_string())
+        .await
+        .unwrap();
+    tx.commit().await.unwrap();
+    for _ in 0..100 {
+        if pending(&pool, &id).await.attempts > 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    // Several poll intervals pass, but the row is not due again for a minute.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let message = pending(&pool, &id).await;
+    assert_eq!(message.attempts, 1);
+    assert_eq!(
+        message.last_error.as_deref(),
+        Some("outbox dispatch failed: broker down")
+    );
+    let retry_in = message.next_attempt_at.unwrap() - chrono::Utc::now().timestamp_millis();
+    assert!(retry_in > 50_000, "{retry_in}");
+    site.shutdown_and_wait().await;
+}
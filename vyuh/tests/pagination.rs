@@ -0,0 +1,80 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use vyuh::{
+    Site, SiteConf, bundles,
+    db::Page,
+    routes::{Json, PageHeaders},
+    testing::TestClient,
+};
+
+fn test_conf() -> SiteConf {
+    SiteConf {
+        log_init: false,
+        logging: vyuh::logging::LoggingConf {
+            env_prefix: None,
+            rules: vec![],
+        },
+        ..SiteConf::default()
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+struct PageQuery {
+    page: usize,
+    per_page: usize,
+}
+
+#[bundles::route(path = "/orders")]
+async fn orders(
+    site: Site,
+    vyuh::routes::Query(query): vyuh::routes::Query<PageQuery>,
+) -> (PageHeaders, Json<Vec<u32>>) {
+    let page = Page {
+        items: vec![1, 2],
+        total: 45,
+        page: query.page,
+        per_page: query.per_page,
+        total_pages: 3,
+    };
+    let headers = PageHeaders::new(&page, &site, "orders", &[("status", "open")]);
+    (headers, Json(page.items))
+}
+
+#[tokio::test]
+async fn paginated_response_sets_link_and_total_headers() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { orders })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+
+    let response = client
+        .get("/orders?page=2&per_page=20")
+        .send()
+        .await
+        .assert_ok();
+    let header = |name| {
+        response
+            .header(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string()
+    };
+    assert_eq!(header("x-total-count"), "45");
+    let link = header("link");
+    assert!(link.contains("</orders?page=1&per_page=20&status=open>; rel=\"prev\""));
+    assert!(link.contains("</orders?page=3&per_page=20&status=open>; rel=\"next\""));
+    assert!(link.contains("rel=\"first\"") && link.contains("rel=\"last\""));
+
+    let last = client
+        .get("/orders?page=3&per_page=20")
+        .send()
+        .await
+        .assert_ok();
+    let link = last
+        .header("link")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    assert!(!link.contains("rel=\"next\""));
+
+    site.shutdown_and_wait().await;
+}
@@ -0,0 +1,134 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use vyuh::{
+    SiteConf, bundles,
+    routes::{Html, StatusCode},
+    templates::{TemplateError, Templates},
+    testing::TestClient,
+};
+
+fn test_conf() -> SiteConf {
+    SiteConf {
+        log_init: false,
+        logging: vyuh::logging::LoggingConf {
+            env_prefix: None,
+            rules: vec![],
+        },
+        ..SiteConf::default()
+    }
+}
+
+static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+#[bundles::route(path = "/about")]
+async fn about(templates: Templates) -> Result<Html<String>, TemplateError> {
+    templates
+        .cached_html("about", Duration::from_secs(60), || async {
+            RENDERS.fetch_add(1, Ordering::SeqCst);
+            templates
+                .render_str(
+                    "<h1>{{ title }}</h1>",
+                    &serde_json::json!({ "title": "About" }),
+                )
+                .map(Html)
+        })
+        .await
+}
+
+#[tokio::test]
+async fn cached_html_skips_rendering_while_fresh() {
+    let site = vyuh::Site::build(test_conf(), bundles::bundle! { about })
+        .await
+        .unwrap();
+    let client = TestClient::new(site.clone());
+
+    for _ in 0..2 {
+        client
+            .get("/about")
+            .send()
+            .await
+            .assert_text(StatusCode::OK, "<h1>About</h1>")
+            .await;
+    }
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+
+    site.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn formatting_filters_use_site_timezone() {
+    let conf = SiteConf {
+        tz: Some("Asia/Kolkata".to_string()),
+        ..test_conf()
+    };
+    let site = vyuh::Site::build(conf, bundles::Bundle::new())
+        .await
+        .unwrap();
+    let render = |source: &str, amount: serde_json::Value| {
+        site.render_template_string(source, &serde_json::json!({ "amount": amount }))
+            .unwrap()
+    };
+
+    assert_eq!(
+        render("{{ amount | currency('USD') }}", 1234.5.into()),
+        "$1,234.50"
+    );
+    assert_eq!(
+        render("{{ amount | currency('JPY') }}", (-98765).into()),
+        "-¥98,765"
+    );
+    assert_eq!(
+        render("{{ amount | currency('CHF') }}", 12.into()),
+        "CHF 12.00"
+    );
+    assert_eq!(render("{{ amount | number }}", 1234567.into()), "1,234,567");
+    assert_eq!(render("{{ amount | number(1) }}", 0.26.into()), "0.3");
+    assert_eq!(
+        render("{{ amount | date }}", "2024-03-31T20:00:00Z".into()),
+        "2024-04-01"
+    );
+    assert_eq!(
+        render("{{ amount | date('%d/%m %H:%M') }}", 0.into()),
+        "01/01 05:30"
+    );
+    assert_eq!(render("{{ timezone }}", 0.into()), "Asia/Kolkata");
+    assert_eq!(site.now_local().timezone(), chrono_tz::Asia::Kolkata);
+
+    site.shutdown_and_wait().await;
+}
+
+#[bundles::route(path = "/orders/{id}")]
+async fn order() -> Html<String> {
+    Html("order".into())
+}
+
+#[tokio::test]
+async fn custom_filters_globals_and_url_reversal() {
+    let conf = test_conf()
+        .with_template_filter("shout", |value: String| value.to_uppercase())
+        .with_template_filter("number", |value: i64| format!("#{value}"))
+        .with_template_global("brand", "Vyuh")
+        .with_template_global(
+            "greet",
+            minijinja::Value::from_function(|name: String| format!("hi {name}")),
+        );
+    let site = vyuh::Site::build(conf, bundles::bundle! { order })
+        .await
+        .unwrap();
+    let render = |source: &str| site.render_template_string(source, &());
+
+    assert_eq!(render("{{ brand | shout }}").unwrap(), "VYUH");
+    assert_eq!(render("{{ greet('ada') }}").unwrap(), "hi ada");
+    assert_eq!(render("{{ 7 | number }}").unwrap(), "#7");
+    assert_eq!(
+        render("{{ url('order', id=7, tab='items') }}").unwrap(),
+        "/orders/7?tab=items"
+    );
+    assert!(render("{{ url('order') }}").is_err());
+    assert!(render("{{ url('missing') }}").is_err());
+
+    site.shutdown_and_wait().await;
+}
@@ -2,6 +2,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use vyuh::file_storage::{StorageName, UploadConf};
+use vyuh::routes::axum_extractors::Multipart;
 use vyuh::routes::multipart::{FieldRule, FileRule, MultipartMap, MultipartSpec, UploadedFile};
 use vyuh::routes::{Json, MultipartForm, StatusCode};
 use vyuh::{Data, Error, Site, SiteConf, Validate, bundles};
@@ -72,6 +73,17 @@ async fn macro_less_upload(site: Site, form: MultipartMap) -> Result<Data<Upload
     }))
 }
 
+#[bundles::route(path = "/raw", method = "POST")]
+async fn raw_upload(site: Site, mut form: Multipart) -> Result<Json<String>, Error> {
+    let field = form
+        .next_field()
+        .await
+        .map_err(|err| Error::bad_request(err.body_text()))?
+        .ok_or_else(|| Error::bad_request("missing file"))?;
+    let path = site.save_upload(field).await?;
+    Ok(Json(path.to_string_lossy().into_owned()))
+}
+
 fn multipart_body(boundary: &str, file_name: &str, content_type: &str, file: &[u8]) -> Vec<u8> {
     let mut body = Vec::new();
     body.extend_from_slice(
@@ -121,6 +133,7 @@ async fn upload_site() -> (vyuh::Site, tempfile::TempDir) {
         bundles::bundle! {
             typed_upload,
             macro_less_upload,
+            raw_upload,
         },
     )
     .await
@@ -247,6 +260,59 @@ async fn oversized_file_is_rejected() {
     site.shutdown_and_wait().await;
 }
 
+fn raw_file_body(boundary: &str, file_name: &str, file: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(file);
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+    body
+}
+
+#[tokio::test]
+async fn save_upload_streams_into_upload_dir() {
+    let (site, dir) = upload_site().await;
+    let client = vyuh::testing::TestClient::new(site.clone());
+    let boundary = "vyuh-boundary";
+    let upload = |file_name: &str, file: &[u8]| {
+        client
+            .post("/raw")
+            .header(
+                "content-type",
+                &format!("multipart/form-data; boundary={boundary}"),
+            )
+            .body(axum::body::Body::from(raw_file_body(
+                boundary, file_name, file,
+            )))
+            .send()
+    };
+
+    let path: String = upload("my report.txt", b"hello")
+        .await
+        .assert_status(StatusCode::OK)
+        .json()
+        .await;
+    let path = std::path::PathBuf::from(path);
+    assert_eq!(path.parent(), Some(dir.path().join("uploads").as_path()));
+    assert!(path.to_string_lossy().ends_with("-my_report.txt"));
+    assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+
+    upload("../../etc/passwd", b"root")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    upload("big.bin", &[7; 200])
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+    let stored = std::fs::read_dir(dir.path().join("uploads"))
+        .unwrap()
+        .count();
+    assert_eq!(stored, 1);
+    assert!(!dir.path().join("etc").exists());
+
+    site.shutdown_and_wait().await;
+}
+
 #[test]
 fn unsafe_storage_names_are_rejected() {
     assert!(StorageName::new("../avatar.png").is_err());
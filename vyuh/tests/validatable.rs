@@ -203,3 +203,311 @@ fn test_delegate_and_custom() {
         assert!(report.has_error("custom_field"));
     }
 }
+
+#[derive(Validate)]
+struct Contact {
+    #[validate(phone)]
+    mobile: String,
+    #[validate(phone)]
+    landline: Option<String>,
+}
+
+#[test]
+fn test_phone_attribute() {
+    let valid = Contact {
+        mobile: "+14155552671".to_string(),
+        landline: None,
+    };
+    assert!(valid.validate().is_ok());
+
+    let invalid = Contact {
+        mobile: "4155552671".to_string(),
+        landline: Some("+1 415 555".to_string()),
+    };
+    let report = invalid.validate().expect_err("invalid phones should fail");
+    assert!(report.has_error("mobile"));
+    assert!(report.has_error("landline"));
+}
+
+#[derive(Validate)]
+struct Endpoint {
+    #[validate(ip)]
+    host: String,
+    #[validate(ipv6)]
+    loopback: String,
+}
+
+#[test]
+fn test_ip_attributes() {
+    let valid = Endpoint {
+        host: "::ffff:192.168.0.1".to_string(),
+        loopback: "::1".to_string(),
+    };
+    assert!(valid.validate().is_ok());
+
+    let v4 = Endpoint {
+        host: "10.0.0.1".to_string(),
+        loopback: "::1".to_string(),
+    };
+    assert!(v4.validate().is_ok());
+
+    let invalid = Endpoint {
+        host: "10.0.0".to_string(),
+        loopback: "127.0.0.1".to_string(),
+    };
+    let report = invalid
+        .validate()
+        .expect_err("invalid addresses should fail");
+    assert!(report.has_error("host"));
+    assert!(report.has_error("loopback"));
+}
+
+#[test]
+fn test_merge_db_error_maps_unique_violation_to_field() {
+    use vyuh::db::{DbError, IntegrityKind};
+    use vyuh::validation::{ValidationReport, merge_db_error};
+
+    let user = UserRegistration {
+        email: "user@example.com".to_string(),
+        password: "secure_password".to_string(),
+        name: "John".to_string(),
+        age: 25,
+        website: None,
+    };
+    let report = user
+        .validate()
+        .err()
+        .unwrap_or_else(ValidationReport::empty);
+    assert!(report.is_empty());
+
+    let error = DbError::Integrity {
+        kind: IntegrityKind::Unique,
+        constraint: Some("users_email_key".to_string()),
+        source: sqlx::Error::Protocol("duplicate key".to_string()),
+    };
+    let report = merge_db_error(report, error, &[("users_email_key", "email")])
+        .expect("integrity errors should merge");
+
+    assert!(report.has_error("email"));
+    let errors = report.to_nested_errors();
+    assert_eq!(errors["email"][0]["code"], "unique");
+    assert_eq!(
+        errors["email"][0]["params"]["constraint"],
+        "users_email_key"
+    );
+
+    let passthrough = merge_db_error(ValidationReport::empty(), DbError::DoesNotExist, &[]);
+    assert!(matches!(passthrough, Err(DbError::DoesNotExist)));
+}
+
+#[test]
+fn nested_map_round_trips_into_report() {
+    use vyuh::validation::{Path, ValidationError, ValidationReport};
+
+    let mut report = ValidationReport::empty();
+    report.push_root(ValidationError::new("mismatch", "Passwords differ"));
+    report.push(
+        Path::root().at_field("email"),
+        ValidationError::new("email", "Invalid email").with_param("value", "nope"),
+    );
+    report.push(
+        Path::root().at_field("items").at_index(1).at_field("qty"),
+        ValidationError::new("min", "Too small"),
+    );
+
+    let errors = report.to_nested_errors();
+    let parsed = ValidationReport::from_nested_map(&errors);
+    assert_eq!(parsed.issues.len(), 3);
+    assert_eq!(parsed.to_nested_errors(), errors);
+    assert!(parsed.has_error("items.1.qty"));
+
+    let messages = report.to_nested_map();
+    let parsed = ValidationReport::from_nested_map(&messages);
+    assert_eq!(parsed.to_nested_map(), messages);
+    assert_eq!(parsed.issues[0].invalid.code, "custom");
+}
+
+#[test]
+fn nested_map_keys_are_sorted_regardless_of_push_order() {
+    use vyuh::validation::{Path, ValidationError, ValidationReport};
+
+    let issues = [
+        Path::root().at_field("zip"),
+        Path::root().at_field("items").at_index(0).at_field("sku"),
+        Path::root().at_field("items").at_index(0).at_field("qty"),
+        Path::root().at_field("address").at_field("street"),
+        Path::root().at_field("address").at_field("city"),
+    ];
+    let build = |order: &[usize]| {
+        let mut report = ValidationReport::empty();
+        for &idx in order {
+            report.push(issues[idx].clone(), ValidationError::new("bad", "first"));
+        }
+        report.push(issues[0].clone(), ValidationError::new("bad", "second"));
+        report
+    };
+
+    let forward = build(&[0, 1, 2, 3, 4]).to_nested_pretty();
+    let backward = build(&[4, 3, 2, 1, 0]).to_nested_pretty();
+    assert_eq!(forward, backward);
+    assert_eq!(
+        serde_json::to_string(&build(&[0, 1, 2, 3, 4]).to_nested_map()).unwrap(),
+        r#"{"address":{"city":["first"],"street":["first"]},"items":[{"qty":["first"],"sku":["first"]}],"zip":["first","second"]}"#
+    );
+}
+
+#[test]
+fn dedup_codes_collapses_repeated_path_and_code() {
+    use vyuh::validation::{Path, ValidationError, ValidationReport};
+
+    let mut report = ValidationReport::empty();
+    let name = Path::root().at_field("name");
+    report.push(name.clone(), ValidationError::new("required", "Required"));
+    report.push(
+        name.clone(),
+        ValidationError::new("min_length", "Too short"),
+    );
+    report.push(
+        name.clone(),
+        ValidationError::new("required", "Name is required"),
+    );
+    report.push(
+        Path::root().at_field("email"),
+        ValidationError::new("required", "Required"),
+    );
+
+    let report = report.deduped();
+    assert_eq!(report.issues.len(), 3);
+    assert_eq!(
+        report.to_nested_map()["name"],
+        serde_json::json!(["Required", "Too short"])
+    );
+}
+
+/// A database model shares its field attributes with the `Validate` derive, so
+/// the same struct carries column metadata and request-time rules.
+#[derive(vyuh::db::Bindable, vyuh::db::Scannable, Validate)]
+struct Account {
+    #[column(primary_key, serial, insertable = false)]
+    #[allow(dead_code)]
+    id: i64,
+
+    #[column(unique)]
+    #[validate(email)]
+    email: String,
+
+    #[column(name = "display_name")]
+    #[validate(min_length = 2)]
+    name: String,
+}
+
+#[test]
+fn model_columns_and_validation_compose() {
+    let account = Account {
+        id: 0,
+        email: "not-an-email".to_string(),
+        name: "x".to_string(),
+    };
+    let report = account.validate().unwrap_err();
+    assert!(report.has_error("email"));
+    assert!(report.has_error("name"));
+
+    let account = Account {
+        id: 0,
+        email: "a@example.com".to_string(),
+        name: "Ada".to_string(),
+    };
+    assert!(account.validate().is_ok());
+}
+
+#[test]
+fn push_field_keys_hand_written_errors() {
+    use vyuh::validation::{ValidationError, ValidationReport};
+
+    struct Basket {
+        items: Vec<String>,
+        discount: f64,
+    }
+
+    impl Validate for Basket {
+        fn validate(&self) -> Result<(), ValidationReport> {
+            let mut report = ValidationReport::empty();
+            if self.items.is_empty() {
+                report.push_field("items", ValidationError::new("empty", "Add an item."));
+            }
+            if self.discount < 0.0 {
+                report.push_field(
+                    "discount",
+                    ValidationError::new("min_value", "Discount cannot be negative."),
+                );
+            }
+            if report.is_empty() {
+                Ok(())
+            } else {
+                Err(report)
+            }
+        }
+    }
+
+    let basket = Basket {
+        items: Vec::new(),
+        discount: -1.0,
+    };
+    let report = basket.validate().unwrap_err();
+    let flat = report.to_field_map_flat();
+    assert_eq!(flat["items"], vec!["Add an item.".to_string()]);
+    assert_eq!(flat["discount"].len(), 1);
+    assert!(!flat.contains_key("non_field_errors"));
+}
+
+#[derive(Validate)]
+struct Cancellation {
+    reason: String,
+    #[validate(required_if = r#"reason == "other""#)]
+    other_reason: Option<String>,
+    notify: bool,
+    #[validate(required_if = "notify", email)]
+    contact: Option<String>,
+    kind: i32,
+    #[validate(required_if = "kind == 3", min_length = 2)]
+    note: String,
+}
+
+#[test]
+fn required_if_keys_errors_to_the_conditional_field() {
+    let ok = Cancellation {
+        reason: "price".to_string(),
+        other_reason: None,
+        notify: false,
+        contact: None,
+        kind: 1,
+        note: "ok".to_string(),
+    };
+    assert!(ok.validate().is_ok());
+
+    let missing = Cancellation {
+        reason: "other".to_string(),
+        other_reason: None,
+        notify: true,
+        contact: None,
+        kind: 3,
+        note: "   ".to_string(),
+    };
+    let report = missing.validate().unwrap_err();
+    let errors = report.to_nested_errors();
+    assert_eq!(errors["other_reason"][0]["code"], "required");
+    assert_eq!(errors["other_reason"][0]["params"]["required_if"], "reason");
+    assert_eq!(errors["contact"][0]["code"], "required");
+    assert_eq!(errors["note"][0]["code"], "required");
+    assert!(!report.has_error("reason"));
+
+    let present = Cancellation {
+        reason: "other".to_string(),
+        other_reason: Some("moving".to_string()),
+        notify: true,
+        contact: Some("a@example.com".to_string()),
+        kind: 3,
+        note: "gone".to_string(),
+    };
+    assert!(present.validate().is_ok());
+}
@@ -442,3 +442,66 @@ async fn html_error_renderer_can_be_forced_by_config() {
 
     site.shutdown_and_wait().await;
 }
+
+#[derive(Clone, Default)]
+struct CapturedLogs(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Ok(mut logs) = self.0.lock() {
+            logs.extend_from_slice(buf);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl CapturedLogs {
+    fn contents(&self) -> String {
+        let logs = self.0.lock().map(|logs| logs.clone()).unwrap_or_default();
+        String::from_utf8_lossy(&logs).into_owned()
+    }
+}
+
+#[tokio::test]
+async fn validation_failures_can_be_logged() {
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let conf = test_conf().errors(ErrorConf::default().log_validation(true));
+    let site = vyuh::Site::build(
+        conf,
+        bundles::bundle! {
+            valid_json,
+        },
+    )
+    .await
+    .unwrap();
+    let client = TestClient::new(site.clone());
+
+    client
+        .post("/valid")
+        .json(&serde_json::json!({
+            "email": "not-an-email",
+            "name": "x"
+        }))
+        .send()
+        .await
+        .assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+
+    let output = logs.contents();
+    assert!(output.contains("request validation failed"));
+    assert!(output.contains("issues=2"));
+    assert!(output.contains("email:email=1"));
+    assert!(output.contains("name:min_length=1"));
+
+    site.shutdown_and_wait().await;
+}
@@ -1,7 +1,7 @@
 use vyuh::validation::{
-    alphanumeric, date, datetime, digits, email, exact_len, ipv4, ipv6, max, max_chars, max_items,
-    max_len, min, min_chars, min_items, min_len, multiple_of, non_empty, non_empty_vec, phone_e164,
-    present, range, slug, unique_items, url, uuid,
+    alphanumeric, date, datetime, digits, email, exact_len, ip, ipv4, ipv6, max, max_chars,
+    max_items, max_len, min, min_chars, min_items, min_len, multiple_of, non_empty, non_empty_vec,
+    phone, phone_e164, present, range, slug, unique_items, url, uuid,
 };
 
 #[test]
@@ -167,10 +167,39 @@ fn test_ipv4() {
     assert!(ipv4("").is_err());
 }
 
+#[test]
+fn test_phone() {
+    // Valid E.164 numbers
+    assert!(phone("+14155552671").is_ok());
+    assert!(phone("+442071838750").is_ok());
+    assert!(phone("+123456789012345").is_ok()); // 15 digits
+
+    // Invalid numbers
+    assert!(phone("14155552671").is_err()); // missing +
+    assert!(phone("+1234567890123456").is_err()); // 16 digits
+    assert!(phone("+1 415 555 2671").is_err()); // spaces
+    assert!(phone("+1-415-555-2671").is_err()); // dashes
+    assert!(phone("+04155552671").is_err()); // leading zero country code
+    assert!(phone("+").is_err());
+    assert!(phone("").is_err());
+
+    let err = phone("abc").unwrap_err();
+    assert_eq!(err.code, "phone");
+}
+
 #[test]
 fn test_additional_formats() {
     assert!(ipv6("2001:db8::1").is_ok());
     assert!(ipv6("not-ipv6").is_err());
+    assert!(ipv6("::1").is_ok());
+    assert!(ipv6("::ffff:192.168.0.1").is_ok());
+    assert!(ipv6("192.168.0.1").is_err());
+
+    assert!(ip("::1").is_ok());
+    assert!(ip("::ffff:192.168.0.1").is_ok());
+    assert!(ip("192.168.0.1").is_ok());
+    assert!(ip("192.168.0.256").is_err());
+    assert_eq!(ip("not-an-ip").unwrap_err().code, "ip");
 
     assert!(phone_e164("+14155552671").is_ok());
     assert!(phone_e164("4155552671").is_err());
@@ -326,3 +355,64 @@ fn test_unicode_validators() {
     assert!(validator("café").is_ok()); // 4 chars
     assert!(validator("hello").is_ok()); // 5 chars
 }
+
+#[test]
+fn test_validation_builder_collects_heterogeneous_checks() {
+    use vyuh::validation::{Path, ValidationBuilder};
+
+    let name = String::from("x");
+    let report = ValidationBuilder::new(&name)
+        .at(Path::root().at_field("name"))
+        .check(non_empty)
+        .check(min_len(3))
+        .check(slug)
+        .check(|value: &String| {
+            if value.starts_with('_') {
+                Err(vyuh::ValidationError::custom("No leading underscore."))
+            } else {
+                Ok(())
+            }
+        })
+        .into_report();
+    assert_eq!(report.issues.len(), 1);
+    assert!(report.has_error("name"));
+    assert_eq!(report.issues[0].invalid.code, "min_length");
+
+    let tags = vec![1, 1];
+    let result = ValidationBuilder::new(&tags)
+        .check(min_items(1))
+        .check(unique_items)
+        .finish();
+    let report = result.expect_err("duplicate tags should fail");
+    assert_eq!(report.issues[0].invalid.code, "unique_items");
+    assert!(report.issues[0].path.is_root());
+
+    assert!(
+        ValidationBuilder::new(&20)
+            .check(range(18, 120))
+            .finish()
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_decimal_range_and_places() {
+    use vyuh::rust_decimal::Decimal;
+    use vyuh::validation::max_decimal_places;
+
+    let price = range(Decimal::new(1, 2), Decimal::new(100_000, 2));
+    assert!(price(&Decimal::new(1999, 2)).is_ok());
+    assert!(price(&Decimal::new(1, 2)).is_ok());
+    assert!(price(&Decimal::ZERO).is_err());
+    let err = price(&Decimal::new(100_001, 2)).unwrap_err();
+    assert_eq!(err.code, "value_range");
+    assert!(err.message.contains("1000.00"));
+
+    let cents = max_decimal_places(2);
+    assert!(cents(&Decimal::new(1999, 2)).is_ok());
+    assert!(cents(&Decimal::new(19, 0)).is_ok());
+    assert_eq!(
+        cents(&Decimal::new(19999, 3)).unwrap_err().code,
+        "max_decimal_places"
+    );
+}